@@ -0,0 +1,116 @@
+//! Module implement CIDs for Bitcoin IPLD blocks. _Refer [bitcoin-ipld]
+//! for detail_.
+//!
+//! Bitcoin objects are addressed by `dbl-sha2-256` of their binary
+//! encoding, the 80 byte header for blocks and the serialized transaction
+//! for transactions. CIDs carry that hash as `dbl-sha2-256` multihash
+//! with one of the `bitcoin-*` codecs.
+//!
+//! Chain-native hashes, block hash and txid, are displayed in reverse
+//! byte order, refer [parse_hash] and [format_hash]. Transaction id of a
+//! segwit transaction is the hash of its serialization without witness.
+//!
+//! [bitcoin-ipld]: https://ipld.io/specs/codecs/dag-bitcoin
+
+use alloc::{string::String, vec::Vec};
+use core::convert::TryInto;
+
+use crate::{
+    cid::{Cid, Version},
+    multibase::Base,
+    multicodec::{self, Multicodec},
+    multihash::Multihash,
+    Error, Result,
+};
+
+/// Size of a block header.
+pub const HEADER_LEN: usize = 80;
+
+/// Type of Bitcoin object, one for each `bitcoin-*` codec.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Kind {
+    /// Block header.
+    Block,
+    Tx,
+    WitnessCommitment,
+}
+
+impl Kind {
+    /// Create kind from its `bitcoin-*` codec.
+    pub fn from_codec(codec: Multicodec) -> Result<Kind> {
+        let val = match codec.to_code() {
+            multicodec::BITCOIN_BLOCK => Kind::Block,
+            multicodec::BITCOIN_TX => Kind::Tx,
+            multicodec::BITCOIN_WITNESS_COMMITMENT => Kind::WitnessCommitment,
+            code => err_at!(BadInput, msg: "bitcoin codec 0x{:x}", code)?,
+        };
+        Ok(val)
+    }
+
+    /// Return the `bitcoin-*` codec for this kind.
+    pub fn to_codec(&self) -> Multicodec {
+        let code = match self {
+            Kind::Block => multicodec::BITCOIN_BLOCK,
+            Kind::Tx => multicodec::BITCOIN_TX,
+            Kind::WitnessCommitment => multicodec::BITCOIN_WITNESS_COMMITMENT,
+        };
+        code.into()
+    }
+}
+
+/// Compute CID for object of `kind` from its encoded bytes `data`. Block
+/// shall be the 80 byte header, without transactions.
+pub fn to_cid(kind: Kind, data: &[u8]) -> Result<Cid> {
+    match (kind, data.len()) {
+        (Kind::Block, HEADER_LEN) => (),
+        (Kind::Block, n) => err_at!(BadInput, msg: "bitcoin header length {}", n)?,
+        (_, 0) => err_at!(BadInput, msg: "bitcoin {:?} empty", kind)?,
+        (_, _) => (),
+    }
+
+    let mh = Multihash::new(multicodec::DBL_SHA2_256.into(), data)?;
+    Cid::new_v1(kind.to_codec(), mh)
+}
+
+/// Create CID for object of `kind` from its hash, in internal byte order.
+pub fn from_hash(kind: Kind, hash: &[u8; 32]) -> Result<Cid> {
+    let mh = Multihash::from_digest(multicodec::DBL_SHA2_256.into(), hash)?;
+    Cid::new_v1(kind.to_codec(), mh)
+}
+
+/// Return the object kind and its hash, in internal byte order, from `cid`.
+pub fn to_hash(cid: &Cid) -> Result<(Kind, [u8; 32])> {
+    if cid.to_version() != Version::V1 {
+        err_at!(BadInput, msg: "bitcoin CID version {:?}", cid.to_version())?
+    }
+    let kind = Kind::from_codec(cid.to_codec())?;
+    let (codec, digest) = cid.to_multihash()?.unwrap()?;
+    match (codec.to_code(), digest.len()) {
+        (multicodec::DBL_SHA2_256, 32) => Ok((kind, digest.as_slice().try_into().unwrap())),
+        (multicodec::DBL_SHA2_256, n) => err_at!(BadInput, msg: "bitcoin CID digest length {}", n),
+        (_, _) => err_at!(BadInput, msg: "bitcoin CID multihash {}", codec),
+    }
+}
+
+/// Parse hash from its display form, hex in reverse byte order, into
+/// internal byte order.
+pub fn parse_hash(text: &str) -> Result<[u8; 32]> {
+    let mut data = match text.len() {
+        64 => Base::Base16Lower.decode(&text.to_ascii_lowercase())?,
+        _ => err_at!(BadInput, msg: "bitcoin hash {:?}", text)?,
+    };
+    data.reverse();
+    Ok(data.as_slice().try_into().unwrap())
+}
+
+/// Format hash, in internal byte order, in its display form, hex in
+/// reverse byte order.
+pub fn format_hash(hash: &[u8; 32]) -> Result<String> {
+    let mut data: Vec<u8> = hash.to_vec();
+    data.reverse();
+    Base::Base16Lower.encode(&data)
+}
+
+#[cfg(test)]
+#[path = "bitcoin_test.rs"]
+mod bitcoin_test;
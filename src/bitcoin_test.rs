@@ -0,0 +1,72 @@
+use super::*;
+
+// Genesis block header and its coinbase transaction.
+const GENESIS_HEADER: &str = concat!(
+    "01000000",
+    "0000000000000000000000000000000000000000000000000000000000000000",
+    "3ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a",
+    "29ab5f49ffff001d1dac2b7c",
+);
+const GENESIS_HASH: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+const GENESIS_TX: &str = concat!(
+    "01000000010000000000000000000000000000000000000000000000000000000000000000",
+    "ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368",
+    "616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420",
+    "666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a6",
+    "7130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c",
+    "384df7ba0b8d578a4c702b6bf11d5fac00000000",
+);
+const GENESIS_TXID: &str = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+#[test]
+fn test_bitcoin_kind() {
+    for kind in [Kind::Block, Kind::Tx, Kind::WitnessCommitment].iter() {
+        assert_eq!(Kind::from_codec(kind.to_codec()).unwrap(), *kind);
+    }
+    assert!(Kind::from_codec(multicodec::ETH_BLOCK.into()).is_err());
+}
+
+#[test]
+fn test_bitcoin_cid() {
+    let testcases = vec![
+        (Kind::Block, GENESIS_HEADER, GENESIS_HASH),
+        (Kind::Tx, GENESIS_TX, GENESIS_TXID),
+    ];
+    for (kind, data, hash) in testcases.into_iter() {
+        let data = Base::Base16Lower.decode(data).unwrap();
+        let cid = to_cid(kind, &data).unwrap();
+        assert_eq!(cid.to_version(), Version::V1);
+        assert_eq!(cid.to_codec(), kind.to_codec());
+
+        let (k, h) = to_hash(&cid).unwrap();
+        assert_eq!(k, kind);
+        assert_eq!(format_hash(&h).unwrap(), hash);
+        assert_eq!(from_hash(kind, &parse_hash(hash).unwrap()).unwrap(), cid);
+
+        let text = cid.to_text(Base::Base32Lower).unwrap();
+        assert_eq!(Cid::from_text(&text).unwrap(), cid);
+    }
+
+    assert!(to_cid(Kind::Block, &Base::Base16Lower.decode(GENESIS_TX).unwrap()).is_err());
+    assert!(to_cid(Kind::Tx, &[]).is_err());
+}
+
+#[test]
+fn test_bitcoin_hash() {
+    // display order is the reverse of internal order.
+    let hash = parse_hash(GENESIS_HASH).unwrap();
+    assert_eq!(hash[31], 0x00);
+    assert_eq!(hash[0], 0x6f);
+    let upper = GENESIS_HASH.to_ascii_uppercase();
+    assert_eq!(parse_hash(&upper).unwrap(), hash);
+    assert!(parse_hash(&GENESIS_HASH[2..]).is_err());
+
+    let mh = Multihash::from_digest(multicodec::SHA2_256.into(), &hash).unwrap();
+    let cid = Cid::new_v1(multicodec::BITCOIN_BLOCK.into(), mh).unwrap();
+    assert!(to_hash(&cid).is_err());
+
+    // multihash allows truncated digests.
+    let mh = Multihash::from_digest(multicodec::DBL_SHA2_256.into(), &hash[..20]).unwrap();
+    let cid = Cid::new_v1(multicodec::BITCOIN_TX.into(), mh).unwrap();
+    assert!(to_hash(&cid).is_err());
+}
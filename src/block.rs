@@ -0,0 +1,104 @@
+//! Module implement IPLD block, content bytes tied to their CID.
+//!
+//! Blocks are content-codec agnostic, `raw`, `dag-pb`, `dag-cbor` or any
+//! other `ipld` tagged codec is carried as opaque bytes. Block size is
+//! limited to [MAX_BLOCK_SIZE], the largest block exchanged by IPFS
+//! implementations over bitswap.
+
+use alloc::vec::Vec;
+use core::{fmt, result};
+
+use crate::{cid::Cid, multicodec::Multicodec, multihash::Multihash, Error, Result};
+
+/// Maximum size of block's data, 2MiB.
+pub const MAX_BLOCK_SIZE: usize = 2 * 1024 * 1024;
+
+/// Type implement IPLD block, data along with its CID.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct Block {
+    cid: Cid,
+    data: Vec<u8>,
+}
+
+impl fmt::Debug for Block {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "Block<{},{}>", self.cid, self.data.len())
+    }
+}
+
+impl Block {
+    /// Create a block from `data` encoded using content `codec`. CIDv1 is
+    /// computed by hashing `data` using the multihash `hash_codec`.
+    pub fn encode(codec: Multicodec, hash_codec: Multicodec, data: &[u8]) -> Result<Block> {
+        check_size(data)?;
+        let mh = Multihash::new(hash_codec, data)?;
+        let val = Block {
+            cid: Cid::new_v1(codec, mh)?,
+            data: data.to_vec(),
+        };
+        Ok(val)
+    }
+
+    /// Create a block from `cid` and its `data`, verify that `data` hashes
+    /// to the multihash in `cid`.
+    pub fn new_checked(cid: Cid, data: Vec<u8>) -> Result<Block> {
+        check_size(&data)?;
+        cid.verify(&data)?;
+        Ok(Block { cid, data })
+    }
+
+    /// Create a block from `cid` and its `data` without verifying the
+    /// digest, say for data read from trusted storage.
+    pub fn new_unchecked(cid: Cid, data: Vec<u8>) -> Result<Block> {
+        check_size(&data)?;
+        Ok(Block { cid, data })
+    }
+
+    /// Verify that block's data hashes to its CID's multihash.
+    pub fn verify(&self) -> Result<()> {
+        self.cid.verify(&self.data)
+    }
+
+    /// Return the block's CID.
+    pub fn as_cid(&self) -> &Cid {
+        &self.cid
+    }
+
+    /// Return the block's content codec.
+    pub fn to_codec(&self) -> Multicodec {
+        self.cid.to_codec()
+    }
+
+    /// Return the block's data.
+    pub fn as_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Return the size of block's data.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Return whether block's data is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Return the CID and data of the block.
+    pub fn unwrap(self) -> (Cid, Vec<u8>) {
+        (self.cid, self.data)
+    }
+}
+
+fn check_size(data: &[u8]) -> Result<()> {
+    match data.len() {
+        n if n > MAX_BLOCK_SIZE => {
+            err_at!(BadInput, msg: "block size {} exceeds {}", n, MAX_BLOCK_SIZE)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+#[path = "block_test.rs"]
+mod block_test;
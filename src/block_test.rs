@@ -0,0 +1,48 @@
+use super::*;
+use crate::{cid::Version, multicodec};
+
+#[test]
+fn test_block() {
+    let raw: Multicodec = multicodec::RAW.into();
+    let sha2: Multicodec = multicodec::SHA2_256.into();
+
+    let block = Block::encode(raw, sha2, b"hello world").unwrap();
+    assert_eq!(
+        block.as_cid().to_string(),
+        "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+    );
+    assert_eq!(block.as_cid().to_version(), Version::V1);
+    assert_eq!(block.to_codec(), raw);
+    assert_eq!(block.as_data(), b"hello world");
+    assert_eq!(block.len(), 11);
+    assert!(!block.is_empty());
+    assert!(block.verify().is_ok());
+
+    let (cid, data) = block.clone().unwrap();
+    assert_eq!(Block::new_checked(cid.clone(), data).unwrap(), block);
+    assert!(Block::new_checked(cid.clone(), b"hello World".to_vec()).is_err());
+    let bad = Block::new_unchecked(cid, b"hello World".to_vec()).unwrap();
+    assert!(bad.verify().is_err());
+
+    let block = Block::encode(multicodec::DAG_CBOR.into(), multicodec::BLAKE3.into(), &[]).unwrap();
+    assert!(block.is_empty());
+    assert!(block.verify().is_ok());
+
+    assert!(Block::encode(raw, raw, b"hello world").is_err());
+}
+
+#[test]
+fn test_block_size() {
+    let raw: Multicodec = multicodec::RAW.into();
+    let sha2: Multicodec = multicodec::SHA2_256.into();
+
+    let data = vec![0xab; MAX_BLOCK_SIZE];
+    let block = Block::encode(raw, sha2, &data).unwrap();
+    assert_eq!(block.len(), MAX_BLOCK_SIZE);
+
+    let data = vec![0xab; MAX_BLOCK_SIZE + 1];
+    assert!(Block::encode(raw, sha2, &data).is_err());
+    let cid = block.as_cid().clone();
+    assert!(Block::new_checked(cid.clone(), data.clone()).is_err());
+    assert!(Block::new_unchecked(cid, data).is_err());
+}
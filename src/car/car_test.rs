@@ -0,0 +1,343 @@
+use std::io::{Cursor, Seek, SeekFrom};
+
+use super::*;
+use crate::{
+    cid::Version,
+    multibase::Base,
+    multicodec::{self, Multicodec},
+    multihash::Multihash,
+};
+
+// carv1-basic.car fixture from the CARv1 spec, rebuilt from the spec's
+// block listing. Every block hashes to its CID.
+const CARV1_BASIC: &str = concat!(
+    "63a265726f6f747382d82a58250001711220f88bc853804cf294fe417e4fa83028689fcdb1b1",
+    "592c5102e1474dbc200fab8bd82a5825000171122069ea0740f9807a28f4d932c62e7c1c83be",
+    "055e55072c90266ab3e79df63a365b6776657273696f6e015b01711220f88bc853804cf294fe",
+    "417e4fa83028689fcdb1b1592c5102e1474dbc200fab8ba2646c696e6bd82a582300122002ac",
+    "ecc5de2438ea4126a3010ecb1f8a599c8eff22fff1a1dcffe999b27fd3de646e616d6564626c",
+    "69708301122002acecc5de2438ea4126a3010ecb1f8a599c8eff22fff1a1dcffe999b27fd3de",
+    "122e0a2401551220b6fbd675f98e2abd22d4ed29fdc83150fedc48597e92dd1a7a24381d44a2",
+    "74511204626561721804122f0a22122079a982de3c9907953d4d323cee1d0fb1ed8f45f8ef02",
+    "870c0cb9e09246bd530a12067365636f6e641895012801551220b6fbd675f98e2abd22d4ed29",
+    "fdc83150fedc48597e92dd1a7a24381d44a27451636363638001122079a982de3c9907953d4d",
+    "323cee1d0fb1ed8f45f8ef02870c0cb9e09246bd530a122d0a240155122081cc5b17018674b4",
+    "01b42f35ba07bb79e211239c23bffe658da1577e3e6468771203646f671804122d0a221220e7",
+    "dc486e97e6ebe5cdabab3e392bdad128b6e09acc94bb4e2aa2af7b986d24d012056669727374",
+    "1833280155122081cc5b17018674b401b42f35ba07bb79e211239c23bffe658da1577e3e6468",
+    "7762626262511220e7dc486e97e6ebe5cdabab3e392bdad128b6e09acc94bb4e2aa2af7b986d",
+    "24d0122d0a240155122061be55a8e2f6b4e172338bddf184d6dbee29c98853e0a0485ecee7f2",
+    "7b9af0b412036361741804280155122061be55a8e2f6b4e172338bddf184d6dbee29c98853e0",
+    "a0485ecee7f27b9af0b461616161360171122069ea0740f9807a28f4d932c62e7c1c83be055e",
+    "55072c90266ab3e79df63a365ba2646c696e6bf6646e616d65656c696d626f",
+);
+
+// Roots and blocks of carv1-basic.car, with their section offsets.
+const CARV1_BASIC_BLOCKS: [(&str, u64); 8] = [
+    (
+        "bafyreihyrpefhacm6kkp4ql6j6udakdit7g3dmkzfriqfykhjw6cad5lrm",
+        100,
+    ),
+    ("QmNX6Tffavsya4xgBi2VJQnSuqy9GsxongxZZ9uZBqp16d", 192),
+    (
+        "bafkreifw7plhl6mofk6sfvhnfh64qmkq73oeqwl6sloru6rehaoujituke",
+        325,
+    ),
+    ("QmWXZxVQ9yZfhQxLD35eDR8LiMRsYtHxYqTFCBbJoiJVys", 366),
+    (
+        "bafkreiebzrnroamgos2adnbpgw5apo3z4iishhbdx77gldnbk57d4zdio4",
+        496,
+    ),
+    ("QmdwjhxpxzcMsR3qUuj7vUL8pbA7MgR3GAxWi2GLHjsKCT", 537),
+    (
+        "bafkreidbxzk2ryxwwtqxem4l3xyyjvw35yu4tcct4cqeqxwo47zhxgxqwq",
+        619,
+    ),
+    (
+        "bafyreidj5idub6mapiupjwjsyyxhyhedxycv4vihfsicm2vt46o7morwlm",
+        660,
+    ),
+];
+
+// CARv2 wrapping carv1-basic.car, pragma and header followed by the payload
+// and an IndexSorted index, laid out per the CARv2 spec.
+const CARV2_HEADER: &str = concat!(
+    "0aa16776657273696f6e02000000000000000000000000000000003300000000000000cb0200",
+    "0000000000fe02000000000000",
+);
+
+const CARV2_INDEX: &str = concat!(
+    "80080100000028000000400100000000000002acecc5de2438ea4126a3010ecb1f8a599c8eff",
+    "22fff1a1dcffe999b27fd3dec00000000000000061be55a8e2f6b4e172338bddf184d6dbee29",
+    "c98853e0a0485ecee7f27b9af0b46b0200000000000069ea0740f9807a28f4d932c62e7c1c83",
+    "be055e55072c90266ab3e79df63a365b940200000000000079a982de3c9907953d4d323cee1d",
+    "0fb1ed8f45f8ef02870c0cb9e09246bd530a6e0100000000000081cc5b17018674b401b42f35",
+    "ba07bb79e211239c23bffe658da1577e3e646877f001000000000000b6fbd675f98e2abd22d4",
+    "ed29fdc83150fedc48597e92dd1a7a24381d44a274514501000000000000e7dc486e97e6ebe5",
+    "cdabab3e392bdad128b6e09acc94bb4e2aa2af7b986d24d01902000000000000f88bc853804c",
+    "f294fe417e4fa83028689fcdb1b1592c5102e1474dbc200fab8b6400000000000000",
+);
+
+fn make_blocks() -> Vec<(Cid, Vec<u8>)> {
+    let mut blocks = vec![];
+    for (i, hash) in [
+        multicodec::SHA2_256,
+        multicodec::BLAKE3,
+        multicodec::SHA2_512,
+    ]
+    .iter()
+    .cycle()
+    .take(30)
+    .enumerate()
+    {
+        let data = format!("block-{}", i).repeat(i + 1).into_bytes();
+        let mh = Multihash::new(Multicodec::from_code(*hash).unwrap(), &data).unwrap();
+        let cid = Cid::new_v1(Multicodec::from_code(multicodec::RAW).unwrap(), mh).unwrap();
+        blocks.push((cid, data));
+    }
+    blocks
+}
+
+#[test]
+fn test_car_v1() {
+    let blocks = make_blocks();
+    let roots = vec![blocks[0].0.clone(), blocks[1].0.clone()];
+
+    let mut w = CarWriter::new(&roots, vec![]).unwrap();
+    let mut offsets = vec![];
+    for (cid, data) in blocks.iter() {
+        offsets.push(w.write_block(cid, data).unwrap());
+    }
+    let buf = w.finish().unwrap();
+
+    // DAG-CBOR header, `{"roots": [tag(42) bytes(37)..`
+    let root = roots[0].encode().unwrap();
+    assert_eq!(buf[1..8], [0xa2, 0x65, b'r', b'o', b'o', b't', b's']);
+    assert_eq!(buf[8..13], [0x82, 0xd8, 0x2a, 0x58, 0x25]);
+    assert_eq!(buf[13], 0x00);
+    assert_eq!(buf[14..14 + root.len()], root[..]);
+
+    let mut r = CarReader::new(Cursor::new(&buf)).unwrap();
+    r.set_verify(true);
+    assert_eq!(r.to_version(), 1);
+    assert_eq!(r.to_roots(), roots);
+    assert_eq!(r.to_v2_header(), None);
+    let items: Vec<(Cid, Vec<u8>)> = r.map(|item| item.unwrap()).collect();
+    assert_eq!(items, blocks);
+
+    // section at offset.
+    let (n, m) = read_varint(&mut &buf[offsets[5] as usize..])
+        .unwrap()
+        .unwrap();
+    let start = offsets[5] as usize + m;
+    let (cid, data) = Cid::decode(&buf[start..start + (n as usize)]).unwrap();
+    assert_eq!((cid, data.to_vec()), blocks[5]);
+}
+
+#[test]
+fn test_car_v1_basic() {
+    let buf = Base::Base16Lower.decode(CARV1_BASIC).unwrap();
+    let cids: Vec<Cid> = CARV1_BASIC_BLOCKS
+        .iter()
+        .map(|(text, _)| Cid::from_text(text).unwrap())
+        .collect();
+
+    let mut r = CarReader::new(buf.as_slice()).unwrap();
+    r.set_verify(true);
+    assert_eq!(r.to_version(), 1);
+    assert_eq!(r.to_roots(), vec![cids[0].clone(), cids[7].clone()]);
+    let items: Vec<(Cid, Vec<u8>)> = r.map(|item| item.unwrap()).collect();
+    assert_eq!(
+        items.iter().map(|(c, _)| c.clone()).collect::<Vec<Cid>>(),
+        cids
+    );
+    assert_eq!(items[2].1, b"cccc".to_vec());
+    assert_eq!(items[4].1, b"bbbb".to_vec());
+    assert_eq!(items[6].1, b"aaaa".to_vec());
+    assert_eq!(items[1].0.to_version(), Version::V0);
+    assert_eq!(items[1].0.to_codec().to_code(), multicodec::DAG_PB);
+
+    // writer reproduces the fixture byte for byte.
+    let mut w = CarWriter::new(&[cids[0].clone(), cids[7].clone()], vec![]).unwrap();
+    for ((cid, data), (_, offset)) in items.iter().zip(CARV1_BASIC_BLOCKS.iter()) {
+        assert_eq!(w.write_block(cid, data).unwrap(), *offset);
+    }
+    assert_eq!(w.finish().unwrap(), buf);
+}
+
+#[test]
+fn test_car_v2_basic() {
+    let payload = Base::Base16Lower.decode(CARV1_BASIC).unwrap();
+    let mut buf = Base::Base16Lower.decode(CARV2_HEADER).unwrap();
+    buf.extend_from_slice(&payload);
+    let index_bytes = Base::Base16Lower.decode(CARV2_INDEX).unwrap();
+    buf.extend_from_slice(&index_bytes);
+
+    let mut r = CarReader::new(buf.as_slice()).unwrap();
+    r.set_verify(true);
+    let hdr = r.to_v2_header().unwrap();
+    assert_eq!(r.to_version(), 2);
+    assert!(!hdr.is_fully_indexed());
+    assert_eq!(hdr.data_offset, 51);
+    assert_eq!(hdr.data_size, 715);
+    assert_eq!(hdr.index_offset, 766);
+    let roots = r.to_roots();
+    assert_eq!(roots.len(), 2);
+    assert_eq!(roots[0].to_string(), CARV1_BASIC_BLOCKS[0].0);
+    assert_eq!(roots[1].to_string(), CARV1_BASIC_BLOCKS[7].0);
+    assert_eq!(r.count(), CARV1_BASIC_BLOCKS.len());
+
+    let index = read_index(&mut Cursor::new(&buf)).unwrap().unwrap();
+    assert_eq!(index.to_kind(), IndexKind::IndexSorted);
+    assert_eq!(index.len(), CARV1_BASIC_BLOCKS.len());
+    for (text, offset) in CARV1_BASIC_BLOCKS.iter() {
+        let cid = Cid::from_text(text).unwrap();
+        assert_eq!(index.lookup(&cid).unwrap(), Some(*offset), "{}", text);
+    }
+    // IndexSorted ignores the codec, same digest under another codec hits.
+    let cid = Cid::from_text(CARV1_BASIC_BLOCKS[2].0).unwrap();
+    let other = Cid::new_v1(multicodec::DAG_CBOR.into(), cid.to_multihash().unwrap()).unwrap();
+    assert_eq!(index.lookup(&other).unwrap(), Some(325));
+    assert_eq!(index.encode().unwrap(), index_bytes);
+}
+
+#[test]
+fn test_car_v1_verify() {
+    let blocks = make_blocks();
+    let mut w = CarWriter::new(&[], vec![]).unwrap();
+    w.write_block(&blocks[0].0, &blocks[0].1).unwrap();
+    w.write_block(&blocks[1].0, b"tampered").unwrap();
+    let buf = w.finish().unwrap();
+
+    let mut r = CarReader::new(buf.as_slice()).unwrap();
+    assert!(r.to_roots().is_empty());
+    assert_eq!(r.next_block().unwrap().unwrap(), blocks[0]);
+    assert_eq!(r.next_block().unwrap().unwrap().1, b"tampered".to_vec());
+    assert!(r.next_block().unwrap().is_none());
+
+    let mut r = CarReader::new(buf.as_slice()).unwrap();
+    r.set_verify(true);
+    assert!(r.next_block().is_ok());
+    assert!(r.next_block().is_err());
+}
+
+#[test]
+fn test_car_v1_bad() {
+    let blocks = make_blocks();
+    let mut w = CarWriter::new(&[blocks[0].0.clone()], vec![]).unwrap();
+    w.write_block(&blocks[0].0, &blocks[0].1).unwrap();
+    let buf = w.finish().unwrap();
+
+    assert!(CarReader::new(&[][..]).is_err());
+    // truncated header.
+    assert!(CarReader::new(&buf[..10]).is_err());
+    // truncated section.
+    let mut r = CarReader::new(&buf[..buf.len() - 1]).unwrap();
+    assert!(r.next_block().is_err());
+    // unknown version.
+    let mut bad = buf.clone();
+    let n = buf[0] as usize;
+    assert_eq!(bad[n], 0x01);
+    bad[n] = 0x03;
+    assert!(CarReader::new(bad.as_slice()).is_err());
+    // section too large.
+    let mut bad = buf[..=n].to_vec();
+    bad.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0x0f]);
+    let mut r = CarReader::new(bad.as_slice()).unwrap();
+    assert!(r.next_block().is_err());
+}
+
+#[test]
+fn test_car_v2() {
+    let blocks = make_blocks();
+    let roots = vec![blocks[2].0.clone()];
+
+    for kind in [IndexKind::IndexSorted, IndexKind::MultihashIndexSorted].iter() {
+        let mut w = CarV2Writer::new(&roots, *kind, Cursor::new(vec![])).unwrap();
+        let mut offsets = vec![];
+        for (cid, data) in blocks.iter() {
+            offsets.push(w.write_block(cid, data).unwrap());
+        }
+        // duplicate blocks retain their first offset.
+        w.write_block(&blocks[0].0, &blocks[0].1).unwrap();
+        let mut cur = w.finish().unwrap();
+        let buf = cur.get_ref().clone();
+        assert_eq!(buf[..PRAGMA.len()], PRAGMA);
+
+        let mut r = CarReader::new(buf.as_slice()).unwrap();
+        r.set_verify(true);
+        let hdr = r.to_v2_header().unwrap();
+        assert_eq!(r.to_version(), 2);
+        assert_eq!(r.to_roots(), roots);
+        assert!(hdr.is_fully_indexed());
+        assert_eq!(hdr.data_offset, 51);
+        assert_eq!(hdr.index_offset, hdr.data_offset + hdr.data_size);
+        assert_eq!(V2Header::decode(&hdr.encode()).unwrap(), hdr);
+        let items: Vec<(Cid, Vec<u8>)> = r.map(|item| item.unwrap()).collect();
+        assert_eq!(items.len(), blocks.len() + 1);
+        assert_eq!(items[..blocks.len()], blocks[..]);
+
+        // the data payload is a valid CARv1.
+        let start = hdr.data_offset as usize;
+        let payload = &buf[start..start + (hdr.data_size as usize)];
+        let r = CarReader::new(payload).unwrap();
+        assert_eq!(r.to_roots(), roots);
+        assert_eq!(r.count(), blocks.len() + 1);
+
+        cur.seek(SeekFrom::Start(0)).unwrap();
+        let index = read_index(&mut cur).unwrap().unwrap();
+        assert_eq!(index.to_kind(), *kind);
+        assert_eq!(index.len(), blocks.len());
+        for ((cid, data), offset) in blocks.iter().zip(offsets.iter()) {
+            assert_eq!(index.lookup(cid).unwrap(), Some(*offset));
+            let mut r = &payload[(*offset as usize)..];
+            let (n, _) = read_varint(&mut r).unwrap().unwrap();
+            let (c, d) = Cid::decode(&r[..n as usize]).unwrap();
+            assert_eq!((&c, d), (cid, data.as_slice()));
+        }
+        let mh = Multihash::new(multicodec::SHA2_256.into(), b"missing").unwrap();
+        let cid = Cid::new_v1(multicodec::RAW.into(), mh).unwrap();
+        assert_eq!(index.lookup(&cid).unwrap(), None);
+    }
+}
+
+#[test]
+fn test_car_index() {
+    let blocks = make_blocks();
+    for kind in [IndexKind::IndexSorted, IndexKind::MultihashIndexSorted].iter() {
+        let mut index = Index::new(*kind);
+        assert!(index.is_empty());
+        for (i, (cid, _)) in blocks.iter().enumerate() {
+            index.insert(cid, (i as u64) * 100).unwrap();
+        }
+        let data = index.encode().unwrap();
+        let (val, rem) = Index::decode(&data).unwrap();
+        assert_eq!(val, index);
+        assert!(rem.is_empty());
+        assert!(Index::decode(&data[..data.len() - 1]).is_err());
+    }
+
+    // sha2-256 digest, width 40, single entry.
+    let mut index = Index::new(IndexKind::IndexSorted);
+    index.insert(&blocks[0].0, 7).unwrap();
+    let data = index.encode().unwrap();
+    assert_eq!(data[..2], [0x80, 0x08]);
+    assert_eq!(data[2..6], 1_u32.to_le_bytes());
+    assert_eq!(data[6..10], 40_u32.to_le_bytes());
+    assert_eq!(data[10..18], 40_u64.to_le_bytes());
+    assert_eq!(data.len(), 18 + 40);
+    assert_eq!(data[50..], 7_u64.to_le_bytes());
+
+    let mut index = Index::new(IndexKind::MultihashIndexSorted);
+    index.insert(&blocks[0].0, 7).unwrap();
+    let data = index.encode().unwrap();
+    assert_eq!(data[..2], [0x81, 0x08]);
+    assert_eq!(data[2..6], 1_u32.to_le_bytes());
+    assert_eq!(data[6..14], 0x12_u64.to_le_bytes());
+    assert_eq!(data[14..18], 1_u32.to_le_bytes());
+
+    // bad bucket width.
+    let mut bad = data.clone();
+    bad[18] = 8;
+    assert!(Index::decode(&bad).is_err());
+}
@@ -0,0 +1,208 @@
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+};
+
+use crate::{
+    cid::Cid,
+    multicodec::{self, Multicodec},
+    Error, Result,
+};
+
+/// CARv2 index formats.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum IndexKind {
+    /// Index digests, without hash function, bucketed by digest length.
+    IndexSorted,
+    /// Index digests bucketed by hash function and digest length.
+    MultihashIndexSorted,
+}
+
+impl IndexKind {
+    /// Return the multicodec for this index format.
+    pub fn to_codec(&self) -> Multicodec {
+        match self {
+            IndexKind::IndexSorted => multicodec::CAR_INDEX_SORTED.into(),
+            IndexKind::MultihashIndexSorted => multicodec::CAR_MULTIHASH_INDEX_SORTED.into(),
+        }
+    }
+
+    fn from_codec(codec: Multicodec) -> Result<IndexKind> {
+        match codec.to_code() {
+            multicodec::CAR_INDEX_SORTED => Ok(IndexKind::IndexSorted),
+            multicodec::CAR_MULTIHASH_INDEX_SORTED => Ok(IndexKind::MultihashIndexSorted),
+            code => Err(Error::UnknownCodec(code)),
+        }
+    }
+}
+
+/// Type implement CARv2 index, mapping the multihash of blocks to the
+/// offset of their section from the start of the data payload.
+///
+/// Entries are keyed by (hash-code, digest-length, digest), which is the
+/// sort order of buckets and entries in the encoded index. For
+/// [IndexKind::IndexSorted] hash-code is always ZERO.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Index {
+    kind: IndexKind,
+    entries: Entries,
+}
+
+impl Index {
+    /// Create an empty index in `kind` format.
+    pub fn new(kind: IndexKind) -> Index {
+        Index {
+            kind,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Insert `offset` for block `cid`. If the block is already indexed,
+    /// its first offset is retained.
+    pub fn insert(&mut self, cid: &Cid, offset: u64) -> Result<()> {
+        let key = self.to_key(cid)?;
+        self.entries.entry(key).or_insert(offset);
+        Ok(())
+    }
+
+    /// Lookup the offset of block `cid`.
+    pub fn lookup(&self, cid: &Cid) -> Result<Option<u64>> {
+        Ok(self.entries.get(&self.to_key(cid)?).copied())
+    }
+
+    /// Return the index format.
+    pub fn to_kind(&self) -> IndexKind {
+        self.kind
+    }
+
+    /// Return the number of indexed blocks.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return whether the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Decode index from its binary form and return the remaining
+    /// unparsed slice.
+    pub fn decode(buf: &[u8]) -> Result<(Index, &[u8])> {
+        let (codec, mut rem) = Multicodec::decode(buf)?;
+        let mut val = Index::new(IndexKind::from_codec(codec)?);
+
+        match val.kind {
+            IndexKind::IndexSorted => rem = decode_buckets(rem, 0, &mut val.entries)?,
+            IndexKind::MultihashIndexSorted => {
+                let (n, r) = read_u32(rem)?;
+                rem = r;
+                for _ in 0..n {
+                    let (b, r) = read_slice!(rem, 8, "car-index")?;
+                    let code = u64::from_le_bytes(b.try_into().unwrap());
+                    rem = decode_buckets(r, code, &mut val.entries)?;
+                }
+            }
+        }
+
+        Ok((val, rem))
+    }
+
+    /// Encode index into its binary form.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut buf = self.kind.to_codec().encode()?;
+
+        let entries: Vec<_> = self.entries.iter().collect();
+        match self.kind {
+            IndexKind::IndexSorted => encode_buckets(&mut buf, &entries),
+            IndexKind::MultihashIndexSorted => {
+                let groups: Vec<_> = group_by(&entries, |((code, _, _), _)| *code);
+                buf.extend_from_slice(&(groups.len() as u32).to_le_bytes());
+                for group in groups.into_iter() {
+                    buf.extend_from_slice(&(group[0].0).0.to_le_bytes());
+                    encode_buckets(&mut buf, group);
+                }
+            }
+        }
+
+        Ok(buf)
+    }
+
+    fn to_key(&self, cid: &Cid) -> Result<(u64, usize, Vec<u8>)> {
+        let (codec, digest) = cid.to_multihash()?.unwrap()?;
+        let code = match self.kind {
+            IndexKind::IndexSorted => 0,
+            IndexKind::MultihashIndexSorted => match u64::try_from(codec.to_code()) {
+                Ok(code) => code,
+                Err(_) => Err(Error::UnknownCodec(codec.to_code()))?,
+            },
+        };
+        Ok((code, digest.len(), digest))
+    }
+}
+
+type Entries = BTreeMap<(u64, usize, Vec<u8>), u64>;
+
+type Entry<'a> = (&'a (u64, usize, Vec<u8>), &'a u64);
+
+// Split sorted `entries` into runs of same key.
+fn group_by<'a, 'b, K, F>(entries: &'b [Entry<'a>], key: F) -> Vec<&'b [Entry<'a>]>
+where
+    K: PartialEq,
+    F: Fn(&Entry<'a>) -> K,
+{
+    let mut groups = vec![];
+    let mut start = 0;
+    for i in 1..=entries.len() {
+        if i == entries.len() || key(&entries[i]) != key(&entries[start]) {
+            groups.push(&entries[start..i]);
+            start = i;
+        }
+    }
+    groups
+}
+
+// Encode entries, sorted by digest length, as buckets of same width, each
+// bucket is `<width u32><byte-length u64><digest offset-u64>..`.
+fn encode_buckets(buf: &mut Vec<u8>, entries: &[Entry]) {
+    let buckets = group_by(entries, |((_, n, _), _)| *n);
+    buf.extend_from_slice(&(buckets.len() as u32).to_le_bytes());
+    for bucket in buckets.into_iter() {
+        let width = ((bucket[0].0).1 + 8) as u32;
+        buf.extend_from_slice(&width.to_le_bytes());
+        buf.extend_from_slice(&((width as u64) * (bucket.len() as u64)).to_le_bytes());
+        for ((_, _, digest), offset) in bucket.iter() {
+            buf.extend_from_slice(digest);
+            buf.extend_from_slice(&offset.to_le_bytes());
+        }
+    }
+}
+
+fn decode_buckets<'a>(buf: &'a [u8], code: u64, entries: &mut Entries) -> Result<&'a [u8]> {
+    let (n, mut rem) = read_u32(buf)?;
+    for _ in 0..n {
+        let (width, r) = read_u32(rem)?;
+        let (b, r) = read_slice!(r, 8, "car-index")?;
+        let size = u64::from_le_bytes(b.try_into().unwrap());
+
+        let width = width as usize;
+        if width <= 8 || size % (width as u64) > 0 {
+            err_at!(DecodeError, msg: "CAR index bucket width {} size {}", width, size)?
+        }
+        let size = usize::try_from(size).unwrap_or(usize::MAX);
+        let (data, r) = read_slice!(r, size, "car-index")?;
+        for entry in data.chunks(width) {
+            let (digest, offset) = entry.split_at(width - 8);
+            let offset = u64::from_le_bytes(offset.try_into().unwrap());
+            entries
+                .entry((code, digest.len(), digest.to_vec()))
+                .or_insert(offset);
+        }
+        rem = r;
+    }
+    Ok(rem)
+}
+
+fn read_u32(buf: &[u8]) -> Result<(u32, &[u8])> {
+    let (b, rem) = read_slice!(buf, 4, "car-index")?;
+    Ok((u32::from_le_bytes(b.try_into().unwrap()), rem))
+}
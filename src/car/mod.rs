@@ -0,0 +1,307 @@
+//! Module implement CAR, content addressable archive. _Refer [carv1] and
+//! [carv2] spec for detail_.
+//!
+//! CARv1 is a varint length prefixed DAG-CBOR header, `{"roots": [CID..],
+//! "version": 1}`, followed by a sequence of varint length prefixed
+//! sections, each section is a binary CID followed by the block's data.
+//!
+//! CARv2 wraps a CARv1 payload with a fixed [PRAGMA], a [V2Header]
+//! locating the payload and an optional [Index] of the blocks for random
+//! access.
+//!
+//! [CarReader] reads both versions, [CarWriter] writes CARv1 and
+//! [CarV2Writer] writes CARv2 with an index.
+//!
+//! [carv1]: https://ipld.io/specs/transport/car/carv1
+//! [carv2]: https://ipld.io/specs/transport/car/carv2
+
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    io::{self, Read, Write},
+};
+
+use crate::{
+    cid::Cid,
+    ipld::{dag_cbor, Ipld},
+    Error, Result,
+};
+
+mod index;
+mod v2;
+
+pub use index::{Index, IndexKind};
+pub use v2::{read_index, CarV2Writer, V2Header, FULLY_INDEXED, PRAGMA};
+
+/// Maximum size of a header or section, excluding its varint length
+/// prefix, accepted by [CarReader].
+pub const MAX_SECTION_SIZE: usize = 32 * 1024 * 1024;
+
+/// Type implement a streaming reader for CARv1 and CARv2 archives.
+///
+/// Blocks are read in the order they are found in the archive, using
+/// [CarReader::next_block] or as an [Iterator]. Blocks are not verified
+/// against their CID unless enabled by [CarReader::set_verify].
+pub struct CarReader<R: Read> {
+    inner: R,
+    version: u64,
+    roots: Vec<Cid>,
+    v2: Option<V2Header>,
+    // remaining bytes in CARv2 data payload.
+    remaining: Option<u64>,
+    verify: bool,
+}
+
+impl<R: Read> CarReader<R> {
+    /// Create a new reader from `inner`, read and validate the header.
+    /// For CARv2 the reader is positioned at the start of the blocks of
+    /// the inner CARv1 payload.
+    pub fn new(mut inner: R) -> Result<CarReader<R>> {
+        let (version, roots, _) = read_header(&mut inner)?;
+        let mut val = CarReader {
+            inner,
+            version,
+            roots,
+            v2: None,
+            remaining: None,
+            verify: false,
+        };
+
+        match version {
+            1 => (),
+            2 => {
+                let mut buf = [0; V2Header::LEN];
+                err_at!(IOError, val.inner.read_exact(&mut buf))?;
+                let hdr = V2Header::decode(&buf)?;
+
+                let pos = (PRAGMA.len() + V2Header::LEN) as u64;
+                let skip = match hdr.data_offset.checked_sub(pos) {
+                    Some(skip) => skip,
+                    None => err_at!(DecodeError, msg: "CARv2 data offset {}", hdr.data_offset)?,
+                };
+                let mut padding = (&mut val.inner).take(skip);
+                if err_at!(IOError, io::copy(&mut padding, &mut io::sink()))? != skip {
+                    err_at!(DecodeError, msg: "CARv2 truncated before data payload")?
+                }
+
+                let (version, roots, n) = read_header(&mut val.inner)?;
+                if version != 1 {
+                    err_at!(DecodeError, msg: "CARv2 data payload version {}", version)?
+                }
+                val.roots = roots;
+                val.remaining = match hdr.data_size.checked_sub(n) {
+                    Some(remaining) => Some(remaining),
+                    None => err_at!(DecodeError, msg: "CARv2 data size {}", hdr.data_size)?,
+                };
+                val.v2 = Some(hdr);
+            }
+            version => err_at!(DecodeError, msg: "CAR version {}", version)?,
+        }
+
+        Ok(val)
+    }
+
+    /// Enable or disable verification of each block's data against the
+    /// multihash in its CID.
+    pub fn set_verify(&mut self, verify: bool) -> &mut Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Return the archive version, 1 or 2.
+    pub fn to_version(&self) -> u64 {
+        self.version
+    }
+
+    /// Return the root CIDs listed in the header.
+    pub fn to_roots(&self) -> Vec<Cid> {
+        self.roots.clone()
+    }
+
+    /// Return the CARv2 header, None for CARv1.
+    pub fn to_v2_header(&self) -> Option<V2Header> {
+        self.v2
+    }
+
+    /// Read the next block, return None at the end of the archive.
+    pub fn next_block(&mut self) -> Result<Option<(Cid, Vec<u8>)>> {
+        if self.remaining == Some(0) {
+            return Ok(None);
+        }
+
+        let (n, m) = match read_varint(&mut self.inner)? {
+            Some(val) => val,
+            None if self.remaining.is_none() => return Ok(None),
+            None => err_at!(DecodeError, msg: "CARv2 data payload truncated")?,
+        };
+        if let Some(remaining) = self.remaining.as_mut() {
+            match remaining.checked_sub(n + (m as u64)) {
+                Some(val) => *remaining = val,
+                None => err_at!(DecodeError, msg: "CAR section beyond data size")?,
+            }
+        }
+
+        let buf = read_section(&mut self.inner, n)?;
+        let (cid, data) = Cid::decode(&buf)?;
+        if self.verify {
+            cid.verify(data)?;
+        }
+
+        Ok(Some((cid, data.to_vec())))
+    }
+
+    /// Return the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Iterator for CarReader<R> {
+    type Item = Result<(Cid, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_block().transpose()
+    }
+}
+
+/// Type implement a streaming writer for CARv1 archives.
+pub struct CarWriter<W: Write> {
+    inner: W,
+    offset: u64,
+}
+
+impl<W: Write> CarWriter<W> {
+    /// Create a new writer, writing the header with `roots` into `inner`.
+    pub fn new(roots: &[Cid], mut inner: W) -> Result<CarWriter<W>> {
+        let offset = write_header(&mut inner, roots)?;
+        Ok(CarWriter { inner, offset })
+    }
+
+    /// Write a block section and return its offset from the start of the
+    /// archive. Data is not verified against `cid`.
+    pub fn write_block(&mut self, cid: &Cid, data: &[u8]) -> Result<u64> {
+        let offset = self.offset;
+        self.offset += write_section(&mut self.inner, cid, data)?;
+        Ok(offset)
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        err_at!(IOError, self.inner.flush())?;
+        Ok(self.inner)
+    }
+}
+
+// Read the varint length prefixed header, return the version, roots and
+// the number of bytes read.
+fn read_header<R: Read>(r: &mut R) -> Result<(u64, Vec<Cid>, u64)> {
+    let (n, m) = match read_varint(r)? {
+        Some(val) => val,
+        None => err_at!(DecodeError, msg: "CAR header missing")?,
+    };
+    let buf = read_section(r, n)?;
+    let (version, roots) = decode_header(&buf)?;
+    Ok((version, roots, n + (m as u64)))
+}
+
+// Write the varint length prefixed header, return the number of bytes
+// written.
+fn write_header<W: Write>(w: &mut W, roots: &[Cid]) -> Result<u64> {
+    let data = encode_header(roots)?;
+    let mut buf = unsigned_varint::encode::u64_buffer();
+    let prefix = unsigned_varint::encode::u64(data.len() as u64, &mut buf);
+    err_at!(IOError, w.write_all(prefix))?;
+    err_at!(IOError, w.write_all(&data))?;
+    Ok((prefix.len() + data.len()) as u64)
+}
+
+// Write the varint length prefixed section, return the number of bytes
+// written.
+fn write_section<W: Write>(w: &mut W, cid: &Cid, data: &[u8]) -> Result<u64> {
+    let cid = cid.encode()?;
+    let n = (cid.len() + data.len()) as u64;
+    let mut buf = unsigned_varint::encode::u64_buffer();
+    let prefix = unsigned_varint::encode::u64(n, &mut buf);
+    err_at!(IOError, w.write_all(prefix))?;
+    err_at!(IOError, w.write_all(&cid))?;
+    err_at!(IOError, w.write_all(data))?;
+    Ok((prefix.len() as u64) + n)
+}
+
+fn read_section<R: Read>(r: &mut R, n: u64) -> Result<Vec<u8>> {
+    if n > (MAX_SECTION_SIZE as u64) {
+        err_at!(DecodeError, msg: "CAR section size {} exceeds limit", n)?
+    }
+    let mut buf = vec![0; n as usize];
+    err_at!(IOError, r.read_exact(&mut buf))?;
+    Ok(buf)
+}
+
+// Read an unsigned varint byte by byte, return the value and the number
+// of bytes read. Return None on end-of-file before the first byte.
+fn read_varint<R: Read>(r: &mut R) -> Result<Option<(u64, usize)>> {
+    let mut buf = [0; 10];
+    for i in 0..buf.len() {
+        let n = loop {
+            match r.read(&mut buf[i..i + 1]) {
+                Ok(n) => break n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => err_at!(IOError, Err(err))?,
+            }
+        };
+        match n {
+            0 if i == 0 => return Ok(None),
+            0 => err_at!(DecodeError, msg: "CAR truncated varint")?,
+            _ if unsigned_varint::decode::is_last(buf[i]) => {
+                let (val, _) = unsigned_varint::decode::u64(&buf[..=i])?;
+                return Ok(Some((val, i + 1)));
+            }
+            _ => (),
+        }
+    }
+    Err(Error::VarintOverflow)
+}
+
+// DAG-CBOR encoding of `{"roots": [CID..], "version": 1}`.
+fn encode_header(roots: &[Cid]) -> Result<Vec<u8>> {
+    let roots = roots.iter().cloned().map(Ipld::Link).collect();
+    let mut map = BTreeMap::new();
+    map.insert("roots".to_string(), Ipld::List(roots));
+    map.insert("version".to_string(), Ipld::Integer(1));
+    dag_cbor::encode(&Ipld::Map(map))
+}
+
+// Decode header, return version and roots. Roots are optional, CARv2
+// pragma has only the version.
+fn decode_header(buf: &[u8]) -> Result<(u64, Vec<Cid>)> {
+    let map = match dag_cbor::decode(buf)? {
+        Ipld::Map(map) => map,
+        _ => err_at!(DecodeError, msg: "CAR header not a map")?,
+    };
+
+    let mut roots = vec![];
+    let mut version = None;
+    for (key, val) in map.into_iter() {
+        match (key.as_str(), val) {
+            ("roots", Ipld::List(list)) => {
+                for item in list.into_iter() {
+                    match item {
+                        Ipld::Link(cid) => roots.push(cid),
+                        _ => err_at!(DecodeError, msg: "CAR header root not a CID")?,
+                    }
+                }
+            }
+            ("version", Ipld::Integer(n)) => version = u64::try_from(n).ok(),
+            (key, _) => err_at!(DecodeError, msg: "CAR header key {:?}", key)?,
+        }
+    }
+
+    match version {
+        Some(version) => Ok((version, roots)),
+        None => err_at!(DecodeError, msg: "CAR header missing version"),
+    }
+}
+
+#[cfg(test)]
+#[path = "car_test.rs"]
+mod car_test;
@@ -0,0 +1,149 @@
+use std::{
+    convert::TryInto,
+    io::{Read, Seek, SeekFrom, Write},
+};
+
+use crate::{
+    car::{write_header, write_section, CarReader, Index, IndexKind},
+    cid::Cid,
+    Error, Result,
+};
+
+/// CARv2 pragma, varint length prefixed DAG-CBOR of `{"version": 2}`.
+pub const PRAGMA: [u8; 11] = [
+    0x0a, 0xa1, 0x67, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x02,
+];
+
+/// Characteristics bit, set when the index covers all blocks in the data
+/// payload, including blocks with identity CIDs.
+pub const FULLY_INDEXED: u128 = 1 << 127;
+
+/// Type implement the fixed size CARv2 header following the [PRAGMA].
+///
+/// Offsets are from the start of the archive, an `index_offset` of ZERO
+/// means the archive has no index.
+#[derive(Clone, Copy, Eq, PartialEq, Default, Debug)]
+pub struct V2Header {
+    pub characteristics: u128,
+    pub data_offset: u64,
+    pub data_size: u64,
+    pub index_offset: u64,
+}
+
+impl V2Header {
+    /// Size of the encoded header.
+    pub const LEN: usize = 40;
+
+    /// Decode header from its binary form.
+    pub fn decode(buf: &[u8]) -> Result<V2Header> {
+        let (buf, _) = read_slice!(buf, V2Header::LEN, "carv2-header")?;
+        let u64_at = |off: usize| u64::from_le_bytes(buf[off..off + 8].try_into().unwrap());
+
+        // characteristics are a 128-bit bitfield, high bits first.
+        let characteristics = ((u64_at(0) as u128) << 64) | (u64_at(8) as u128);
+        let val = V2Header {
+            characteristics,
+            data_offset: u64_at(16),
+            data_size: u64_at(24),
+            index_offset: u64_at(32),
+        };
+        Ok(val)
+    }
+
+    /// Encode header into its binary form.
+    pub fn encode(&self) -> [u8; V2Header::LEN] {
+        let mut buf = [0; V2Header::LEN];
+        buf[0..8].copy_from_slice(&((self.characteristics >> 64) as u64).to_le_bytes());
+        buf[8..16].copy_from_slice(&(self.characteristics as u64).to_le_bytes());
+        buf[16..24].copy_from_slice(&self.data_offset.to_le_bytes());
+        buf[24..32].copy_from_slice(&self.data_size.to_le_bytes());
+        buf[32..40].copy_from_slice(&self.index_offset.to_le_bytes());
+        buf
+    }
+
+    /// Return whether the index covers all blocks.
+    pub fn is_fully_indexed(&self) -> bool {
+        (self.characteristics & FULLY_INDEXED) != 0
+    }
+}
+
+/// Type implement a writer for CARv2 archives, with an index of all
+/// blocks appended after the data payload.
+///
+/// Header is written as placeholder on construction and patched on
+/// [CarV2Writer::finish], hence the underlying writer must be seekable.
+pub struct CarV2Writer<W: Write + Seek> {
+    inner: W,
+    index: Index,
+    // position of the pragma in the underlying writer.
+    start: u64,
+    // bytes written into the data payload.
+    offset: u64,
+}
+
+impl<W: Write + Seek> CarV2Writer<W> {
+    /// Create a new writer, writing the pragma, a placeholder header and
+    /// the data payload's header with `roots` into `inner`. Index is
+    /// written in `kind` format.
+    pub fn new(roots: &[Cid], kind: IndexKind, mut inner: W) -> Result<CarV2Writer<W>> {
+        let start = err_at!(IOError, inner.stream_position())?;
+        err_at!(IOError, inner.write_all(&PRAGMA))?;
+        err_at!(IOError, inner.write_all(&V2Header::default().encode()))?;
+        let offset = write_header(&mut inner, roots)?;
+
+        let val = CarV2Writer {
+            inner,
+            index: Index::new(kind),
+            start,
+            offset,
+        };
+        Ok(val)
+    }
+
+    /// Write a block section and index it. Return its offset from the
+    /// start of the data payload. Data is not verified against `cid`.
+    pub fn write_block(&mut self, cid: &Cid, data: &[u8]) -> Result<u64> {
+        let offset = self.offset;
+        self.index.insert(cid, offset)?;
+        self.offset += write_section(&mut self.inner, cid, data)?;
+        Ok(offset)
+    }
+
+    /// Write the index, patch the header, flush and return the underlying
+    /// writer positioned at the end of the archive.
+    pub fn finish(mut self) -> Result<W> {
+        let data_offset = (PRAGMA.len() + V2Header::LEN) as u64;
+        let hdr = V2Header {
+            characteristics: FULLY_INDEXED,
+            data_offset,
+            data_size: self.offset,
+            index_offset: data_offset + self.offset,
+        };
+        err_at!(IOError, self.inner.write_all(&self.index.encode()?))?;
+
+        let pos = self.start + (PRAGMA.len() as u64);
+        err_at!(IOError, self.inner.seek(SeekFrom::Start(pos)))?;
+        err_at!(IOError, self.inner.write_all(&hdr.encode()))?;
+        err_at!(IOError, self.inner.seek(SeekFrom::End(0)))?;
+        err_at!(IOError, self.inner.flush())?;
+        Ok(self.inner)
+    }
+}
+
+/// Read the index of CARv2 archive, positioned at its start, from `r`.
+/// Return None if the archive has no index.
+pub fn read_index<R: Read + Seek>(r: &mut R) -> Result<Option<Index>> {
+    let start = err_at!(IOError, r.stream_position())?;
+    let hdr = match CarReader::new(&mut *r)?.to_v2_header() {
+        Some(hdr) => hdr,
+        None => err_at!(BadInput, msg: "CARv1 has no index")?,
+    };
+    if hdr.index_offset == 0 {
+        return Ok(None);
+    }
+
+    err_at!(IOError, r.seek(SeekFrom::Start(start + hdr.index_offset)))?;
+    let mut buf = vec![];
+    err_at!(IOError, r.read_to_end(&mut buf))?;
+    Ok(Some(Index::decode(&buf)?.0))
+}
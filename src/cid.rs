@@ -0,0 +1,216 @@
+//! Module implement CID, content identifier. _Refer [cid] spec for detail_.
+//!
+//! A CID is a self-describing content-addressed identifier, it uses
+//! multihash to address the content, multicodec to describe the content
+//! type, and multibase for its text form.
+//!
+//! * CIDv0 is a bare `sha2-256` multihash of `dag-pb` content, its text
+//!   form is raw base58btc without multibase prefix, `Qm...`.
+//! * CIDv1 is `<cidv1><content-codec><multihash>`, its text form is
+//!   multibase encoded, by default base32 lower, `bafy...`.
+//!
+//! [cid]: https://github.com/multiformats/cid
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, result, str::FromStr};
+
+use crate::{
+    multibase::{Base, Multibase},
+    multicodec::{self, Multicodec},
+    multihash::Multihash,
+    Error, Result,
+};
+
+/// CID version.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, Debug)]
+pub enum Version {
+    V0,
+    V1,
+}
+
+/// Type implement content identifier.
+///
+/// Held as version, content codec and the encoded multihash, ordering
+/// and hashing are done in that order.
+#[derive(Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Cid {
+    version: Version,
+    codec: u128,
+    mh: Vec<u8>,
+}
+
+impl fmt::Display for Cid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        let text = match self.version {
+            Version::V0 => bs58::encode(&self.mh).into_string(),
+            Version::V1 => self.to_text(Base::Base32Lower).map_err(|_| fmt::Error)?,
+        };
+        write!(f, "{}", text)
+    }
+}
+
+impl fmt::Debug for Cid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "Cid<{}>", self)
+    }
+}
+
+impl FromStr for Cid {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Cid> {
+        Cid::from_text(text)
+    }
+}
+
+impl Cid {
+    /// Create a CIDv0 from `sha2-256` multihash of `dag-pb` content.
+    pub fn new_v0(mh: Multihash) -> Result<Cid> {
+        let (codec, digest) = mh.clone().unwrap()?;
+        match (codec.to_code(), digest.len()) {
+            (multicodec::SHA2_256, 32) => (),
+            _ => err_at!(BadInput, msg: "CIDv0 multihash {}", mh)?,
+        }
+
+        let val = Cid {
+            version: Version::V0,
+            codec: multicodec::DAG_PB,
+            mh: mh.encode()?,
+        };
+        Ok(val)
+    }
+
+    /// Create a CIDv1 from content `codec` and multihash of the content.
+    pub fn new_v1(codec: Multicodec, mh: Multihash) -> Result<Cid> {
+        let val = Cid {
+            version: Version::V1,
+            codec: codec.to_code(),
+            mh: mh.encode()?,
+        };
+        Ok(val)
+    }
+
+    /// Decode CID from its binary form and return the remaining unparsed
+    /// slice.
+    pub fn decode(buf: &[u8]) -> Result<(Cid, &[u8])> {
+        // CIDv0 is a bare sha2-256 multihash.
+        if buf.len() >= 2 && buf[..2] == [0x12, 0x20] {
+            let (mh, rem) = read_slice!(buf, 34, "cidv0")?;
+            let val = Cid {
+                version: Version::V0,
+                codec: multicodec::DAG_PB,
+                mh: mh.to_vec(),
+            };
+            return Ok((val, rem));
+        }
+
+        let (version, rem) = Multicodec::decode(buf)?;
+        match version.to_code() {
+            multicodec::CID_V1 => (),
+            code => err_at!(DecodeError, msg: "CID version 0x{:x}", code)?,
+        }
+        let (codec, rem) = Multicodec::decode(rem)?;
+        let (mh, rem) = {
+            let (mh, rem) = Multihash::decode(rem)?;
+            (mh.encode()?, rem)
+        };
+
+        let val = Cid {
+            version: Version::V1,
+            codec: codec.to_code(),
+            mh,
+        };
+        Ok((val, rem))
+    }
+
+    /// Encode CID into its binary form.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let data = match self.version {
+            Version::V0 => self.mh.clone(),
+            Version::V1 => {
+                let mut data = Multicodec::from_code(multicodec::CID_V1)?.encode()?;
+                data.extend_from_slice(&Multicodec::from_code(self.codec)?.encode()?);
+                data.extend_from_slice(&self.mh);
+                data
+            }
+        };
+        Ok(data)
+    }
+
+    /// Parse CID from its text form, raw base58btc for CIDv0 and multibase
+    /// for CIDv1.
+    pub fn from_text(text: &str) -> Result<Cid> {
+        let data = match text.len() {
+            46 if text.starts_with("Qm") => {
+                err_at!(DecodeError, bs58::decode(text.as_bytes()).into_vec())?
+            }
+            _ => match Multibase::from_text(text)?.to_bytes() {
+                Some(data) => data,
+                None => err_at!(DecodeError, msg: "empty CID")?,
+            },
+        };
+        match Cid::decode(&data)? {
+            (cid, []) => Ok(cid),
+            (_, rem) => err_at!(DecodeError, msg: "CID trailing bytes {}", rem.len()),
+        }
+    }
+
+    /// Return the text form of CIDv1 multibase encoded using `base`.
+    /// CIDv0 can only be encoded in raw base58btc, refer [fmt::Display].
+    pub fn to_text(&self, base: Base) -> Result<String> {
+        match self.version {
+            Version::V0 => err_at!(Invalid, msg: "CIDv0 cannot be multibase encoded"),
+            Version::V1 => Multibase::with_base(base, &self.encode()?)?.to_text(),
+        }
+    }
+
+    /// Return the CID version.
+    pub fn to_version(&self) -> Version {
+        self.version
+    }
+
+    /// Return the content codec.
+    pub fn to_codec(&self) -> Multicodec {
+        self.codec.into()
+    }
+
+    /// Return the multihash of the content.
+    pub fn to_multihash(&self) -> Result<Multihash> {
+        Ok(Multihash::decode(&self.mh)?.0)
+    }
+
+    /// Return the encoded multihash of the content.
+    pub fn as_multihash_bytes(&self) -> &[u8] {
+        &self.mh
+    }
+
+    /// Convert CIDv0 to CIDv1, CIDv1 is returned as is.
+    pub fn into_v1(self) -> Cid {
+        Cid {
+            version: Version::V1,
+            ..self
+        }
+    }
+
+    /// Verify that `data` hashes to this CID's multihash. Return
+    /// [Error::BadInput] if it does not match.
+    pub fn verify(&self, data: &[u8]) -> Result<()> {
+        let (codec, digest) = self.to_multihash()?.unwrap()?;
+        let (_, computed) = Multihash::new(codec, data)?.unwrap()?;
+
+        // truncated digests are a prefix of the full digest, multihash
+        // rejects digests longer than the hash function's output.
+        let ok = match codec.to_code() {
+            multicodec::IDENTITY => computed == digest,
+            _ => computed.get(..digest.len()) == Some(&digest[..]),
+        };
+        match ok {
+            true => Ok(()),
+            false => err_at!(BadInput, msg: "CID {} does not match content", self),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "cid_test.rs"]
+mod cid_test;
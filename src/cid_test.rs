@@ -0,0 +1,75 @@
+use super::*;
+
+// Refer cid spec, both identify the same dag-pb content.
+const CIDV0: &str = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n";
+const CIDV1: &str = "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+
+#[test]
+fn test_cid_text() {
+    let a: Cid = CIDV0.parse().unwrap();
+    let b: Cid = CIDV1.parse().unwrap();
+    assert_eq!(a.to_version(), Version::V0);
+    assert_eq!(b.to_version(), Version::V1);
+    assert_eq!(a.to_codec().to_code(), multicodec::DAG_PB);
+    assert_eq!(b.to_codec().to_code(), multicodec::DAG_PB);
+    assert_eq!(a.to_multihash().unwrap(), b.to_multihash().unwrap());
+    assert_ne!(a, b);
+    assert_eq!(a.clone().into_v1(), b);
+
+    assert_eq!(a.to_string(), CIDV0);
+    assert_eq!(b.to_string(), CIDV1);
+    assert_eq!(format!("{:?}", b), format!("Cid<{}>", CIDV1));
+    assert!(a.to_text(Base::Base32Lower).is_err());
+
+    let text = b.to_text(Base::Base58Btc).unwrap();
+    assert!(text.starts_with("zdj7W"), "{}", text);
+    assert_eq!(text.parse::<Cid>().unwrap(), b);
+
+    for cid in [a, b].iter() {
+        let data = cid.encode().unwrap();
+        assert_eq!(&Cid::decode(&data).unwrap(), &(cid.clone(), &[][..]));
+    }
+}
+
+#[test]
+fn test_cid_new() {
+    let data = b"hello world";
+    let mh = Multihash::new(multicodec::SHA2_256.into(), data).unwrap();
+
+    let cid = Cid::new_v1(multicodec::RAW.into(), mh.clone()).unwrap();
+    assert_eq!(
+        cid.to_string(),
+        "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+    );
+    cid.verify(data).unwrap();
+    assert!(cid.verify(b"hello world!").is_err());
+
+    let cid = Cid::new_v0(mh).unwrap();
+    cid.verify(data).unwrap();
+    assert_eq!(cid.as_multihash_bytes()[..2], [0x12, 0x20]);
+
+    let mh = Multihash::new(multicodec::SHA2_512.into(), data).unwrap();
+    assert!(Cid::new_v0(mh).is_err());
+
+    // truncated digest
+    let mh = Multihash::new(multicodec::SHA2_256.into(), data).unwrap();
+    let digest = mh.to_digest().unwrap();
+    let mh = Multihash::from_digest(multicodec::SHA2_256.into(), &digest[..20]).unwrap();
+    let cid = Cid::new_v1(multicodec::RAW.into(), mh).unwrap();
+    cid.verify(data).unwrap();
+    assert!(cid.verify(b"hello").is_err());
+}
+
+#[test]
+fn test_cid_errors() {
+    assert!(Cid::decode(&[0x12, 0x20, 0x00]).is_err());
+    assert!(Cid::decode(&[0x02, 0x55, 0x12, 0x00]).is_err());
+    // digest longer than sha2-256 output never makes it into a CID.
+    let mut data = vec![0x01, 0x55, 0x12, 0x21];
+    data.extend_from_slice(&[0; 33]);
+    assert!(Cid::decode(&data).is_err());
+    assert!("bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyk"
+        .parse::<Cid>()
+        .is_err());
+    assert!("".parse::<Cid>().is_err());
+}
@@ -0,0 +1,359 @@
+//! Module implement content paths, `/ipfs/<cid>/a/b`, `/ipns/<name>/x`
+//! and `/ipld/<cid>/x`, along with their URL and contenthash forms.
+//!
+//! A path starts with a namespace and a root identifier, followed by zero
+//! or more segments. For `ipfs` and `ipld` namespaces the root is a CID,
+//! for `ipns` it is either an [IpnsName] or a DNSLink domain name.
+//!
+//! In text form segments are carried as is, they shall not be empty,
+//! `.` or `..`. In URL form, `ipfs://<cid>/a/b` and `ipns://<name>/x`,
+//! segments are percent-encoded and query or fragment is ignored.
+//!
+//! Contenthash is the binary form used by ENS, _refer [EIP-1577]_,
+//! `<ns-codec><cid>` without path segments. DNSLink names are held as
+//! CIDv1 `libp2p-key` with `identity` multihash over the domain name.
+//! Swarm contenthash, under `swarm-ns`, is handled by [crate::swarm].
+//!
+//! [EIP-1577]: https://eips.ethereum.org/EIPS/eip-1577
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, result, str::FromStr};
+
+use crate::{
+    cid::Cid,
+    ipns::IpnsName,
+    multicodec::{self, Multicodec},
+    multihash::Multihash,
+    peer_id::{self, PeerId},
+    Error, Result,
+};
+
+/// Namespace of a content path.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Namespace {
+    Ipfs,
+    Ipns,
+    Ipld,
+}
+
+impl Namespace {
+    /// Create namespace from its `namespace` tagged multicodec.
+    pub fn from_codec(codec: Multicodec) -> Result<Namespace> {
+        let val = match codec.to_code() {
+            multicodec::IPFS_NS => Namespace::Ipfs,
+            multicodec::IPNS_NS => Namespace::Ipns,
+            multicodec::IPLD_NS => Namespace::Ipld,
+            code => err_at!(BadInput, msg: "namespace codec 0x{:x}", code)?,
+        };
+        Ok(val)
+    }
+
+    /// Return the `namespace` tagged multicodec.
+    pub fn to_codec(&self) -> Multicodec {
+        match self {
+            Namespace::Ipfs => multicodec::IPFS_NS.into(),
+            Namespace::Ipns => multicodec::IPNS_NS.into(),
+            Namespace::Ipld => multicodec::IPLD_NS.into(),
+        }
+    }
+
+    /// Return the namespace as used in paths, without slashes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Namespace::Ipfs => "ipfs",
+            Namespace::Ipns => "ipns",
+            Namespace::Ipld => "ipld",
+        }
+    }
+
+    fn from_name(s: &str) -> Result<Namespace> {
+        let val = match s {
+            "ipfs" => Namespace::Ipfs,
+            "ipns" => Namespace::Ipns,
+            "ipld" => Namespace::Ipld,
+            s => err_at!(BadInput, msg: "namespace {:?}", s)?,
+        };
+        Ok(val)
+    }
+}
+
+/// Root identifier of a content path.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Root {
+    Cid(Cid),
+    Name(IpnsName),
+    DnsLink(String),
+}
+
+impl fmt::Display for Root {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        match self {
+            Root::Cid(cid) => write!(f, "{}", cid),
+            Root::Name(name) => write!(f, "{}", name),
+            Root::DnsLink(domain) => write!(f, "{}", domain),
+        }
+    }
+}
+
+/// Type implement content path, refer to [module][self] documentation.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ContentPath {
+    namespace: Namespace,
+    root: Root,
+    segments: Vec<String>,
+}
+
+impl fmt::Display for ContentPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "/{}/{}", self.namespace.as_str(), self.root)?;
+        for segment in self.segments.iter() {
+            write!(f, "/{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ContentPath {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<ContentPath> {
+        ContentPath::from_text(text)
+    }
+}
+
+impl ContentPath {
+    /// Create a content path. `root` shall be a CID for `ipfs` and `ipld`
+    /// namespaces, and a name or domain for `ipns` namespace.
+    pub fn new(namespace: Namespace, root: Root, segments: Vec<String>) -> Result<ContentPath> {
+        match (namespace, &root) {
+            (Namespace::Ipfs, Root::Cid(_)) | (Namespace::Ipld, Root::Cid(_)) => (),
+            (Namespace::Ipns, Root::Name(_)) | (Namespace::Ipns, Root::DnsLink(_)) => (),
+            (namespace, root) => err_at!(BadInput, msg: "{} under /{}", root, namespace.as_str())?,
+        }
+        if let Root::DnsLink(domain) = &root {
+            check_domain(domain)?;
+        }
+        for segment in segments.iter() {
+            check_segment(segment)?;
+        }
+
+        Ok(ContentPath {
+            namespace,
+            root,
+            segments,
+        })
+    }
+
+    /// Parse content path from its text form, `/<namespace>/<root>/..`,
+    /// or its URL form, `ipfs://<cid>/..` or `ipns://<name>/..`. Trailing
+    /// slash is ignored.
+    pub fn from_text(text: &str) -> Result<ContentPath> {
+        let (namespace, rest, url) = if let Some(rest) = text.strip_prefix("ipfs://") {
+            (Namespace::Ipfs, rest, true)
+        } else if let Some(rest) = text.strip_prefix("ipns://") {
+            (Namespace::Ipns, rest, true)
+        } else if let Some(rest) = text.strip_prefix('/') {
+            match rest.split_once('/') {
+                Some((ns, rest)) => (Namespace::from_name(ns)?, rest, false),
+                None => err_at!(BadInput, msg: "content path {:?}", text)?,
+            }
+        } else {
+            err_at!(BadInput, msg: "content path {:?}", text)?
+        };
+
+        let rest = match url {
+            true => rest.split(['?', '#']).next().unwrap_or(""),
+            false => rest,
+        };
+        let rest = rest.strip_suffix('/').unwrap_or(rest);
+
+        let mut parts = rest.split('/');
+        let root = match (parts.next().unwrap_or(""), namespace) {
+            ("", _) => err_at!(BadInput, msg: "content path missing root {:?}", text)?,
+            (s, Namespace::Ipns) if s.contains('.') => Root::DnsLink(String::from(s)),
+            (s, Namespace::Ipns) => Root::Name(s.parse()?),
+            (s, _) => Root::Cid(s.parse()?),
+        };
+        let segments = match url {
+            true => parts.map(percent_decode).collect::<Result<Vec<String>>>()?,
+            false => parts.map(String::from).collect(),
+        };
+
+        ContentPath::new(namespace, root, segments)
+    }
+
+    /// Return the text form, `/<namespace>/<root>/..`. Same as the
+    /// [fmt::Display] output.
+    pub fn to_text(&self) -> String {
+        format!("{}", self)
+    }
+
+    /// Return the URL form with percent-encoded segments. There is no
+    /// URL scheme for `ipld` namespace.
+    pub fn to_url(&self) -> Result<String> {
+        let mut url = match self.namespace {
+            Namespace::Ipfs | Namespace::Ipns => {
+                format!("{}://{}", self.namespace.as_str(), self.root)
+            }
+            Namespace::Ipld => err_at!(BadInput, msg: "no URL scheme for ipld")?,
+        };
+        for segment in self.segments.iter() {
+            url.push('/');
+            percent_encode(segment, &mut url);
+        }
+        Ok(url)
+    }
+
+    /// Decode content path from EIP-1577 contenthash, `<ns-codec><cid>`.
+    pub fn from_contenthash(buf: &[u8]) -> Result<ContentPath> {
+        let (codec, rem) = Multicodec::decode(buf)?;
+        let namespace = Namespace::from_codec(codec)?;
+        let cid = match Cid::decode(rem)? {
+            (cid, []) => cid,
+            (_, rem) => err_at!(BadInput, msg: "contenthash trailing bytes {}", rem.len())?,
+        };
+
+        let root = match namespace {
+            Namespace::Ipfs | Namespace::Ipld => Root::Cid(cid),
+            Namespace::Ipns => {
+                let (hash, digest) = cid.to_multihash()?.unwrap()?;
+                let is_key = hash.to_code() != multicodec::IDENTITY
+                    || peer_id::decode_public_key(&digest).is_ok();
+                match cid.to_codec().to_code() {
+                    multicodec::LIBP2P_KEY if is_key => {
+                        Root::Name(PeerId::from_bytes(cid.as_multihash_bytes())?.into())
+                    }
+                    multicodec::LIBP2P_KEY | multicodec::DAG_PB if !is_key => {
+                        match String::from_utf8(digest) {
+                            Ok(domain) => Root::DnsLink(domain),
+                            Err(_) => err_at!(BadInput, msg: "contenthash dnslink not utf8")?,
+                        }
+                    }
+                    code => err_at!(BadInput, msg: "contenthash ipns codec 0x{:x}", code)?,
+                }
+            }
+        };
+
+        ContentPath::new(namespace, root, vec![])
+    }
+
+    /// Encode content path as EIP-1577 contenthash, `<ns-codec><cid>`.
+    /// Path segments cannot be encoded.
+    pub fn to_contenthash(&self) -> Result<Vec<u8>> {
+        if !self.segments.is_empty() {
+            err_at!(BadInput, msg: "contenthash with path segments")?
+        }
+
+        let cid = match &self.root {
+            Root::Cid(cid) => cid.clone(),
+            Root::Name(name) => {
+                let mh = name.to_peer_id().to_multihash()?;
+                Cid::new_v1(multicodec::LIBP2P_KEY.into(), mh)?
+            }
+            Root::DnsLink(domain) => {
+                let mh = Multihash::from_digest(multicodec::IDENTITY.into(), domain.as_bytes())?;
+                Cid::new_v1(multicodec::LIBP2P_KEY.into(), mh)?
+            }
+        };
+
+        let mut buf = self.namespace.to_codec().encode()?;
+        buf.extend_from_slice(&cid.encode()?);
+        Ok(buf)
+    }
+
+    /// Return the namespace.
+    pub fn to_namespace(&self) -> Namespace {
+        self.namespace
+    }
+
+    /// Return the root identifier.
+    pub fn as_root(&self) -> &Root {
+        &self.root
+    }
+
+    /// Return the path segments following the root.
+    pub fn as_segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Return a new path with `segment` appended.
+    pub fn join(&self, segment: &str) -> Result<ContentPath> {
+        check_segment(segment)?;
+        let mut val = self.clone();
+        val.segments.push(String::from(segment));
+        Ok(val)
+    }
+}
+
+pub(crate) fn check_segment(segment: &str) -> Result<()> {
+    match segment {
+        "" | "." | ".." => err_at!(BadInput, msg: "path segment {:?}", segment),
+        s if s.contains(['/', '\0']) => {
+            err_at!(BadInput, msg: "path segment {:?}", segment)
+        }
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn check_domain(domain: &str) -> Result<()> {
+    let ok = domain.len() <= 253
+        && domain.contains('.')
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        });
+    match ok {
+        true => Ok(()),
+        false => err_at!(BadInput, msg: "dnslink domain {:?}", domain),
+    }
+}
+
+// Characters allowed un-escaped in URL path segments, RFC3986 `pchar`.
+fn is_pchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&b)
+}
+
+pub(crate) fn percent_encode(segment: &str, out: &mut String) {
+    for b in segment.bytes() {
+        match is_pchar(b) {
+            true => out.push(b as char),
+            false => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+}
+
+pub(crate) fn percent_decode(segment: &str) -> Result<String> {
+    let mut out = vec![];
+    let mut bytes = segment.bytes();
+    while let Some(b) = bytes.next() {
+        match b {
+            b'%' => {
+                let hex = [bytes.next(), bytes.next()];
+                // from_str_radix accepts a leading `+`, check digits first.
+                let val = match hex {
+                    [Some(h), Some(l)] if h.is_ascii_hexdigit() && l.is_ascii_hexdigit() => {
+                        core::str::from_utf8(&[h, l])
+                            .ok()
+                            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    }
+                    _ => None,
+                };
+                match val {
+                    Some(val) => out.push(val),
+                    None => err_at!(BadInput, msg: "percent-encoding in {:?}", segment)?,
+                }
+            }
+            b => out.push(b),
+        }
+    }
+    err_at!(BadInput, String::from_utf8(out))
+}
+
+#[cfg(test)]
+#[path = "content_path_test.rs"]
+mod content_path_test;
@@ -0,0 +1,147 @@
+use super::*;
+use crate::{cid::Version, multibase::Base, peer_id::KeyType};
+
+const CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+#[test]
+fn test_content_path() {
+    let name = PeerId::from_public_key(KeyType::Ed25519, &[1; 32]).unwrap();
+    let name = IpnsName::from(name).to_text().unwrap();
+
+    let testcases = vec![
+        format!("/ipfs/{}", CID),
+        format!("/ipfs/{}/a/b c/ü", CID),
+        "/ipfs/QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o/readme".to_string(),
+        format!("/ipld/{}/links/0/Hash", CID),
+        format!("/ipns/{}/x", name),
+        "/ipns/docs.ipfs.tech/concepts".to_string(),
+    ];
+    for text in testcases.into_iter() {
+        let path: ContentPath = text.parse().unwrap();
+        assert_eq!(path.to_string(), text);
+        assert_eq!(path.to_text(), text);
+    }
+
+    let path = ContentPath::from_text(&format!("/ipfs/{}/a/b/", CID)).unwrap();
+    assert_eq!(path.to_namespace(), Namespace::Ipfs);
+    assert_eq!(path.as_root(), &Root::Cid(CID.parse().unwrap()));
+    assert_eq!(path.as_segments(), &["a".to_string(), "b".to_string()]);
+    assert_eq!(path.join("c").unwrap().as_segments().len(), 3);
+    assert!(path.join("c/d").is_err());
+    assert!(path.join("..").is_err());
+
+    let path = ContentPath::from_text(&format!("/ipns/{}", name)).unwrap();
+    assert!(matches!(path.as_root(), Root::Name(_)));
+    let path = ContentPath::from_text("/ipns/docs.ipfs.tech").unwrap();
+    assert_eq!(path.as_root(), &Root::DnsLink("docs.ipfs.tech".to_string()));
+
+    let testcases = vec![
+        "".to_string(),
+        "ipfs".to_string(),
+        "/ipfs".to_string(),
+        "/ipfs/".to_string(),
+        format!("ipfs/{}", CID),
+        format!("/foo/{}", CID),
+        format!("/ipfs/{}//a", CID),
+        format!("/ipfs/{}/./a", CID),
+        format!("/ipfs/{}/a/..", CID),
+        "/ipfs/notacid".to_string(),
+        "/ipfs/docs.ipfs.tech".to_string(),
+        format!("/ipns/{}", CID),
+        "/ipns/-docs.ipfs.tech".to_string(),
+        "/ipns/docs..tech".to_string(),
+        "/ipns/docs_ipfs.tech".to_string(),
+    ];
+    for text in testcases.into_iter() {
+        assert!(ContentPath::from_text(&text).is_err(), "{}", text);
+    }
+
+    let cid: Cid = CID.parse().unwrap();
+    assert!(ContentPath::new(Namespace::Ipns, Root::Cid(cid.clone()), vec![]).is_err());
+    let root = Root::DnsLink("docs.ipfs.tech".to_string());
+    assert!(ContentPath::new(Namespace::Ipfs, root, vec![]).is_err());
+    let segments = vec!["a\0".to_string()];
+    assert!(ContentPath::new(Namespace::Ipfs, Root::Cid(cid), segments).is_err());
+}
+
+#[test]
+fn test_content_path_url() {
+    let text = format!("ipfs://{}/a%20b/%C3%BC/c:d@e", CID);
+    let path = ContentPath::from_text(&text).unwrap();
+    assert_eq!(path.to_text(), format!("/ipfs/{}/a b/ü/c:d@e", CID));
+    assert_eq!(path.to_url().unwrap(), text);
+
+    let path = ContentPath::from_text(&format!("ipfs://{}/a/?x=1#frag", CID)).unwrap();
+    assert_eq!(path.to_text(), format!("/ipfs/{}/a", CID));
+
+    // escaped slash cannot be carried by a segment.
+    assert!(ContentPath::from_text("ipns://docs.ipfs.tech/a%2Fb").is_err());
+
+    let path = ContentPath::from_text(&format!("/ipld/{}", CID)).unwrap();
+    assert!(path.to_url().is_err());
+
+    let testcases = vec![
+        "ipfs://".to_string(),
+        format!("ipfs://{}/a%2", CID),
+        format!("ipfs://{}/a%zz", CID),
+        format!("ipfs://{}/%ff", CID),
+        format!("ipfs://{}/%+f", CID),
+        format!("ipfs://{}/%2e%2e", CID),
+    ];
+    for text in testcases.into_iter() {
+        assert!(ContentPath::from_text(&text).is_err(), "{}", text);
+    }
+}
+
+#[test]
+fn test_content_path_contenthash() {
+    // EIP-1577 example, ipfs://QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4
+    let buf = Base::Base16Lower
+        .decode("e3010170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f")
+        .unwrap();
+    let path = ContentPath::from_contenthash(&buf).unwrap();
+    let cid = match path.as_root() {
+        Root::Cid(cid) => cid.clone(),
+        root => panic!("{:?}", root),
+    };
+    assert_eq!(cid.to_version(), Version::V1);
+    let mh = cid.to_multihash().unwrap();
+    assert_eq!(
+        Cid::new_v0(mh).unwrap().to_string(),
+        "QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4"
+    );
+    assert_eq!(path.to_contenthash().unwrap(), buf);
+
+    // ipns key, inlined Ed25519 key in libp2p-key CID.
+    let peer_id = PeerId::from_public_key(KeyType::Ed25519, &[1; 32]).unwrap();
+    let path =
+        ContentPath::from_text(&format!("ipns://{}", IpnsName::from(peer_id.clone()))).unwrap();
+    let buf = path.to_contenthash().unwrap();
+    assert_eq!(buf[..2], [0xe5, 0x01]);
+    assert_eq!(buf[2..], peer_id.to_cid_bytes().unwrap()[..]);
+    assert_eq!(ContentPath::from_contenthash(&buf).unwrap(), path);
+
+    // ipns dnslink, as encoded by ENS tooling.
+    let buf = Base::Base16Lower
+        .decode("e5010172000f6170702e756e69737761702e6f7267")
+        .unwrap();
+    let path = ContentPath::from_contenthash(&buf).unwrap();
+    assert_eq!(path.to_text(), "/ipns/app.uniswap.org");
+    assert_eq!(path.to_contenthash().unwrap(), buf);
+
+    let cid = Cid::from_text(CID).unwrap();
+    let path = ContentPath::new(Namespace::Ipld, Root::Cid(cid.clone()), vec![]).unwrap();
+    let buf = path.to_contenthash().unwrap();
+    assert_eq!(buf[..2], [0xe2, 0x01]);
+    assert_eq!(ContentPath::from_contenthash(&buf).unwrap(), path);
+
+    assert!(path.join("a").unwrap().to_contenthash().is_err());
+    let mut bad = path.to_contenthash().unwrap();
+    bad.push(0x00);
+    assert!(ContentPath::from_contenthash(&bad).is_err());
+    // swarm-ns is not a content path namespace.
+    let mut bad = vec![0xe4, 0x01];
+    bad.extend_from_slice(&cid.encode().unwrap());
+    assert!(ContentPath::from_contenthash(&bad).is_err());
+    assert!(ContentPath::from_contenthash(&[0xe3, 0x01]).is_err());
+}
@@ -0,0 +1,168 @@
+//! Module implement DAG-PB codec. _Refer [dag-pb] spec for detail_.
+//!
+//! A dag-pb node is a protobuf message with a list of named links to
+//! other nodes and an opaque data field, used by IPFS, along with
+//! [unixfs][crate::unixfs], to represent files and directories.
+//!
+//! ```text
+//! message PBLink {
+//!   optional bytes Hash = 1;
+//!   optional string Name = 2;
+//!   optional uint64 Tsize = 3;
+//! }
+//! message PBNode {
+//!   repeated PBLink Links = 2;
+//!   optional bytes Data = 1;
+//! }
+//! ```
+//!
+//! Decoding is strict, as required by the spec, fields shall appear in
+//! the above order without duplicates or unknown fields, and every link
+//! shall have a Hash.
+//!
+//! [dag-pb]: https://ipld.io/specs/codecs/dag-pb/spec
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    cid::Cid,
+    protobuf::{self, decode_field},
+    Error, Result,
+};
+
+/// Type implement a link from dag-pb node.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct PbLink {
+    /// CID of the target node.
+    pub hash: Cid,
+    /// Name of the link, entry name for UnixFS directories.
+    pub name: Option<String>,
+    /// Cumulative size of the target node, including its sub-DAG.
+    pub tsize: Option<u64>,
+}
+
+impl PbLink {
+    /// Create a new link to `hash`.
+    pub fn new(hash: Cid, name: Option<&str>, tsize: Option<u64>) -> PbLink {
+        PbLink {
+            hash,
+            name: name.map(String::from),
+            tsize,
+        }
+    }
+
+    fn decode(buf: &[u8]) -> Result<PbLink> {
+        let (mut hash, mut name, mut tsize) = (None, None, None);
+
+        let (mut rem, mut last) = (buf, 0);
+        while !rem.is_empty() {
+            let (field, val, r) = decode_field(rem)?;
+            if field <= last {
+                err_at!(DecodeError, msg: "dag-pb link field {} after {}", field, last)?
+            }
+            match field {
+                1 => {
+                    hash = match Cid::decode(val.to_bytes(field)?)? {
+                        (cid, []) => Some(cid),
+                        (_, r) => {
+                            err_at!(DecodeError, msg: "dag-pb link hash trailing {}", r.len())?
+                        }
+                    }
+                }
+                2 => {
+                    let s = err_at!(DecodeError, core::str::from_utf8(val.to_bytes(field)?))?;
+                    name = Some(String::from(s));
+                }
+                3 => tsize = Some(val.to_varint(field)?),
+                _ => err_at!(DecodeError, msg: "dag-pb link unknown field {}", field)?,
+            }
+            rem = r;
+            last = field;
+        }
+
+        match hash {
+            Some(hash) => Ok(PbLink { hash, name, tsize }),
+            None => err_at!(DecodeError, msg: "dag-pb link missing hash"),
+        }
+    }
+
+    fn encode(&self, buf: &mut Vec<u8>) -> Result<()> {
+        protobuf::encode_bytes_field(buf, 1, &self.hash.encode()?);
+        if let Some(name) = &self.name {
+            protobuf::encode_bytes_field(buf, 2, name.as_bytes());
+        }
+        if let Some(tsize) = self.tsize {
+            protobuf::encode_varint_field(buf, 3, tsize);
+        }
+        Ok(())
+    }
+}
+
+/// Type implement a dag-pb node.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct PbNode {
+    pub links: Vec<PbLink>,
+    pub data: Option<Vec<u8>>,
+}
+
+impl PbNode {
+    /// Create a new node, `links` are sorted by name to canonical order.
+    pub fn new(mut links: Vec<PbLink>, data: Option<Vec<u8>>) -> PbNode {
+        links.sort_by(|a, b| link_name(a).cmp(link_name(b)));
+        PbNode { links, data }
+    }
+
+    /// Decode a node from its binary form.
+    pub fn decode(buf: &[u8]) -> Result<PbNode> {
+        let mut val = PbNode::default();
+
+        let mut rem = buf;
+        while !rem.is_empty() {
+            let (field, v, r) = decode_field(rem)?;
+            match field {
+                1 if val.data.is_none() => val.data = Some(v.to_bytes(field)?.to_vec()),
+                1 => err_at!(DecodeError, msg: "dag-pb duplicate data")?,
+                2 if val.data.is_none() => val.links.push(PbLink::decode(v.to_bytes(field)?)?),
+                2 => err_at!(DecodeError, msg: "dag-pb links after data")?,
+                _ => err_at!(DecodeError, msg: "dag-pb unknown field {}", field)?,
+            }
+            rem = r;
+        }
+
+        Ok(val)
+    }
+
+    /// Encode node into its binary form, links are encoded in the order
+    /// they are held.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut buf = vec![];
+        for link in self.links.iter() {
+            let mut lbuf = vec![];
+            link.encode(&mut lbuf)?;
+            protobuf::encode_bytes_field(&mut buf, 2, &lbuf);
+        }
+        if let Some(data) = &self.data {
+            protobuf::encode_bytes_field(&mut buf, 1, data);
+        }
+        Ok(buf)
+    }
+
+    /// Validate that the node is in canonical form, links sorted by their
+    /// name bytes, links without name sorting as empty name.
+    pub fn validate(&self) -> Result<()> {
+        for (i, w) in self.links.windows(2).enumerate() {
+            if link_name(&w[0]) > link_name(&w[1]) {
+                err_at!(BadInput, msg: "dag-pb link {} not sorted", i + 1)?
+            }
+        }
+        Ok(())
+    }
+}
+
+fn link_name(link: &PbLink) -> &[u8] {
+    link.name.as_ref().map(|s| s.as_bytes()).unwrap_or(&[])
+}
+
+#[cfg(test)]
+#[path = "dag_pb_test.rs"]
+mod dag_pb_test;
@@ -0,0 +1,100 @@
+use super::*;
+use crate::{multicodec, multihash::Multihash};
+
+fn to_cid_v0(data: &[u8]) -> Cid {
+    Cid::new_v0(Multihash::new(multicodec::SHA2_256.into(), data).unwrap()).unwrap()
+}
+
+#[test]
+fn test_dag_pb_empty_dir() {
+    // unixfs empty directory.
+    let data = [0x0a, 0x02, 0x08, 0x01];
+    let node = PbNode::decode(&data).unwrap();
+    assert!(node.links.is_empty());
+    assert_eq!(node.data, Some(vec![0x08, 0x01]));
+    assert_eq!(node.encode().unwrap(), data);
+    assert_eq!(
+        to_cid_v0(&data).to_string(),
+        "QmUNLLsPACCz1vLxQVkXqqLX5R1X345qqfHbsf67hvA3Nn"
+    );
+
+    let node = PbNode::decode(&[]).unwrap();
+    assert_eq!(node, PbNode::default());
+    assert!(node.encode().unwrap().is_empty());
+}
+
+#[test]
+fn test_dag_pb_links() {
+    let a = to_cid_v0(b"a");
+    let b = to_cid_v0(b"b");
+    let links = vec![
+        PbLink::new(b.clone(), Some("zz"), Some(300)),
+        PbLink::new(a.clone(), Some("aa"), None),
+        PbLink::new(a.clone(), None, Some(1)),
+    ];
+    let node = PbNode::new(links, Some(b"data".to_vec()));
+    assert!(node.validate().is_ok());
+    assert_eq!(node.links[0].name, None);
+    assert_eq!(node.links[1].name.as_deref(), Some("aa"));
+    assert_eq!(node.links[2].hash, b);
+
+    let data = node.encode().unwrap();
+    assert_eq!(data[0], 0x12);
+    assert_eq!(data[data.len() - 6..], [0x0a, 0x04, b'd', b'a', b't', b'a']);
+    assert_eq!(PbNode::decode(&data).unwrap(), node);
+
+    let mut unsorted = node.clone();
+    unsorted.links.swap(1, 2);
+    assert!(unsorted.validate().is_err());
+    // decoders accept unsorted links.
+    let data = unsorted.encode().unwrap();
+    assert_eq!(PbNode::decode(&data).unwrap(), unsorted);
+}
+
+#[test]
+fn test_dag_pb_strict() {
+    let cid = to_cid_v0(b"a").encode().unwrap();
+    let mut link = vec![0x0a, cid.len() as u8];
+    link.extend_from_slice(&cid);
+
+    let mut ok = vec![0x12, link.len() as u8];
+    ok.extend_from_slice(&link);
+    ok.extend_from_slice(&[0x0a, 0x00]);
+    assert!(PbNode::decode(&ok).is_ok());
+
+    // links after data.
+    let mut bad = vec![0x0a, 0x00, 0x12, link.len() as u8];
+    bad.extend_from_slice(&link);
+    assert!(PbNode::decode(&bad).is_err());
+    // duplicate data.
+    assert!(PbNode::decode(&[0x0a, 0x00, 0x0a, 0x00]).is_err());
+    // unknown field.
+    assert!(PbNode::decode(&[0x18, 0x01]).is_err());
+    // data as varint.
+    assert!(PbNode::decode(&[0x08, 0x01]).is_err());
+    // truncated.
+    assert!(PbNode::decode(&[0x0a, 0x02, 0x08]).is_err());
+    // non-minimal length varint.
+    assert!(PbNode::decode(&[0x0a, 0x80, 0x00]).is_err());
+
+    // link missing hash.
+    assert!(PbNode::decode(&[0x12, 0x02, 0x18, 0x01]).is_err());
+    // link tsize before name.
+    let mut l = link.clone();
+    l.extend_from_slice(&[0x18, 0x01, 0x12, 0x01, b'a']);
+    let mut bad = vec![0x12, l.len() as u8];
+    bad.extend_from_slice(&l);
+    assert!(PbNode::decode(&bad).is_err());
+    // link duplicate hash.
+    let mut l = link.clone();
+    l.extend_from_slice(&link);
+    let mut bad = vec![0x12, l.len() as u8];
+    bad.extend_from_slice(&l);
+    assert!(PbNode::decode(&bad).is_err());
+    // link name not utf8.
+    let mut l = link.clone();
+    l.extend_from_slice(&[0x12, 0x01, 0xff]);
+    let mut bad = vec![0x12, l.len() as u8];
+    bad.extend_from_slice(&l);
+    assert!(PbNode::decode(&bad).is_err());
+}
@@ -0,0 +1,167 @@
+//! Module implement `did:key` identifiers. _Refer [did-key] spec for
+//! detail_.
+//!
+//! A `did:key` identifier is the [Multikey] text form of a public key,
+//! base58btc encoded, prefixed with `did:key:`, for example
+//! `did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp`.
+//!
+//! [did-key]: https://w3c-ccg.github.io/did-method-key
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, result, str::FromStr};
+
+use crate::{
+    multibase::Base,
+    multicodec::{self, Multicodec},
+    multikey::Multikey,
+    peer_id::{self, PeerId},
+    Error, Result,
+};
+
+/// Scheme and method prefix for `did:key` identifiers.
+pub const PREFIX: &str = "did:key:";
+
+/// Type of public keys supported by `did:key`.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum KeyType {
+    Ed25519,
+    X25519,
+    Secp256k1,
+    P256,
+    P384,
+    Bls12381G1,
+    Bls12381G2,
+}
+
+impl KeyType {
+    fn from_codec(codec: Multicodec) -> Result<KeyType> {
+        let val = match codec.to_code() {
+            multicodec::ED25519_PUB => KeyType::Ed25519,
+            multicodec::X25519_PUB => KeyType::X25519,
+            multicodec::SECP256K1_PUB => KeyType::Secp256k1,
+            multicodec::P256_PUB => KeyType::P256,
+            multicodec::P384_PUB => KeyType::P384,
+            multicodec::BLS12_381_G1_PUB => KeyType::Bls12381G1,
+            multicodec::BLS12_381_G2_PUB => KeyType::Bls12381G2,
+            // concatenated G1 and G2 keys have no did:key verification
+            // method, each key shall be its own did:key.
+            multicodec::BLS12_381_G1G2_PUB => {
+                err_at!(NotImplemented, msg: "did:key bls12_381-g1g2-pub")?
+            }
+            code => Err(Error::UnknownCodec(code))?,
+        };
+        Ok(val)
+    }
+
+    /// Return the multicodec for this key type.
+    pub fn to_codec(&self) -> Multicodec {
+        let code = match self {
+            KeyType::Ed25519 => multicodec::ED25519_PUB,
+            KeyType::X25519 => multicodec::X25519_PUB,
+            KeyType::Secp256k1 => multicodec::SECP256K1_PUB,
+            KeyType::P256 => multicodec::P256_PUB,
+            KeyType::P384 => multicodec::P384_PUB,
+            KeyType::Bls12381G1 => multicodec::BLS12_381_G1_PUB,
+            KeyType::Bls12381G2 => multicodec::BLS12_381_G2_PUB,
+        };
+        code.into()
+    }
+}
+
+/// Type implement `did:key` identifier.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct DidKey {
+    key: Multikey,
+}
+
+impl fmt::Display for DidKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "{}{}", PREFIX, self.key)
+    }
+}
+
+impl fmt::Debug for DidKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "DidKey<{}>", self)
+    }
+}
+
+impl FromStr for DidKey {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<DidKey> {
+        DidKey::from_text(text)
+    }
+}
+
+impl DidKey {
+    /// Create a did:key from raw public key bytes `data` of `key_type`.
+    /// Elliptic curve keys, other than Ed25519 and X25519, shall be in
+    /// compressed form.
+    pub fn new(key_type: KeyType, data: &[u8]) -> Result<DidKey> {
+        DidKey::from_multikey(Multikey::new(key_type.to_codec(), data)?)
+    }
+
+    /// Create a did:key from a multikey, which shall be a public key of
+    /// one of the [KeyType].
+    pub fn from_multikey(key: Multikey) -> Result<DidKey> {
+        KeyType::from_codec(key.to_codec())?;
+        Ok(DidKey { key })
+    }
+
+    /// Parse `did:key:z...` identifier.
+    pub fn from_text(text: &str) -> Result<DidKey> {
+        let id = match text.strip_prefix(PREFIX) {
+            Some(id) => id,
+            None => err_at!(BadInput, msg: "did:key {:?}", text)?,
+        };
+        // spec mandates base58btc encoding.
+        if !id.starts_with(Base::Base58Btc.code()) {
+            err_at!(BadInput, msg: "did:key must be base58btc {:?}", text)?
+        }
+        DidKey::from_multikey(Multikey::from_text(id)?)
+    }
+
+    /// Return the identifier, same as the [fmt::Display] output.
+    pub fn to_text(&self) -> String {
+        self.to_string()
+    }
+
+    /// Return the type of the public key.
+    pub fn to_key_type(&self) -> KeyType {
+        // validated when constructing DidKey.
+        KeyType::from_codec(self.key.to_codec()).unwrap()
+    }
+
+    /// Return the raw public key bytes.
+    pub fn to_key_bytes(&self) -> Vec<u8> {
+        self.key.as_key_bytes().to_vec()
+    }
+
+    /// Return the public key as multikey.
+    pub fn to_multikey(&self) -> Multikey {
+        self.key.clone()
+    }
+
+    /// Return the libp2p peer-id for this key. Only Ed25519 and
+    /// Secp256k1 keys can be used as libp2p identity without conversion,
+    /// for other key types return None.
+    pub fn to_peer_id(&self) -> Result<Option<PeerId>> {
+        let key_type = match self.to_key_type() {
+            KeyType::Ed25519 => peer_id::KeyType::Ed25519,
+            KeyType::Secp256k1 => peer_id::KeyType::Secp256k1,
+            _ => return Ok(None),
+        };
+        Ok(Some(PeerId::from_public_key(
+            key_type,
+            self.key.as_key_bytes(),
+        )?))
+    }
+}
+
+#[cfg(test)]
+#[path = "did_key_test.rs"]
+mod did_key_test;
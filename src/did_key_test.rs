@@ -0,0 +1,110 @@
+use super::*;
+
+// Refer did:key spec test vectors.
+const DIDS: [(KeyType, &str); 8] = [
+    (
+        KeyType::Ed25519,
+        "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+    ),
+    (
+        KeyType::Ed25519,
+        "did:key:z6MkjchhfUsD6mmvni8mCdXHw216Xrm9bQe2mBH1P5RDjVJG",
+    ),
+    (
+        KeyType::X25519,
+        "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F",
+    ),
+    (
+        KeyType::Secp256k1,
+        "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme",
+    ),
+    (
+        KeyType::P256,
+        "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169",
+    ),
+    (
+        KeyType::P384,
+        "did:key:z82Lm1MpAkeJcix9K8TMiLd5NMAhnwkjjCBeWHXyu3U4oT2MVJJKXkcVBgjGhnLBn2Kaau9",
+    ),
+    (
+        KeyType::Bls12381G1,
+        "did:key:z3tEFALUKUzzCAvytMHX8X4SnsNsq6T5tC5Zb18oQEt1FqNcJXqJ3AA9umgzA9yoqPBeWA",
+    ),
+    (
+        KeyType::Bls12381G2,
+        "did:key:zUC7EK3ZakmukHhuncwkbySmomv3FmrkmS36E4Ks5rsb6VQSRpoCrx6Hb8e2Nk6UvJFSdyw9NK1scFXJp21gNNYFjVWNgaqyGnkyhtagagCpQb5B7tagJu3HDbjQ8h5ypoHjwBb",
+    ),
+];
+
+#[test]
+fn test_did_key() {
+    for (key_type, text) in DIDS.iter() {
+        let did: DidKey = text.parse().unwrap();
+        assert_eq!(did.to_key_type(), *key_type, "{}", text);
+        assert_eq!(&did.to_text(), text);
+        assert_eq!(format!("{:?}", did), format!("DidKey<{}>", text));
+
+        let other = DidKey::new(*key_type, &did.to_key_bytes()).unwrap();
+        assert_eq!(other, did);
+        assert_eq!(DidKey::from_multikey(did.to_multikey()).unwrap(), did);
+        assert_eq!(&text[8..], did.to_multikey().to_string());
+    }
+}
+
+#[test]
+fn test_did_key_peer_id() {
+    for (key_type, text) in DIDS.iter() {
+        let did: DidKey = text.parse().unwrap();
+        let pid = did.to_peer_id().unwrap();
+        let prefix = match key_type {
+            KeyType::Ed25519 => "12D3KooW",
+            KeyType::Secp256k1 => "16Uiu2HA",
+            _ => {
+                assert!(pid.is_none());
+                continue;
+            }
+        };
+        let pid = pid.unwrap();
+        assert!(pid.to_string().starts_with(prefix), "{}", pid);
+        let (_, data) = pid.to_public_key().unwrap().unwrap();
+        assert_eq!(data, did.to_key_bytes());
+    }
+}
+
+#[test]
+fn test_did_key_errors() {
+    let testcases = [
+        "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+        "did:web:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+        "did:key:6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+        "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooW",
+        "did:key:",
+    ];
+    for text in testcases.iter() {
+        assert!(text.parse::<DidKey>().is_err(), "{}", text);
+    }
+
+    // did:key must be base58btc
+    let did: DidKey = DIDS[0].1.parse().unwrap();
+    let text = did.to_multikey().to_text(Base::Base32Lower).unwrap();
+    assert!(format!("{}{}", PREFIX, text).parse::<DidKey>().is_err());
+
+    // private keys and key types not supported by did:key.
+    let key = Multikey::new(multicodec::ED25519_PRIV.into(), &[7; 32]).unwrap();
+    match DidKey::from_multikey(key) {
+        Err(Error::UnknownCodec(multicodec::ED25519_PRIV)) => (),
+        res => panic!("{:?}", res),
+    }
+    let key = Multikey::new(multicodec::ED448_PUB.into(), &[7; 57]).unwrap();
+    assert!(DidKey::from_multikey(key).is_err());
+    assert!(DidKey::new(KeyType::Secp256k1, &[2; 32]).is_err());
+
+    // G1G2 keys are rejected, not mistaken for either key.
+    let key = Multikey::new(multicodec::BLS12_381_G1G2_PUB.into(), &[7; 144]).unwrap();
+    match DidKey::from_multikey(key.clone()) {
+        Err(Error::NotImplemented(_, _)) => (),
+        res => panic!("{:?}", res),
+    }
+    let text = format!("{}{}", PREFIX, key.to_text(Base::Base58Btc).unwrap());
+    assert!(text.parse::<DidKey>().is_err());
+}
@@ -0,0 +1,189 @@
+//! Module implement CIDs for Ethereum IPLD blocks. _Refer [eth-ipld]
+//! for detail_.
+//!
+//! Ethereum objects are addressed by `keccak-256` of their RLP encoding,
+//! CIDs carry that hash as `keccak-256` multihash with one of the `eth-*`
+//! codecs. Block hash and transaction hash are chain-native hashes of
+//! `eth-block` and `eth-tx` objects, written as `0x` prefixed hex.
+//!
+//! Typed transactions and receipts, _refer EIP-2718_, are the type byte
+//! followed by RLP, other objects shall be a single RLP item.
+//!
+//! [eth-ipld]: https://github.com/ipld/specs/tree/master/block-layer/codecs
+
+use alloc::string::String;
+use core::convert::TryInto;
+
+use crate::{
+    cid::{Cid, Version},
+    multibase::Base,
+    multicodec::{self, Multicodec},
+    multihash::Multihash,
+    Error, Result,
+};
+
+// Nesting limit when validating RLP.
+const MAX_RLP_DEPTH: usize = 64;
+
+/// Type of Ethereum object, one for each `eth-*` codec.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Kind {
+    /// Block header.
+    Block,
+    /// List of ommer headers.
+    BlockList,
+    TxTrie,
+    Tx,
+    TxReceiptTrie,
+    TxReceipt,
+    StateTrie,
+    AccountSnapshot,
+    StorageTrie,
+}
+
+impl Kind {
+    /// Create kind from its `eth-*` codec.
+    pub fn from_codec(codec: Multicodec) -> Result<Kind> {
+        let val = match codec.to_code() {
+            multicodec::ETH_BLOCK => Kind::Block,
+            multicodec::ETH_BLOCK_LIST => Kind::BlockList,
+            multicodec::ETH_TX_TRIE => Kind::TxTrie,
+            multicodec::ETH_TX => Kind::Tx,
+            multicodec::ETH_TX_RECEIPT_TRIE => Kind::TxReceiptTrie,
+            multicodec::ETH_TX_RECEIPT => Kind::TxReceipt,
+            multicodec::ETH_STATE_TRIE => Kind::StateTrie,
+            multicodec::ETH_ACCOUNT_SNAPSHOT => Kind::AccountSnapshot,
+            multicodec::ETH_STORAGE_TRIE => Kind::StorageTrie,
+            code => err_at!(BadInput, msg: "eth codec 0x{:x}", code)?,
+        };
+        Ok(val)
+    }
+
+    /// Return the `eth-*` codec for this kind.
+    pub fn to_codec(&self) -> Multicodec {
+        let code = match self {
+            Kind::Block => multicodec::ETH_BLOCK,
+            Kind::BlockList => multicodec::ETH_BLOCK_LIST,
+            Kind::TxTrie => multicodec::ETH_TX_TRIE,
+            Kind::Tx => multicodec::ETH_TX,
+            Kind::TxReceiptTrie => multicodec::ETH_TX_RECEIPT_TRIE,
+            Kind::TxReceipt => multicodec::ETH_TX_RECEIPT,
+            Kind::StateTrie => multicodec::ETH_STATE_TRIE,
+            Kind::AccountSnapshot => multicodec::ETH_ACCOUNT_SNAPSHOT,
+            Kind::StorageTrie => multicodec::ETH_STORAGE_TRIE,
+        };
+        code.into()
+    }
+}
+
+/// Compute CID for object of `kind` from its encoded bytes `data`.
+pub fn to_cid(kind: Kind, data: &[u8]) -> Result<Cid> {
+    let rlp = match (kind, data) {
+        (Kind::Tx, [typ, rlp @ ..]) | (Kind::TxReceipt, [typ, rlp @ ..]) if *typ < 0x80 => rlp,
+        (_, data) => data,
+    };
+    check_rlp(kind, rlp)?;
+
+    let mh = Multihash::new(multicodec::KECCAK_256.into(), data)?;
+    Cid::new_v1(kind.to_codec(), mh)
+}
+
+/// Create CID for object of `kind` from its `keccak-256` hash.
+pub fn from_hash(kind: Kind, hash: &[u8; 32]) -> Result<Cid> {
+    let mh = Multihash::from_digest(multicodec::KECCAK_256.into(), hash)?;
+    Cid::new_v1(kind.to_codec(), mh)
+}
+
+/// Return the object kind and its `keccak-256` hash from `cid`.
+pub fn to_hash(cid: &Cid) -> Result<(Kind, [u8; 32])> {
+    if cid.to_version() != Version::V1 {
+        err_at!(BadInput, msg: "eth CID version {:?}", cid.to_version())?
+    }
+    let kind = Kind::from_codec(cid.to_codec())?;
+    let (codec, digest) = cid.to_multihash()?.unwrap()?;
+    match (codec.to_code(), digest.len()) {
+        (multicodec::KECCAK_256, 32) => Ok((kind, digest.as_slice().try_into().unwrap())),
+        (multicodec::KECCAK_256, n) => err_at!(BadInput, msg: "eth CID digest length {}", n),
+        (_, _) => err_at!(BadInput, msg: "eth CID multihash {}", codec),
+    }
+}
+
+/// Parse hash from its `0x` prefixed hex form.
+pub fn parse_hash(text: &str) -> Result<[u8; 32]> {
+    let data = match text.strip_prefix("0x") {
+        Some(hex) if hex.len() == 64 => Base::Base16Lower.decode(&hex.to_ascii_lowercase())?,
+        _ => err_at!(BadInput, msg: "eth hash {:?}", text)?,
+    };
+    Ok(data.as_slice().try_into().unwrap())
+}
+
+/// Format hash in its `0x` prefixed hex form.
+pub fn format_hash(hash: &[u8; 32]) -> Result<String> {
+    Ok(String::from("0x") + &Base::Base16Lower.encode(hash)?)
+}
+
+// Objects are RLP lists, trie nodes can also be an RLP string, like the
+// empty trie.
+fn check_rlp(kind: Kind, data: &[u8]) -> Result<()> {
+    let (is_list, n) = check_rlp_item(data, 0)?;
+    if n != data.len() {
+        err_at!(BadInput, msg: "eth rlp trailing bytes {}", data.len() - n)?
+    }
+    match kind {
+        Kind::TxTrie | Kind::TxReceiptTrie | Kind::StateTrie | Kind::StorageTrie => Ok(()),
+        _ if is_list => Ok(()),
+        kind => err_at!(BadInput, msg: "eth {:?} not an rlp list", kind),
+    }
+}
+
+// Validate RLP item at the start of `buf`, return whether it is a list
+// and its encoded length.
+fn check_rlp_item(buf: &[u8], depth: usize) -> Result<(bool, usize)> {
+    if depth > MAX_RLP_DEPTH {
+        err_at!(BadInput, msg: "eth rlp nesting beyond {}", MAX_RLP_DEPTH)?
+    }
+
+    let (b, rem) = read_slice!(buf, 1, "rlp")?;
+    let (is_list, head, len) = match b[0] {
+        0x00..=0x7f => return Ok((false, 1)),
+        b @ 0x80..=0xb7 => (false, 1, (b - 0x80) as usize),
+        b @ 0xb8..=0xbf => (false, 1 + (b - 0xb7) as usize, rlp_len(rem, b - 0xb7)?),
+        b @ 0xc0..=0xf7 => (true, 1, (b - 0xc0) as usize),
+        b => (true, 1 + (b - 0xf7) as usize, rlp_len(rem, b - 0xf7)?),
+    };
+    let payload = match buf.get(head..).and_then(|p| p.get(..len)) {
+        Some(payload) => payload,
+        None => err_at!(BadInput, msg: "eth rlp truncated, need {}", len)?,
+    };
+
+    if is_list {
+        let mut rem = payload;
+        while !rem.is_empty() {
+            let (_, n) = check_rlp_item(rem, depth + 1)?;
+            rem = &rem[n..];
+        }
+    } else if len == 1 && payload[0] < 0x80 {
+        err_at!(BadInput, msg: "eth rlp non-canonical byte")?
+    }
+
+    Ok((is_list, head + len))
+}
+
+// Big-endian length of `n` bytes, canonical and beyond the short form.
+fn rlp_len(buf: &[u8], n: u8) -> Result<usize> {
+    let (b, _) = read_slice!(buf, n as usize, "rlp")?;
+    if b[0] == 0 {
+        err_at!(BadInput, msg: "eth rlp length with leading zero")?
+    }
+    let len = b.iter().try_fold(0_usize, |a, b| {
+        a.checked_mul(256).map(|a| a + (*b as usize))
+    });
+    match len {
+        Some(len) if len >= 56 => Ok(len),
+        _ => err_at!(BadInput, msg: "eth rlp length non-canonical"),
+    }
+}
+
+#[cfg(test)]
+#[path = "eth_test.rs"]
+mod eth_test;
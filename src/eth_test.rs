@@ -0,0 +1,188 @@
+use super::*;
+
+// keccak-256 of `c0`, the empty ommers list, and of `80`, the empty trie.
+const EMPTY_LIST_HASH: &str = "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347";
+const EMPTY_TRIE_HASH: &str = "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421";
+
+// Mainnet genesis block header, zero fields and logs bloom filled in by
+// genesis_header().
+const GENESIS_HASH: &str = "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3";
+
+// Mainnet legacy transaction.
+const LEGACY_TX: &str = concat!(
+    "f9015482078b8505d21dba0083022ef1947a250d5630b4cf539739df2c5dacb4c659f2488d88",
+    "0c46549a521b13d8b8e47ff36ab50000000000000000000000000000000000000000000066ab",
+    "5a608bd00a23f2fe000000000000000000000000000000000000000000000000000000000000",
+    "008000000000000000000000000048c04ed5691981c42154c6167398f95e8f38a7ff00000000",
+    "000000000000000000000000000000000000000000000000632ceac700000000000000000000",
+    "00000000000000000000000000000000000000000002000000000000000000000000c02aaa39",
+    "b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000006c6ee5e31d828de24128",
+    "2b9606c8e98ea48526e225a0c9077369501641a92ef7399ff81c21639ed4fd8fc69cb793cfa1",
+    "dbfab342e10aa0615facb2f1bcf3274a354cfe384a38d0cc008a11c2dd23a69111bc6930ba27",
+    "a8",
+);
+const LEGACY_TX_HASH: &str = "0x280cde7cdefe4b188750e76c888f13bd05ce9a4d7767730feefe8a0e50ca6fc4";
+
+// Mainnet EIP-1559 transaction.
+const TYPED_TX: &str = concat!(
+    "02f86f0102843b9aca0085029e7822d68298f094d9e1459a7a482635700cbc20bbaf52d495",
+    "ab9c9680841b55ba3ac080a0c199674fcb29f353693dd779c017823b954b3c69dffa3cd6b2",
+    "a6ff7888798039a028ca912de909e7e6cdef9cdcaf24c54dd8c1032946dfa1d85c206b32a9",
+    "064fe8",
+);
+const TYPED_TX_HASH: &str = "0xce4dc6d7a7549a98ee3b071b67e970879ff51b5b95d1c340bacd80fa1e1aab31";
+
+// Receipts have no chain-native hash, these are keccak-256 of the receipt,
+// status, cumulative gas, bloom and logs per EIP-658, as is and in the
+// EIP-2718 envelope with EIP-1559 type byte, computed independently.
+const RECEIPT_HASH: &str = "0xb6634cdf0e5a46387be05d4a7b4596b32f21d99b189fbfd304a32c700c1da6c5";
+const TYPED_RECEIPT_HASH: &str =
+    "0x859428b76afafa7c8b9f4e658d7aefd9388fda12574c59d893d31acea967e5d5";
+
+fn genesis_header() -> String {
+    let zero = "00".repeat(32);
+    let empty_trie = &EMPTY_TRIE_HASH[2..];
+    format!(
+        "f90214a0{}a0{}94{}a0{}a0{}a0{}b90100{}{}a0{}a0{}{}",
+        zero,
+        &EMPTY_LIST_HASH[2..],
+        "00".repeat(20),
+        "d7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+        empty_trie,
+        empty_trie,
+        "00".repeat(256),
+        "850400000000808213888080",
+        "11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+        zero,
+        "880000000000000042",
+    )
+}
+
+fn receipt() -> String {
+    format!(
+        "f901668001b90100{}f85ff85d94{}f842a0{}a0{}830100ff",
+        "00".repeat(256),
+        "0000000000000000000000000000000000000011",
+        "000000000000000000000000000000000000000000000000000000000000dead",
+        "000000000000000000000000000000000000000000000000000000000000beef",
+    )
+}
+
+#[test]
+fn test_eth_kind() {
+    let kinds = vec![
+        Kind::Block,
+        Kind::BlockList,
+        Kind::TxTrie,
+        Kind::Tx,
+        Kind::TxReceiptTrie,
+        Kind::TxReceipt,
+        Kind::StateTrie,
+        Kind::AccountSnapshot,
+        Kind::StorageTrie,
+    ];
+    for kind in kinds.into_iter() {
+        assert_eq!(Kind::from_codec(kind.to_codec()).unwrap(), kind);
+    }
+    assert!(Kind::from_codec(multicodec::DAG_CBOR.into()).is_err());
+}
+
+#[test]
+fn test_eth_cid() {
+    let testcases = vec![
+        (Kind::Block, genesis_header(), GENESIS_HASH),
+        (Kind::Tx, LEGACY_TX.to_string(), LEGACY_TX_HASH),
+        (Kind::Tx, TYPED_TX.to_string(), TYPED_TX_HASH),
+        (Kind::TxReceipt, receipt(), RECEIPT_HASH),
+        (
+            Kind::TxReceipt,
+            format!("02{}", receipt()),
+            TYPED_RECEIPT_HASH,
+        ),
+        (Kind::BlockList, "c0".to_string(), EMPTY_LIST_HASH),
+        (Kind::StateTrie, "80".to_string(), EMPTY_TRIE_HASH),
+        (Kind::StorageTrie, "80".to_string(), EMPTY_TRIE_HASH),
+    ];
+    for (kind, data, hash) in testcases.into_iter() {
+        let data = Base::Base16Lower.decode(&data).unwrap();
+        let cid = to_cid(kind, &data).unwrap();
+        assert_eq!(cid.to_version(), Version::V1);
+        assert_eq!(cid.to_codec(), kind.to_codec());
+
+        let (k, h) = to_hash(&cid).unwrap();
+        assert_eq!(k, kind);
+        assert_eq!(format_hash(&h).unwrap(), hash);
+        assert_eq!(from_hash(kind, &parse_hash(hash).unwrap()).unwrap(), cid);
+
+        let text = cid.to_text(Base::Base32Lower).unwrap();
+        assert_eq!(Cid::from_text(&text).unwrap(), cid);
+    }
+}
+
+#[test]
+fn test_eth_typed() {
+    // EIP-2718 envelope, type byte followed by rlp list.
+    let data = Base::Base16Lower.decode("02c3010203").unwrap();
+    let cid = to_cid(Kind::Tx, &data).unwrap();
+    let mh = Multihash::new(multicodec::KECCAK_256.into(), &data).unwrap();
+    assert_eq!(cid.to_multihash().unwrap(), mh);
+    assert!(to_cid(Kind::TxReceipt, &data).is_ok());
+
+    assert!(to_cid(Kind::Block, &data).is_err());
+    assert!(to_cid(Kind::Tx, &[0x02]).is_err());
+}
+
+#[test]
+fn test_eth_rlp() {
+    let long = format!("f838b7{}", "00".repeat(55));
+    for data in ["c0", "c3010203", "c4c0c1c0c0", long.as_str()].iter() {
+        assert!(
+            to_cid(Kind::Block, &Base::Base16Lower.decode(data).unwrap()).is_ok(),
+            "{}",
+            data
+        );
+    }
+
+    let nested = format!("{}c0", "c1".repeat(MAX_RLP_DEPTH + 1));
+    let bad = vec![
+        "",         // empty
+        "c0c0",     // trailing bytes
+        "c2",       // truncated list
+        "8201",     // truncated string
+        "8101",     // single byte as string
+        "b80101",   // long form for short string
+        "b9000101", // length with leading zero
+        "c182",     // item truncated within list
+        nested.as_str(),
+    ];
+    for data in bad.into_iter() {
+        assert!(
+            to_cid(Kind::TxTrie, &Base::Base16Lower.decode(data).unwrap()).is_err(),
+            "{}",
+            data
+        );
+    }
+    assert!(to_cid(Kind::Block, &[0x80]).is_err());
+    assert!(to_cid(Kind::AccountSnapshot, &[0x01]).is_err());
+}
+
+#[test]
+fn test_eth_hash() {
+    let hash = parse_hash(EMPTY_LIST_HASH).unwrap();
+    let upper = format!("0x{}", EMPTY_LIST_HASH[2..].to_ascii_uppercase());
+    assert_eq!(parse_hash(&upper).unwrap(), hash);
+    assert!(parse_hash(&EMPTY_LIST_HASH[2..]).is_err());
+    assert!(parse_hash("0x1dcc").is_err());
+
+    let mh = Multihash::from_digest(multicodec::KECCAK_256.into(), &hash).unwrap();
+    let cid = Cid::new_v1(multicodec::RAW.into(), mh).unwrap();
+    assert!(to_hash(&cid).is_err());
+    let mh = Multihash::from_digest(multicodec::SHA2_256.into(), &hash).unwrap();
+    let cid = Cid::new_v1(multicodec::ETH_BLOCK.into(), mh).unwrap();
+    assert!(to_hash(&cid).is_err());
+
+    // multihash allows truncated digests.
+    let mh = Multihash::from_digest(multicodec::KECCAK_256.into(), &hash[..20]).unwrap();
+    let cid = Cid::new_v1(multicodec::ETH_TX.into(), mh).unwrap();
+    assert!(to_hash(&cid).is_err());
+}
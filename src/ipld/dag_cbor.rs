@@ -0,0 +1,262 @@
+//! Module implement DAG-CBOR codec. _Refer [dag-cbor] spec for detail_.
+//!
+//! DAG-CBOR is a strict subset of CBOR:
+//!
+//! * integers and lengths use the shortest encoding,
+//! * floats are always 64-bit and finite,
+//! * map keys are strings, sorted by length and then bytewise,
+//! * no indefinite lengths, tags other than 42 or simple values other
+//!   than `false`, `true` and `null`,
+//! * links are tag 42 over the binary CID prefixed with ZERO byte.
+//!
+//! [dag-cbor]: https://ipld.io/specs/codecs/dag-cbor/spec
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::convert::{TryFrom, TryInto};
+
+use crate::{
+    cid::Cid,
+    ipld::{Ipld, MAX_DEPTH},
+    Error, Result,
+};
+
+const TAG_CID: u64 = 42;
+
+/// Encode `val` into DAG-CBOR.
+pub fn encode(val: &Ipld) -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    encode_value(&mut buf, val, 0)?;
+    Ok(buf)
+}
+
+/// Decode DAG-CBOR from `buf`, the entire buffer shall be a single value.
+pub fn decode(buf: &[u8]) -> Result<Ipld> {
+    match decode_value(buf, 0)? {
+        (val, []) => Ok(val),
+        (_, rem) => err_at!(DecodeError, msg: "dag-cbor trailing bytes {}", rem.len()),
+    }
+}
+
+fn encode_value(buf: &mut Vec<u8>, val: &Ipld, depth: usize) -> Result<()> {
+    if depth > MAX_DEPTH {
+        err_at!(BadInput, msg: "dag-cbor nesting beyond {}", MAX_DEPTH)?
+    }
+
+    match val {
+        Ipld::Null => buf.push(0xf6),
+        Ipld::Bool(false) => buf.push(0xf4),
+        Ipld::Bool(true) => buf.push(0xf5),
+        Ipld::Integer(n) if *n >= 0 => match u64::try_from(*n) {
+            Ok(n) => encode_head(buf, 0, n),
+            Err(_) => err_at!(BadInput, msg: "dag-cbor integer {} out of range", n)?,
+        },
+        Ipld::Integer(n) => match u64::try_from(-1 - *n) {
+            Ok(n) => encode_head(buf, 1, n),
+            Err(_) => err_at!(BadInput, msg: "dag-cbor integer {} out of range", n)?,
+        },
+        Ipld::Float(f) if f.is_finite() => {
+            buf.push(0xfb);
+            buf.extend_from_slice(&f.to_bits().to_be_bytes());
+        }
+        Ipld::Float(f) => err_at!(BadInput, msg: "dag-cbor float {}", f)?,
+        Ipld::String(s) => {
+            encode_head(buf, 3, s.len() as u64);
+            buf.extend_from_slice(s.as_bytes());
+        }
+        Ipld::Bytes(b) => {
+            encode_head(buf, 2, b.len() as u64);
+            buf.extend_from_slice(b);
+        }
+        Ipld::List(list) => {
+            encode_head(buf, 4, list.len() as u64);
+            for item in list.iter() {
+                encode_value(buf, item, depth + 1)?;
+            }
+        }
+        Ipld::Map(map) => {
+            let mut items: Vec<(&String, &Ipld)> = map.iter().collect();
+            items.sort_by(|a, b| key_order(a.0, b.0));
+            encode_head(buf, 5, items.len() as u64);
+            for (key, item) in items.into_iter() {
+                encode_head(buf, 3, key.len() as u64);
+                buf.extend_from_slice(key.as_bytes());
+                encode_value(buf, item, depth + 1)?;
+            }
+        }
+        Ipld::Link(cid) => {
+            let data = cid.encode()?;
+            encode_head(buf, 6, TAG_CID);
+            encode_head(buf, 2, (data.len() + 1) as u64);
+            buf.push(0x00);
+            buf.extend_from_slice(&data);
+        }
+    }
+
+    Ok(())
+}
+
+fn encode_head(buf: &mut Vec<u8>, major: u8, val: u64) {
+    let major = major << 5;
+    match val {
+        0..=23 => buf.push(major | (val as u8)),
+        24..=0xff => buf.extend_from_slice(&[major | 24, val as u8]),
+        0x100..=0xffff => {
+            buf.push(major | 25);
+            buf.extend_from_slice(&(val as u16).to_be_bytes());
+        }
+        0x10000..=0xffff_ffff => {
+            buf.push(major | 26);
+            buf.extend_from_slice(&(val as u32).to_be_bytes());
+        }
+        _ => {
+            buf.push(major | 27);
+            buf.extend_from_slice(&val.to_be_bytes());
+        }
+    }
+}
+
+// Canonical order of map keys, shorter keys first, then bytewise.
+fn key_order(a: &str, b: &str) -> core::cmp::Ordering {
+    (a.len(), a.as_bytes()).cmp(&(b.len(), b.as_bytes()))
+}
+
+fn decode_value(buf: &[u8], depth: usize) -> Result<(Ipld, &[u8])> {
+    if depth > MAX_DEPTH {
+        err_at!(DecodeError, msg: "dag-cbor nesting beyond {}", MAX_DEPTH)?
+    }
+
+    let (b, rem) = read_slice!(buf, 1, "dag-cbor")?;
+    if (b[0] >> 5) == 7 {
+        let val = match b[0] {
+            0xf4 => (Ipld::Bool(false), rem),
+            0xf5 => (Ipld::Bool(true), rem),
+            0xf6 => (Ipld::Null, rem),
+            0xfb => {
+                let (b, rem) = read_slice!(rem, 8, "dag-cbor")?;
+                let f = f64::from_bits(u64::from_be_bytes(b.try_into().unwrap()));
+                if !f.is_finite() {
+                    err_at!(DecodeError, msg: "dag-cbor float {}", f)?
+                }
+                (Ipld::Float(f), rem)
+            }
+            b => err_at!(DecodeError, msg: "dag-cbor simple value 0x{:x}", b)?,
+        };
+        return Ok(val);
+    }
+
+    let (major, n, rem) = decode_head(buf)?;
+    let val = match major {
+        0 => (Ipld::Integer(n as i128), rem),
+        1 => (Ipld::Integer(-1 - (n as i128)), rem),
+        2 => {
+            let (data, rem) = read_slice!(rem, to_len(n), "dag-cbor")?;
+            (Ipld::Bytes(data.to_vec()), rem)
+        }
+        3 => {
+            let (s, rem) = decode_text(n, rem)?;
+            (Ipld::String(String::from(s)), rem)
+        }
+        4 => {
+            let (mut list, mut rem) = (vec![], rem);
+            for _ in 0..n {
+                let (item, r) = decode_value(rem, depth + 1)?;
+                list.push(item);
+                rem = r;
+            }
+            (Ipld::List(list), rem)
+        }
+        5 => {
+            let (mut map, mut rem) = (BTreeMap::new(), rem);
+            let mut prev: Option<&str> = None;
+            for _ in 0..n {
+                let key = match decode_head(rem)? {
+                    (3, n, r) => {
+                        let (key, r) = decode_text(n, r)?;
+                        rem = r;
+                        key
+                    }
+                    (major, _, _) => err_at!(DecodeError, msg: "dag-cbor map key major {}", major)?,
+                };
+                match prev {
+                    Some(prev) if key_order(prev, key).is_ge() => {
+                        err_at!(DecodeError, msg: "dag-cbor map key {:?} not canonical", key)?
+                    }
+                    _ => (),
+                }
+                let (item, r) = decode_value(rem, depth + 1)?;
+                map.insert(String::from(key), item);
+                prev = Some(key);
+                rem = r;
+            }
+            (Ipld::Map(map), rem)
+        }
+        6 if n == TAG_CID => {
+            let (data, rem) = match decode_head(rem)? {
+                (2, n, r) => read_slice!(r, to_len(n), "dag-cbor")?,
+                (major, _, _) => err_at!(DecodeError, msg: "dag-cbor CID major {}", major)?,
+            };
+            let cid = match data {
+                [0x00, data @ ..] => match Cid::decode(data)? {
+                    (cid, []) => cid,
+                    (_, r) => err_at!(DecodeError, msg: "dag-cbor CID trailing {}", r.len())?,
+                },
+                _ => err_at!(DecodeError, msg: "dag-cbor CID missing ZERO prefix")?,
+            };
+            (Ipld::Link(cid), rem)
+        }
+        6 => err_at!(DecodeError, msg: "dag-cbor tag {}", n)?,
+        _ => unreachable!(),
+    };
+
+    Ok(val)
+}
+
+// Decode major type and argument, argument shall be minimally encoded.
+fn decode_head(buf: &[u8]) -> Result<(u8, u64, &[u8])> {
+    let (b, rem) = read_slice!(buf, 1, "dag-cbor")?;
+    let (major, info) = (b[0] >> 5, b[0] & 0x1f);
+
+    let (val, min, rem) = match info {
+        0..=23 => (info as u64, 0, rem),
+        24 => {
+            let (b, rem) = read_slice!(rem, 1, "dag-cbor")?;
+            (b[0] as u64, 24, rem)
+        }
+        25 => {
+            let (b, rem) = read_slice!(rem, 2, "dag-cbor")?;
+            (u16::from_be_bytes(b.try_into().unwrap()) as u64, 0x100, rem)
+        }
+        26 => {
+            let (b, rem) = read_slice!(rem, 4, "dag-cbor")?;
+            (
+                u32::from_be_bytes(b.try_into().unwrap()) as u64,
+                0x10000,
+                rem,
+            )
+        }
+        27 => {
+            let (b, rem) = read_slice!(rem, 8, "dag-cbor")?;
+            (
+                u64::from_be_bytes(b.try_into().unwrap()),
+                0x1_0000_0000,
+                rem,
+            )
+        }
+        31 => err_at!(DecodeError, msg: "dag-cbor indefinite length")?,
+        info => err_at!(DecodeError, msg: "dag-cbor additional info {}", info)?,
+    };
+    if val < min {
+        err_at!(DecodeError, msg: "dag-cbor non-minimal argument {}", val)?
+    }
+
+    Ok((major, val, rem))
+}
+
+fn decode_text(n: u64, buf: &[u8]) -> Result<(&str, &[u8])> {
+    let (text, rem) = read_slice!(buf, to_len(n), "dag-cbor")?;
+    Ok((err_at!(DecodeError, core::str::from_utf8(text))?, rem))
+}
+
+fn to_len(n: u64) -> usize {
+    usize::try_from(n).unwrap_or(usize::MAX)
+}
@@ -0,0 +1,339 @@
+//! Module implement DAG-JSON codec. _Refer [dag-json] spec for detail_.
+//!
+//! DAG-JSON is JSON with two reserved forms, `{"/": "<cid>"}` for links
+//! and `{"/": {"bytes": "<base64>"}}` for bytes, base64 being the
+//! standard alphabet without padding. Maps with a single `"/"` key are
+//! reserved and cannot be encoded otherwise.
+//!
+//! Encoder produces canonical form, without whitespace and with map keys
+//! sorted bytewise. Decoder rejects whitespace, unsorted or duplicate map
+//! keys, and invalid reserved forms. It also rejects strings and numbers
+//! not in the form produced by the encoder, like `\/` or `\u00e9`
+//! escapes, `1E+2`, `1.50` and `-0`.
+//!
+//! [dag-json]: https://ipld.io/specs/codecs/dag-json/spec
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{convert::TryFrom, fmt::Write};
+
+use crate::{
+    cid::Cid,
+    ipld::{Ipld, MAX_DEPTH},
+    multibase::Base,
+    Error, Result,
+};
+
+/// Encode `val` into DAG-JSON.
+pub fn encode(val: &Ipld) -> Result<Vec<u8>> {
+    let mut out = String::new();
+    encode_value(&mut out, val, 0)?;
+    Ok(out.into_bytes())
+}
+
+/// Decode DAG-JSON from `buf`, the entire buffer shall be a single value.
+pub fn decode(buf: &[u8]) -> Result<Ipld> {
+    let mut p = Parser { buf, off: 0 };
+    let val = p.value(0)?;
+    match p.off == buf.len() {
+        true => Ok(val),
+        false => err_at!(DecodeError, msg: "dag-json trailing bytes at {}", p.off),
+    }
+}
+
+fn encode_value(out: &mut String, val: &Ipld, depth: usize) -> Result<()> {
+    if depth > MAX_DEPTH {
+        err_at!(BadInput, msg: "dag-json nesting beyond {}", MAX_DEPTH)?
+    }
+
+    match val {
+        Ipld::Null => out.push_str("null"),
+        Ipld::Bool(false) => out.push_str("false"),
+        Ipld::Bool(true) => out.push_str("true"),
+        Ipld::Integer(n) => match (i128::from(u64::MAX), -i128::from(u64::MAX) - 1) {
+            (max, min) if *n <= max && *n >= min => write!(out, "{}", n).unwrap(),
+            _ => err_at!(BadInput, msg: "dag-json integer {} out of range", n)?,
+        },
+        // debug format always carries a fraction or an exponent.
+        Ipld::Float(f) if f.is_finite() => write!(out, "{:?}", f).unwrap(),
+        Ipld::Float(f) => err_at!(BadInput, msg: "dag-json float {}", f)?,
+        Ipld::String(s) => encode_string(out, s),
+        Ipld::Bytes(b) => {
+            out.push_str(r#"{"/":{"bytes":""#);
+            Base::Base64.encode_into(b, out)?;
+            out.push_str(r#""}}"#);
+        }
+        Ipld::List(list) => {
+            out.push('[');
+            for (i, item) in list.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                encode_value(out, item, depth + 1)?;
+            }
+            out.push(']');
+        }
+        Ipld::Map(map) if map.len() == 1 && map.contains_key("/") => {
+            err_at!(BadInput, msg: "dag-json map with reserved key \"/\"")?
+        }
+        Ipld::Map(map) => {
+            out.push('{');
+            for (i, (key, item)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                encode_string(out, key);
+                out.push(':');
+                encode_value(out, item, depth + 1)?;
+            }
+            out.push('}');
+        }
+        Ipld::Link(cid) => {
+            out.push_str(r#"{"/":""#);
+            out.push_str(&cid.to_string());
+            out.push_str(r#""}"#);
+        }
+    }
+
+    Ok(())
+}
+
+fn encode_string(out: &mut String, s: &str) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            ch if (ch as u32) < 0x20 => write!(out, "\\u{:04x}", ch as u32).unwrap(),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+struct Parser<'a> {
+    buf: &'a [u8],
+    off: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Result<u8> {
+        match self.buf.get(self.off) {
+            Some(ch) => Ok(*ch),
+            None => err_at!(DecodeError, msg: "dag-json unexpected end"),
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<()> {
+        match self.buf[self.off..].starts_with(token.as_bytes()) {
+            true => {
+                self.off += token.len();
+                Ok(())
+            }
+            false => err_at!(DecodeError, msg: "dag-json expected {:?} at {}", token, self.off),
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Ipld> {
+        if depth > MAX_DEPTH {
+            err_at!(DecodeError, msg: "dag-json nesting beyond {}", MAX_DEPTH)?
+        }
+
+        let val = match self.peek()? {
+            b'n' => self.expect("null").map(|_| Ipld::Null)?,
+            b't' => self.expect("true").map(|_| Ipld::Bool(true))?,
+            b'f' => self.expect("false").map(|_| Ipld::Bool(false))?,
+            b'"' => Ipld::String(self.string()?),
+            b'-' | b'0'..=b'9' => self.number()?,
+            b'[' => {
+                self.off += 1;
+                let mut list = vec![];
+                if self.peek()? == b']' {
+                    self.off += 1;
+                    return Ok(Ipld::List(list));
+                }
+                loop {
+                    list.push(self.value(depth + 1)?);
+                    match self.peek()? {
+                        b',' => self.off += 1,
+                        b']' => break self.off += 1,
+                        ch => err_at!(DecodeError, msg: "dag-json list {:?}", ch as char)?,
+                    }
+                }
+                Ipld::List(list)
+            }
+            b'{' => self.map(depth)?,
+            ch => {
+                err_at!(DecodeError, msg: "dag-json unexpected {:?} at {}", ch as char, self.off)?
+            }
+        };
+        Ok(val)
+    }
+
+    fn map(&mut self, depth: usize) -> Result<Ipld> {
+        self.off += 1;
+        let mut map: BTreeMap<String, Ipld> = BTreeMap::new();
+        if self.peek()? == b'}' {
+            self.off += 1;
+            return Ok(Ipld::Map(map));
+        }
+        loop {
+            if self.peek()? != b'"' {
+                err_at!(DecodeError, msg: "dag-json map key at {}", self.off)?
+            }
+            let key = self.string()?;
+            match map.keys().next_back() {
+                Some(prev) if prev.as_str() >= key.as_str() => {
+                    err_at!(DecodeError, msg: "dag-json map key {:?} not canonical", key)?
+                }
+                _ => (),
+            }
+            self.expect(":")?;
+            let item = self.value(depth + 1)?;
+            map.insert(key, item);
+            match self.peek()? {
+                b',' => self.off += 1,
+                b'}' => break self.off += 1,
+                ch => err_at!(DecodeError, msg: "dag-json map {:?}", ch as char)?,
+            }
+        }
+
+        match map.get("/") {
+            Some(val) if map.len() == 1 => reserved(val),
+            _ => Ok(Ipld::Map(map)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect("\"")?;
+        let mut out: Vec<u8> = vec![];
+        loop {
+            let ch = self.peek()?;
+            self.off += 1;
+            match ch {
+                b'"' => break,
+                b'\\' => {
+                    let esc = self.peek()?;
+                    self.off += 1;
+                    match esc {
+                        b'"' | b'\\' => out.push(esc),
+                        b'b' => out.push(0x08),
+                        b'f' => out.push(0x0c),
+                        b'n' => out.push(b'\n'),
+                        b'r' => out.push(b'\r'),
+                        b't' => out.push(b'\t'),
+                        // only control chars without a short escape.
+                        b'u' => match self.hex4()? {
+                            0x08 | 0x09 | 0x0a | 0x0c | 0x0d => {
+                                err_at!(DecodeError, msg: "dag-json non-canonical \\u escape")?
+                            }
+                            code if code < 0x20 => out.push(code as u8),
+                            code => err_at!(DecodeError, msg: "dag-json escape \\u{:04x}", code)?,
+                        },
+                        esc => err_at!(DecodeError, msg: "dag-json escape {:?}", esc as char)?,
+                    }
+                }
+                ch if ch < 0x20 => err_at!(DecodeError, msg: "dag-json control char in string")?,
+                ch => out.push(ch),
+            }
+        }
+        err_at!(DecodeError, String::from_utf8(out))
+    }
+
+    fn hex4(&mut self) -> Result<u32> {
+        let (hex, _) = read_slice!(&self.buf[self.off..], 4, "dag-json")?;
+        if !hex.iter().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+            err_at!(DecodeError, msg: "dag-json escape hex at {}", self.off)?
+        }
+        let hex = core::str::from_utf8(hex).unwrap();
+        let val = err_at!(DecodeError, u32::from_str_radix(hex, 16))?;
+        self.off += 4;
+        Ok(val)
+    }
+
+    // -? (0 | [1-9][0-9]*) (\.[0-9]+)? ([eE][+-]?[0-9]+)?
+    fn number(&mut self) -> Result<Ipld> {
+        let start = self.off;
+        let digits = |p: &mut Parser| {
+            let start = p.off;
+            while matches!(p.buf.get(p.off), Some(b'0'..=b'9')) {
+                p.off += 1;
+            }
+            p.off - start
+        };
+
+        if self.buf[self.off] == b'-' {
+            self.off += 1;
+        }
+        match digits(self) {
+            0 => err_at!(DecodeError, msg: "dag-json number at {}", start)?,
+            n if n > 1 && self.buf[self.off - n] == b'0' => {
+                err_at!(DecodeError, msg: "dag-json number leading zero at {}", start)?
+            }
+            _ => (),
+        }
+
+        let mut float = false;
+        if self.buf.get(self.off) == Some(&b'.') {
+            self.off += 1;
+            float = true;
+            if digits(self) == 0 {
+                err_at!(DecodeError, msg: "dag-json number fraction at {}", start)?
+            }
+        }
+        if matches!(self.buf.get(self.off), Some(b'e' | b'E')) {
+            self.off += 1;
+            float = true;
+            if matches!(self.buf.get(self.off), Some(b'+' | b'-')) {
+                self.off += 1;
+            }
+            if digits(self) == 0 {
+                err_at!(DecodeError, msg: "dag-json number exponent at {}", start)?
+            }
+        }
+
+        // number chars are ascii, canonical text is what encoder produces.
+        let text = core::str::from_utf8(&self.buf[start..self.off]).unwrap();
+        let (val, canonical) = if float {
+            let f: f64 = err_at!(DecodeError, text.parse())?;
+            if !f.is_finite() {
+                err_at!(DecodeError, msg: "dag-json float {}", text)?
+            }
+            (Ipld::Float(f), format!("{:?}", f))
+        } else {
+            let n: i128 = err_at!(DecodeError, text.parse())?;
+            match u64::try_from(n) {
+                Ok(_) => (),
+                Err(_) if n < 0 && u64::try_from(-1 - n).is_ok() => (),
+                Err(_) => err_at!(DecodeError, msg: "dag-json integer {} out of range", text)?,
+            }
+            (Ipld::Integer(n), n.to_string())
+        };
+        match canonical == text {
+            true => Ok(val),
+            false => err_at!(DecodeError, msg: "dag-json number {} not canonical", text),
+        }
+    }
+}
+
+// Decode the reserved `{"/": ..}` forms.
+fn reserved(val: &Ipld) -> Result<Ipld> {
+    match val {
+        Ipld::String(text) => Ok(Ipld::Link(Cid::from_text(text)?)),
+        Ipld::Map(map) => match map.get("bytes") {
+            Some(Ipld::String(text)) if map.len() == 1 => {
+                Ok(Ipld::Bytes(Base::Base64.decode(text)?))
+            }
+            _ => err_at!(DecodeError, msg: "dag-json reserved bytes form"),
+        },
+        _ => err_at!(DecodeError, msg: "dag-json reserved \"/\" form"),
+    }
+}
@@ -0,0 +1,234 @@
+use super::*;
+
+const CID: &str = "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e";
+
+fn map(items: Vec<(&str, Ipld)>) -> Ipld {
+    Ipld::Map(
+        items
+            .into_iter()
+            .map(|(k, v)| (String::from(k), v))
+            .collect(),
+    )
+}
+
+fn sample() -> Ipld {
+    map(vec![
+        (
+            "a",
+            Ipld::List(vec![
+                Ipld::Integer(1),
+                Ipld::Integer(-2),
+                Ipld::Float(1.5),
+                Ipld::Null,
+                Ipld::Bool(true),
+                Ipld::String(String::from("x\"\n\u{1}é😀")),
+            ]),
+        ),
+        ("bb", Ipld::Bytes(vec![1, 2, 3])),
+        ("c", Ipld::Link(CID.parse().unwrap())),
+        ("", map(vec![])),
+    ])
+}
+
+#[test]
+fn test_ipld() {
+    let val = sample();
+    assert_eq!(val.get("bb"), Some(&Ipld::Bytes(vec![1, 2, 3])));
+    assert_eq!(val.get("zz"), None);
+    assert_eq!(Ipld::Null.get("a"), None);
+
+    let cid: Cid = CID.parse().unwrap();
+    let val = Ipld::List(vec![Ipld::Link(cid.clone()), map(vec![("x", val)])]);
+    assert_eq!(val.to_links(), vec![cid.clone(), cid]);
+}
+
+#[test]
+fn test_dag_cbor() {
+    let testcases: Vec<(Ipld, Vec<u8>)> = vec![
+        (Ipld::Integer(0), vec![0x00]),
+        (Ipld::Integer(23), vec![0x17]),
+        (Ipld::Integer(24), vec![0x18, 0x18]),
+        (Ipld::Integer(256), vec![0x19, 0x01, 0x00]),
+        (Ipld::Integer(-1), vec![0x20]),
+        (
+            Ipld::Integer(u64::MAX as i128),
+            vec![0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        ),
+        (
+            Ipld::Integer(-(u64::MAX as i128) - 1),
+            vec![0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        ),
+        (Ipld::Float(1.5), vec![0xfb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0]),
+        (Ipld::Null, vec![0xf6]),
+        (Ipld::Bool(true), vec![0xf5]),
+        (Ipld::String(String::from("a")), vec![0x61, 0x61]),
+        (Ipld::Bytes(vec![1]), vec![0x41, 0x01]),
+        (Ipld::List(vec![]), vec![0x80]),
+        (
+            map(vec![
+                ("bb", Ipld::Integer(1)),
+                ("a", Ipld::Integer(2)),
+                ("c", Ipld::Integer(3)),
+            ]),
+            vec![
+                0xa3, 0x61, 0x61, 0x02, 0x61, 0x63, 0x03, 0x62, 0x62, 0x62, 0x01,
+            ],
+        ),
+    ];
+    for (val, data) in testcases.into_iter() {
+        assert_eq!(dag_cbor::encode(&val).unwrap(), data, "{:?}", val);
+        assert_eq!(dag_cbor::decode(&data).unwrap(), val, "{:?}", data);
+    }
+
+    let cid: Cid = CID.parse().unwrap();
+    let data = dag_cbor::encode(&Ipld::Link(cid.clone())).unwrap();
+    assert_eq!(data[..5], [0xd8, 0x2a, 0x58, 0x25, 0x00]);
+    assert_eq!(data[5..], cid.encode().unwrap()[..]);
+
+    let val = sample();
+    let data = dag_cbor::encode(&val).unwrap();
+    assert_eq!(dag_cbor::decode(&data).unwrap(), val);
+
+    assert!(dag_cbor::encode(&Ipld::Float(f64::NAN)).is_err());
+    assert!(dag_cbor::encode(&Ipld::Integer(u64::MAX as i128 + 1)).is_err());
+    assert!(dag_cbor::encode(&Ipld::Integer(-(u64::MAX as i128) - 2)).is_err());
+}
+
+#[test]
+fn test_dag_cbor_strict() {
+    let testcases: Vec<Vec<u8>> = vec![
+        // non-minimal integer and length.
+        vec![0x18, 0x17],
+        vec![0x19, 0x00, 0xff],
+        vec![0x78, 0x01, 0x61],
+        // indefinite length.
+        vec![0x9f, 0xff],
+        vec![0x5f, 0x41, 0x01, 0xff],
+        // half and single floats, NaN, infinity.
+        vec![0xf9, 0x3c, 0x00],
+        vec![0xfa, 0x3f, 0x80, 0x00, 0x00],
+        vec![0xfb, 0x7f, 0xf8, 0, 0, 0, 0, 0, 0],
+        vec![0xfb, 0x7f, 0xf0, 0, 0, 0, 0, 0, 0],
+        // undefined, other tags.
+        vec![0xf7],
+        vec![0xc1, 0x00],
+        // map keys unsorted, duplicate, not string.
+        vec![0xa2, 0x62, 0x62, 0x62, 0x01, 0x61, 0x61, 0x02],
+        vec![0xa2, 0x61, 0x61, 0x01, 0x61, 0x61, 0x02],
+        vec![0xa1, 0x01, 0x01],
+        // trailing and truncated bytes.
+        vec![0x00, 0x00],
+        vec![0x82, 0x01],
+        vec![0x62, 0x61],
+        // invalid utf8.
+        vec![0x61, 0xff],
+        // CID without ZERO prefix.
+        vec![0xd8, 0x2a, 0x41, 0x01],
+        vec![],
+    ];
+    for data in testcases.into_iter() {
+        assert!(dag_cbor::decode(&data).is_err(), "{:x?}", data);
+    }
+
+    let mut data = vec![0x81; MAX_DEPTH + 2];
+    data.push(0x00);
+    assert!(dag_cbor::decode(&data).is_err());
+}
+
+#[test]
+fn test_dag_json() {
+    let val = sample();
+    let data = dag_json::encode(&val).unwrap();
+    let text = String::from_utf8(data.clone()).unwrap();
+    assert_eq!(
+        text,
+        format!(
+            concat!(
+                r#"{{"":{{}},"a":[1,-2,1.5,null,true,"x\"\n\u0001é😀"],"#,
+                r#""bb":{{"/":{{"bytes":"AQID"}}}},"c":{{"/":"{}"}}}}"#
+            ),
+            CID
+        )
+    );
+    assert_eq!(dag_json::decode(&data).unwrap(), val);
+
+    let testcases = vec![
+        (Ipld::Float(1.0), "1.0"),
+        (Ipld::Float(1e21), "1e21"),
+        (Ipld::Float(-0.25), "-0.25"),
+        (
+            Ipld::Integer(-(u64::MAX as i128) - 1),
+            "-18446744073709551616",
+        ),
+        (Ipld::Bytes(vec![]), r#"{"/":{"bytes":""}}"#),
+    ];
+    for (val, text) in testcases.into_iter() {
+        assert_eq!(dag_json::encode(&val).unwrap(), text.as_bytes());
+        assert_eq!(dag_json::decode(text.as_bytes()).unwrap(), val);
+    }
+
+    let val = Ipld::String(String::from("é😀/\u{1f}\u{0}\n\""));
+    let text = r#""é😀/\u001f\u0000\n\"""#;
+    assert_eq!(dag_json::encode(&val).unwrap(), text.as_bytes());
+    assert_eq!(dag_json::decode(text.as_bytes()).unwrap(), val);
+
+    // both codecs share the data model.
+    let cbor = dag_cbor::encode(&sample()).unwrap();
+    let json = dag_json::encode(&dag_cbor::decode(&cbor).unwrap()).unwrap();
+    assert_eq!(json, data);
+
+    assert!(dag_json::encode(&map(vec![("/", Ipld::Null)])).is_err());
+    assert!(dag_json::encode(&Ipld::Float(f64::INFINITY)).is_err());
+}
+
+#[test]
+fn test_dag_json_strict() {
+    let testcases = vec![
+        "",
+        " 1",
+        "[1, 2]",
+        r#"{"a":1 }"#,
+        r#"{"b":1,"a":2}"#,
+        r#"{"a":1,"a":2}"#,
+        r#"{1:2}"#,
+        "01",
+        "1.",
+        ".5",
+        "-",
+        "1e",
+        "+1",
+        "NaN",
+        "18446744073709551616",
+        "1e999",
+        "nul",
+        "[1,]",
+        r#""abc"#,
+        "\"a\u{1}\"",
+        r#""\x""#,
+        r#""\ud83d""#,
+        r#"{"/":1}"#,
+        r#"{"/":"notacid"}"#,
+        r#"{"/":{"bytes":"AQID","x":1}}"#,
+        r#"{"/":{"bytes":"AQI="}}"#,
+        "1 ",
+        "[1][2]",
+        // valid json, not in the form the encoder produces.
+        r#""\/""#,
+        r#""\u00e9""#,
+        r#""\ud83d\ude00""#,
+        r#""\u000a""#,
+        r#""\u001F""#,
+        "1E2",
+        "1e+2",
+        "1e2",
+        "1.50",
+        "100.0e0",
+        "-0",
+    ];
+    for text in testcases.into_iter() {
+        assert!(dag_json::decode(text.as_bytes()).is_err(), "{}", text);
+    }
+
+    let text = "[".repeat(MAX_DEPTH + 2) + &"]".repeat(MAX_DEPTH + 2);
+    assert!(dag_json::decode(text.as_bytes()).is_err());
+}
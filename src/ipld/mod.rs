@@ -0,0 +1,65 @@
+//! Module implement IPLD data model and its codecs. _Refer [data-model]
+//! spec for detail_.
+//!
+//! [Ipld] values can be encoded using [dag_cbor], the `dag-cbor` codec, and
+//! [dag_json], the `dag-json` codec. Both codecs are strict, encoders
+//! produce canonical form and decoders reject non-canonical input.
+//!
+//! [data-model]: https://ipld.io/docs/data-model
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::cid::Cid;
+
+pub mod dag_cbor;
+pub mod dag_json;
+
+/// Maximum nesting of lists and maps accepted by encoders and decoders.
+pub const MAX_DEPTH: usize = 64;
+
+/// Type implement IPLD data model.
+///
+/// Integers span the range of CBOR integers, `-2^64` to `2^64 - 1`.
+/// Floats shall be finite, NaN and infinities cannot be encoded.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Ipld {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    List(Vec<Ipld>),
+    Map(BTreeMap<String, Ipld>),
+    Link(Cid),
+}
+
+impl Ipld {
+    /// Lookup `key` in map value, None if not a map or key is missing.
+    pub fn get(&self, key: &str) -> Option<&Ipld> {
+        match self {
+            Ipld::Map(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    /// Return all links in this value, including nested values, in the
+    /// order they are found, depth first.
+    pub fn to_links(&self) -> Vec<Cid> {
+        let mut links = vec![];
+        let mut stack = vec![self];
+        while let Some(val) = stack.pop() {
+            match val {
+                Ipld::Link(cid) => links.push(cid.clone()),
+                Ipld::List(list) => stack.extend(list.iter().rev()),
+                Ipld::Map(map) => stack.extend(map.values().rev()),
+                _ => (),
+            }
+        }
+        links
+    }
+}
+
+#[cfg(test)]
+#[path = "ipld_test.rs"]
+mod ipld_test;
@@ -0,0 +1,511 @@
+//! Module implement IPNS names and records. _Refer [ipns] spec for detail_.
+//!
+//! An IPNS name is the peer-id of the key publishing the name, as text it
+//! is a CIDv1 with `libp2p-key` codec, encoded in base36 (`k51...`).
+//!
+//! IPNS record is a protobuf message, the authoritative fields are held in
+//! its `data` field as DAG-CBOR, signed by `signatureV2`. Legacy V1 fields
+//! are duplicated in the protobuf message for older implementations, when
+//! present they shall match the DAG-CBOR fields.
+//!
+//! Signatures are verified with `ed25519` feature, for Ed25519 keys.
+//!
+//! [ipns]: https://specs.ipfs.tech/ipns/ipns-record
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{cmp, fmt, result, str::FromStr};
+
+use crate::{
+    ipld::{dag_cbor, Ipld},
+    multibase::Base,
+    peer_id::{self, PeerId},
+    protobuf::{self, decode_field},
+    Error, Result,
+};
+
+/// Maximum size of an encoded IPNS record, 10KiB.
+pub const MAX_RECORD_SIZE: usize = 10 * 1024;
+
+/// Prefix for data signed by `signatureV2`.
+pub const SIGNATURE_V2_PREFIX: &[u8] = b"ipns-signature:";
+
+/// Type implement IPNS name, the peer-id of the publishing key.
+#[derive(Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct IpnsName {
+    peer_id: PeerId,
+}
+
+impl fmt::Display for IpnsName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        let text = self.to_text().map_err(|_| fmt::Error)?;
+        write!(f, "{}", text)
+    }
+}
+
+impl fmt::Debug for IpnsName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "IpnsName<{}>", self)
+    }
+}
+
+impl FromStr for IpnsName {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<IpnsName> {
+        IpnsName::from_text(text)
+    }
+}
+
+impl From<PeerId> for IpnsName {
+    fn from(peer_id: PeerId) -> IpnsName {
+        IpnsName { peer_id }
+    }
+}
+
+impl IpnsName {
+    /// Parse IPNS name from CIDv1 `libp2p-key` in any multibase, or from
+    /// the legacy base58btc peer-id.
+    pub fn from_text(text: &str) -> Result<IpnsName> {
+        Ok(IpnsName {
+            peer_id: text.parse()?,
+        })
+    }
+
+    /// Return the text form, base36 encoded CIDv1 with `libp2p-key` codec.
+    pub fn to_text(&self) -> Result<String> {
+        self.peer_id.to_cid_text(Base::Base36Lower)
+    }
+
+    /// Return the peer-id of the publishing key.
+    pub fn to_peer_id(&self) -> PeerId {
+        self.peer_id.clone()
+    }
+
+    /// Return the key used to store records in the routing system,
+    /// `/ipns/` followed by the binary peer-id.
+    pub fn to_routing_key(&self) -> Vec<u8> {
+        let mut key = b"/ipns/".to_vec();
+        key.extend_from_slice(&self.peer_id.to_bytes());
+        key
+    }
+}
+
+/// Type implement IPNS record.
+///
+/// Fields are read from the signed DAG-CBOR `data` field, End-Of-Life
+/// validity is an RFC3339 timestamp and TTL is in nanoseconds.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Record {
+    value: Vec<u8>,
+    validity: Vec<u8>,
+    sequence: u64,
+    ttl: u64,
+    data: Vec<u8>,
+    public_key: Option<Vec<u8>>,
+    signature_v1: Option<Vec<u8>>,
+    signature_v2: Option<Vec<u8>>,
+}
+
+impl Record {
+    /// Create an unsigned record pointing to `value`, say `/ipfs/<cid>`,
+    /// valid until `eol` as `(seconds, nanos)` since unix epoch.
+    pub fn new(value: &[u8], eol: (i64, u32), sequence: u64, ttl: u64) -> Result<Record> {
+        let validity = format_rfc3339(eol)?.into_bytes();
+
+        let mut map = BTreeMap::new();
+        map.insert(String::from("Value"), Ipld::Bytes(value.to_vec()));
+        map.insert(String::from("Validity"), Ipld::Bytes(validity.clone()));
+        map.insert(String::from("ValidityType"), Ipld::Integer(0));
+        map.insert(String::from("Sequence"), Ipld::Integer(sequence.into()));
+        map.insert(String::from("TTL"), Ipld::Integer(ttl.into()));
+
+        let val = Record {
+            value: value.to_vec(),
+            validity,
+            sequence,
+            ttl,
+            data: dag_cbor::encode(&Ipld::Map(map))?,
+            public_key: None,
+            signature_v1: None,
+            signature_v2: None,
+        };
+        Ok(val)
+    }
+
+    /// Decode record from its protobuf form. Record shall carry the
+    /// DAG-CBOR `data` field, V1 fields if present shall match it.
+    pub fn decode(buf: &[u8]) -> Result<Record> {
+        if buf.len() > MAX_RECORD_SIZE {
+            err_at!(DecodeError, msg: "ipns record size {}", buf.len())?
+        }
+
+        let mut v1 = Record::new_empty();
+        let (mut validity_type, mut data, mut legacy) = (None, None, false);
+        let mut rem = buf;
+        while !rem.is_empty() {
+            let (field, v, r) = decode_field(rem)?;
+            legacy = legacy || matches!(field, 1 | 3 | 4 | 5 | 6);
+            match field {
+                1 => v1.value = v.to_bytes(field)?.to_vec(),
+                2 => v1.signature_v1 = Some(v.to_bytes(field)?.to_vec()),
+                3 => validity_type = Some(v.to_varint(field)?),
+                4 => v1.validity = v.to_bytes(field)?.to_vec(),
+                5 => v1.sequence = v.to_varint(field)?,
+                6 => v1.ttl = v.to_varint(field)?,
+                7 => v1.public_key = Some(v.to_bytes(field)?.to_vec()),
+                8 => v1.signature_v2 = Some(v.to_bytes(field)?.to_vec()),
+                9 => data = Some(v.to_bytes(field)?.to_vec()),
+                _ => (),
+            }
+            rem = r;
+        }
+
+        let data = match data {
+            Some(data) => data,
+            None => err_at!(DecodeError, msg: "ipns record missing data")?,
+        };
+        let val = Record {
+            public_key: v1.public_key.take(),
+            signature_v1: v1.signature_v1.take(),
+            signature_v2: v1.signature_v2.take(),
+            ..Record::decode_data(data)?
+        };
+
+        // legacy fields, when present, shall match the signed data.
+        match validity_type {
+            Some(0) | None => (),
+            Some(n) => err_at!(DecodeError, msg: "ipns validity type {}", n)?,
+        }
+        if legacy {
+            let ok = v1.value == val.value
+                && v1.validity == val.validity
+                && v1.sequence == val.sequence
+                && v1.ttl == val.ttl;
+            if !ok {
+                err_at!(DecodeError, msg: "ipns record V1 fields mismatch data")?
+            }
+        }
+
+        Ok(val)
+    }
+
+    /// Encode record into its protobuf form, along with V1 fields.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut buf = vec![];
+        protobuf::encode_bytes_field(&mut buf, 1, &self.value);
+        if let Some(sig) = &self.signature_v1 {
+            protobuf::encode_bytes_field(&mut buf, 2, sig);
+        }
+        protobuf::encode_varint_field(&mut buf, 3, 0);
+        protobuf::encode_bytes_field(&mut buf, 4, &self.validity);
+        protobuf::encode_varint_field(&mut buf, 5, self.sequence);
+        protobuf::encode_varint_field(&mut buf, 6, self.ttl);
+        if let Some(key) = &self.public_key {
+            protobuf::encode_bytes_field(&mut buf, 7, key);
+        }
+        if let Some(sig) = &self.signature_v2 {
+            protobuf::encode_bytes_field(&mut buf, 8, sig);
+        }
+        protobuf::encode_bytes_field(&mut buf, 9, &self.data);
+
+        if buf.len() > MAX_RECORD_SIZE {
+            err_at!(BadInput, msg: "ipns record size {}", buf.len())?
+        }
+        Ok(buf)
+    }
+
+    /// Set the publishing key, serialized as libp2p's `PublicKey`
+    /// protobuf message. Required when the key is not inlined in the
+    /// IPNS name, like for RSA keys.
+    pub fn set_public_key(&mut self, key: &[u8]) -> &mut Self {
+        self.public_key = Some(key.to_vec());
+        self
+    }
+
+    /// Set signature over [Record::to_signing_data_v1], deprecated.
+    pub fn set_signature_v1(&mut self, signature: &[u8]) -> &mut Self {
+        self.signature_v1 = Some(signature.to_vec());
+        self
+    }
+
+    /// Set signature over [Record::to_signing_data].
+    pub fn set_signature_v2(&mut self, signature: &[u8]) -> &mut Self {
+        self.signature_v2 = Some(signature.to_vec());
+        self
+    }
+
+    /// Return the data to be signed for `signatureV2`,
+    /// [SIGNATURE_V2_PREFIX] followed by DAG-CBOR `data` field.
+    pub fn to_signing_data(&self) -> Vec<u8> {
+        let mut buf = SIGNATURE_V2_PREFIX.to_vec();
+        buf.extend_from_slice(&self.data);
+        buf
+    }
+
+    /// Return the data to be signed for the deprecated `signatureV1`,
+    /// value, validity and the validity-type `EOL`.
+    pub fn to_signing_data_v1(&self) -> Vec<u8> {
+        let mut buf = self.value.clone();
+        buf.extend_from_slice(&self.validity);
+        buf.extend_from_slice(b"EOL");
+        buf
+    }
+
+    /// Return the path this record points to.
+    pub fn as_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Return the End-Of-Life as `(seconds, nanos)` since unix epoch.
+    pub fn to_eol(&self) -> Result<(i64, u32)> {
+        match core::str::from_utf8(&self.validity) {
+            Ok(text) => parse_rfc3339(text),
+            Err(_) => err_at!(DecodeError, msg: "ipns validity not utf8"),
+        }
+    }
+
+    /// Return the sequence number.
+    pub fn to_sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Return the caching hint in nanoseconds.
+    pub fn to_ttl(&self) -> u64 {
+        self.ttl
+    }
+
+    /// Return the DAG-CBOR `data` field.
+    pub fn as_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Return the serialized public key, if carried by the record.
+    pub fn as_public_key(&self) -> Option<&[u8]> {
+        self.public_key.as_deref()
+    }
+
+    /// Return the `signatureV2` field.
+    pub fn as_signature_v2(&self) -> Option<&[u8]> {
+        self.signature_v2.as_deref()
+    }
+
+    /// Validate the record for `name` at time `now`, seconds since unix
+    /// epoch. Record shall be signed, not expired and its public key,
+    /// inlined in the name or carried in the record, shall match the
+    /// name. Signature is not verified, refer [Record::verify].
+    pub fn validate(&self, name: &IpnsName, now: i64) -> Result<()> {
+        if self.signature_v2.is_none() {
+            err_at!(BadInput, msg: "ipns record missing signatureV2")?
+        }
+        self.to_public_key(name)?;
+        if self.to_eol()? < (now, 0) {
+            err_at!(BadInput, msg: "ipns record expired")?
+        }
+        Ok(())
+    }
+
+    /// Validate the record, refer [Record::validate], and verify its
+    /// `signatureV2`. Only Ed25519 keys are supported.
+    #[cfg(feature = "ed25519")]
+    pub fn verify(&self, name: &IpnsName, now: i64) -> Result<()> {
+        use core::convert::TryInto;
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        self.validate(name, now)?;
+
+        let key = match self.to_public_key(name)? {
+            (peer_id::KeyType::Ed25519, key) => {
+                let key: [u8; 32] = match key.as_slice().try_into() {
+                    Ok(key) => key,
+                    Err(_) => err_at!(BadInput, msg: "ipns ed25519 key length {}", key.len())?,
+                };
+                err_at!(BadInput, VerifyingKey::from_bytes(&key))?
+            }
+            (key_type, _) => err_at!(NotImplemented, msg: "ipns {:?} signature", key_type)?,
+        };
+        let sig = self.signature_v2.as_deref().unwrap_or_default();
+        let sig = err_at!(BadInput, Signature::from_slice(sig))?;
+        err_at!(BadInput, key.verify(&self.to_signing_data(), &sig))
+    }
+
+    /// Order records for the same name, higher sequence number is newer,
+    /// for the same sequence number the later End-Of-Life is newer.
+    pub fn compare(&self, other: &Record) -> Result<cmp::Ordering> {
+        match self.sequence.cmp(&other.sequence) {
+            cmp::Ordering::Equal => Ok(self.to_eol()?.cmp(&other.to_eol()?)),
+            ord => Ok(ord),
+        }
+    }
+
+    fn new_empty() -> Record {
+        Record {
+            value: vec![],
+            validity: vec![],
+            sequence: 0,
+            ttl: 0,
+            data: vec![],
+            public_key: None,
+            signature_v1: None,
+            signature_v2: None,
+        }
+    }
+
+    fn decode_data(data: Vec<u8>) -> Result<Record> {
+        let map = match dag_cbor::decode(&data)? {
+            Ipld::Map(map) => map,
+            _ => err_at!(DecodeError, msg: "ipns data not a map")?,
+        };
+        let bytes = |key: &str| match map.get(key) {
+            Some(Ipld::Bytes(val)) => Ok(val.clone()),
+            _ => err_at!(DecodeError, msg: "ipns data {:?}", key),
+        };
+        let uint = |key: &str| match map.get(key) {
+            Some(Ipld::Integer(n)) if *n >= 0 && *n <= u64::MAX.into() => Ok(*n as u64),
+            _ => err_at!(DecodeError, msg: "ipns data {:?}", key),
+        };
+
+        if uint("ValidityType")? != 0 {
+            err_at!(DecodeError, msg: "ipns validity type {}", uint("ValidityType")?)?
+        }
+        let val = Record {
+            value: bytes("Value")?,
+            validity: bytes("Validity")?,
+            sequence: uint("Sequence")?,
+            ttl: uint("TTL")?,
+            data,
+            ..Record::new_empty()
+        };
+        val.to_eol()?;
+        Ok(val)
+    }
+
+    // Public key carried by the record, matching the name, else the key
+    // inlined in the name.
+    fn to_public_key(&self, name: &IpnsName) -> Result<(peer_id::KeyType, Vec<u8>)> {
+        match &self.public_key {
+            Some(key) if PeerId::from_protobuf(key)? == name.peer_id => {
+                peer_id::decode_public_key(key)
+            }
+            Some(_) => err_at!(BadInput, msg: "ipns public key does not match {}", name),
+            None => match name.peer_id.to_public_key()? {
+                Some(key) => Ok(key),
+                None => err_at!(BadInput, msg: "ipns record missing public key"),
+            },
+        }
+    }
+}
+
+/// Format `(seconds, nanos)` since unix epoch as RFC3339 timestamp in
+/// UTC with nanosecond precision, as used by IPNS validity.
+pub fn format_rfc3339((secs, nanos): (i64, u32)) -> Result<String> {
+    let (days, rem) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+    let (y, m, d) = civil_from_days(days);
+    if !(0..=9999).contains(&y) || nanos >= 1_000_000_000 {
+        err_at!(BadInput, msg: "timestamp {}.{} out of range", secs, nanos)?
+    }
+    let (hh, mm, ss) = (rem / 3600, (rem % 3600) / 60, rem % 60);
+    let text = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}Z",
+        y, m, d, hh, mm, ss, nanos
+    );
+    Ok(text)
+}
+
+/// Parse RFC3339 timestamp into `(seconds, nanos)` since unix epoch.
+pub fn parse_rfc3339(text: &str) -> Result<(i64, u32)> {
+    let b = text.as_bytes();
+    let num = |s: usize, e: usize| -> Result<i64> {
+        match b.get(s..e) {
+            Some(digits) if digits.iter().all(u8::is_ascii_digit) => {
+                Ok(digits.iter().fold(0, |a, d| a * 10 + i64::from(d - b'0')))
+            }
+            _ => err_at!(DecodeError, msg: "timestamp {:?}", text),
+        }
+    };
+    let sep = |i: usize, chars: &[u8]| match b.get(i) {
+        Some(ch) if chars.contains(ch) => Ok(()),
+        _ => err_at!(DecodeError, msg: "timestamp {:?}", text),
+    };
+
+    sep(4, b"-")?;
+    sep(7, b"-")?;
+    sep(10, b"Tt")?;
+    sep(13, b":")?;
+    sep(16, b":")?;
+    let (y, m, d) = (num(0, 4)?, num(5, 7)?, num(8, 10)?);
+    let (hh, mm, ss) = (num(11, 13)?, num(14, 16)?, num(17, 19)?);
+    if !(1..=12).contains(&m) || d < 1 || d > days_in_month(y, m) || hh > 23 || mm > 59 || ss > 60 {
+        err_at!(DecodeError, msg: "timestamp {:?}", text)?
+    }
+
+    let (mut off, mut nanos) = (19, 0);
+    if b.get(off) == Some(&b'.') {
+        let n = b[off + 1..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if n == 0 || n > 9 {
+            err_at!(DecodeError, msg: "timestamp fraction {:?}", text)?
+        }
+        nanos = (num(off + 1, off + 1 + n)? * 10_i64.pow(9 - n as u32)) as u32;
+        off += 1 + n;
+    }
+
+    let offset = match b.get(off) {
+        Some(b'Z') | Some(b'z') if b.len() == off + 1 => 0,
+        Some(b'+') | Some(b'-') if b.len() == off + 6 => {
+            sep(off + 3, b":")?;
+            let (oh, om) = (num(off + 1, off + 3)?, num(off + 4, off + 6)?);
+            if oh > 23 || om > 59 {
+                err_at!(DecodeError, msg: "timestamp offset {:?}", text)?
+            }
+            let offset = oh * 3600 + om * 60;
+            match b[off] {
+                b'+' => offset,
+                _ => -offset,
+            }
+        }
+        _ => err_at!(DecodeError, msg: "timestamp zone {:?}", text)?,
+    };
+
+    let secs = days_from_civil(y, m, d) * 86400 + hh * 3600 + mm * 60 + ss - offset;
+    Ok((secs, nanos))
+}
+
+fn days_in_month(y: i64, m: i64) -> i64 {
+    match m {
+        2 if (y % 4 == 0 && y % 100 != 0) || y % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since unix epoch for proleptic Gregorian date, and its inverse.
+// Refer http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+#[cfg(test)]
+#[path = "ipns_test.rs"]
+mod ipns_test;
@@ -0,0 +1,280 @@
+use super::*;
+use crate::peer_id::KeyType;
+
+const EOL: i64 = 2_000_000_000; // 2033-05-18T03:33:20Z
+
+// RFC 8032 Ed25519 test 1 key pair, and its name computed independently.
+#[cfg(feature = "ed25519")]
+const RFC8032_SECRET: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+const RFC8032_PUBLIC: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+const RFC8032_NAME: &str = "k51qzi5uqu5dljtg5upm7x7ugan9lql3ewyknv4r4mhhkwzn8n7cnbd1unfwgq";
+const RFC8032_PEER_ID: &str = "12D3KooWQK1wnefoLrcVHbbnf5tLzbopUd3K3bFAoJpA7YJgL5pV";
+
+// Names of the IPNS spec test-vector records, V1-only, V1+V2, V1+V2 with
+// mismatching V1 value and V2-only.
+const SPEC_NAMES: [&str; 4] = [
+    "k51qzi5uqu5dm4tm0wt8srkg9h9suud4wuiwjimndrkydqm81cqtlb5ak6p7ku",
+    "k51qzi5uqu5dlkw8pxuw9qmqayfdeh4kfebhmreauqdc6a7c3y7d5i9fi8mk9w",
+    "k51qzi5uqu5dlmit2tuwdvnx4sbnyqgmvbxftl0eo3f33wwtb9gr7yozae9kpw",
+    "k51qzi5uqu5dit2ku9mutlfgwyz8u730on38kd10m97m36bjt66my99hb6103f",
+];
+
+fn ed25519_name(key: &[u8]) -> IpnsName {
+    PeerId::from_public_key(KeyType::Ed25519, key)
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn test_ipns_name() {
+    let name = ed25519_name(&[1; 32]);
+    let text = name.to_text().unwrap();
+    // Ed25519 keys are inlined, names share this prefix.
+    assert!(text.starts_with("k51qzi5uqu5d"), "{}", text);
+    assert_eq!(name.to_string(), text);
+    assert_eq!(text.parse::<IpnsName>().unwrap(), name);
+
+    let peer_id = name.to_peer_id();
+    assert_eq!(IpnsName::from_text(&peer_id.to_base58()).unwrap(), name);
+    assert_eq!(
+        IpnsName::from_text(&peer_id.to_cid_text(Base::Base32Lower).unwrap()).unwrap(),
+        name
+    );
+
+    let key = name.to_routing_key();
+    assert_eq!(&key[..6], b"/ipns/");
+    assert_eq!(key[6..], peer_id.to_bytes()[..]);
+
+    assert!(IpnsName::from_text("k51qzi5uqu5d").is_err());
+    assert!(
+        IpnsName::from_text("bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e").is_err()
+    );
+}
+
+#[test]
+fn test_ipns_name_vectors() {
+    let key = Base::Base16Lower.decode(RFC8032_PUBLIC).unwrap();
+    let name = ed25519_name(&key);
+    assert_eq!(name.to_text().unwrap(), RFC8032_NAME);
+    assert_eq!(name.to_peer_id().to_base58(), RFC8032_PEER_ID);
+    assert_eq!(IpnsName::from_text(RFC8032_NAME).unwrap(), name);
+    assert_eq!(IpnsName::from_text(RFC8032_PEER_ID).unwrap(), name);
+
+    for text in SPEC_NAMES.iter() {
+        let name = IpnsName::from_text(text).unwrap();
+        assert_eq!(&name.to_text().unwrap(), text);
+        let (key_type, key) = name.to_peer_id().to_public_key().unwrap().unwrap();
+        assert_eq!((key_type, key.len()), (KeyType::Ed25519, 32), "{}", text);
+        assert_eq!(ed25519_name(&key), name);
+    }
+}
+
+#[test]
+fn test_ipns_rfc3339() {
+    let testcases = vec![
+        ((0, 0), "1970-01-01T00:00:00.000000000Z"),
+        ((EOL, 5), "2033-05-18T03:33:20.000000005Z"),
+        ((951782400, 123456789), "2000-02-29T00:00:00.123456789Z"),
+        ((-1, 0), "1969-12-31T23:59:59.000000000Z"),
+    ];
+    for (ts, text) in testcases.into_iter() {
+        assert_eq!(format_rfc3339(ts).unwrap(), text);
+        assert_eq!(parse_rfc3339(text).unwrap(), ts);
+    }
+
+    assert_eq!(parse_rfc3339("2033-05-18T03:33:20Z").unwrap(), (EOL, 0));
+    assert_eq!(
+        parse_rfc3339("2033-05-18t09:03:20.5+05:30").unwrap(),
+        (EOL, 500_000_000)
+    );
+    assert_eq!(
+        parse_rfc3339("2033-05-17T23:33:20-04:00").unwrap(),
+        (EOL, 0)
+    );
+
+    let testcases = vec![
+        "",
+        "2033-05-18",
+        "2033-05-18T03:33:20",
+        "2033-05-18 03:33:20Z",
+        "2033-13-18T03:33:20Z",
+        "2023-02-29T03:33:20Z",
+        "2033-05-18T24:33:20Z",
+        "2033-05-18T03:33:20.Z",
+        "2033-05-18T03:33:20.0000000001Z",
+        "2033-05-18T03:33:20+0530",
+        "2033-05-18T03:33:20Zx",
+        "+033-05-18T03:33:20Z",
+    ];
+    for text in testcases.into_iter() {
+        assert!(parse_rfc3339(text).is_err(), "{}", text);
+    }
+    assert!(format_rfc3339((0, 1_000_000_000)).is_err());
+    assert!(format_rfc3339((i64::MAX / 2, 0)).is_err());
+}
+
+#[test]
+fn test_ipns_record() {
+    let name = ed25519_name(&[1; 32]);
+    let mut record = Record::new(b"/ipfs/bafkqaaa", (EOL, 0), 7, 3_600_000_000_000).unwrap();
+    assert!(record.validate(&name, 0).is_err());
+    record.set_signature_v2(&[0xaa; 64]);
+
+    assert_eq!(record.as_value(), b"/ipfs/bafkqaaa");
+    assert_eq!(record.to_eol().unwrap(), (EOL, 0));
+    assert_eq!(record.to_sequence(), 7);
+    assert_eq!(record.to_ttl(), 3_600_000_000_000);
+    assert_eq!(record.as_signature_v2(), Some(&[0xaa; 64][..]));
+    assert_eq!(record.as_public_key(), None);
+
+    // data is canonical DAG-CBOR, keys sorted by length.
+    assert_eq!(record.as_data()[..5], [0xa5, 0x63, b'T', b'T', b'L']);
+    match dag_cbor::decode(record.as_data()).unwrap() {
+        Ipld::Map(map) => {
+            let keys: Vec<&str> = map.keys().map(|k| k.as_str()).collect();
+            assert_eq!(
+                keys,
+                vec!["Sequence", "TTL", "Validity", "ValidityType", "Value"]
+            );
+        }
+        val => panic!("{:?}", val),
+    }
+    let data = record.to_signing_data();
+    assert_eq!(&data[..15], SIGNATURE_V2_PREFIX);
+    assert_eq!(data[15..], record.as_data()[..]);
+    assert_eq!(
+        record.to_signing_data_v1(),
+        b"/ipfs/bafkqaaa2033-05-18T03:33:20.000000000ZEOL".to_vec()
+    );
+
+    let buf = record.encode().unwrap();
+    assert_eq!(Record::decode(&buf).unwrap(), record);
+
+    assert!(record.validate(&name, EOL - 1).is_ok());
+    assert!(record.validate(&name, EOL).is_ok());
+    assert!(record.validate(&name, EOL + 1).is_err());
+
+    // keys that are not inlined shall be carried by the record.
+    let rsa_key = vec![0x30; 64];
+    let rsa_name: IpnsName = PeerId::from_public_key(KeyType::Rsa, &rsa_key)
+        .unwrap()
+        .into();
+    assert!(record.validate(&rsa_name, 0).is_err());
+    let mut pb = vec![0x08, 0x00, 0x12, 0x40];
+    pb.extend_from_slice(&rsa_key);
+    record.set_public_key(&pb);
+    assert!(record.validate(&rsa_name, 0).is_ok());
+    assert!(record.validate(&ed25519_name(&[2; 32]), 0).is_err());
+    record.set_public_key(&[0x08, 0x01, 0x12, 0x01, 0x00]);
+    assert!(record.validate(&rsa_name, 0).is_err());
+
+    // higher sequence wins, then later EOL.
+    let a = Record::new(b"/ipfs/a", (EOL, 0), 1, 0).unwrap();
+    let b = Record::new(b"/ipfs/b", (EOL - 10, 0), 2, 0).unwrap();
+    let c = Record::new(b"/ipfs/c", (EOL, 1), 2, 0).unwrap();
+    assert_eq!(b.compare(&a).unwrap(), cmp::Ordering::Greater);
+    assert_eq!(b.compare(&c).unwrap(), cmp::Ordering::Less);
+    assert_eq!(c.compare(&c).unwrap(), cmp::Ordering::Equal);
+}
+
+#[test]
+fn test_ipns_record_decode() {
+    let mut record = Record::new(b"/ipfs/bafkqaaa", (EOL, 0), 7, 0).unwrap();
+    record.set_signature_v2(&[0xaa; 64]);
+    let data = record.as_data().to_vec();
+
+    // V2 only record.
+    let mut buf = vec![];
+    protobuf::encode_bytes_field(&mut buf, 8, &[0xaa; 64]);
+    protobuf::encode_bytes_field(&mut buf, 9, &data);
+    assert_eq!(Record::decode(&buf).unwrap(), record);
+
+    // V1 fields shall match data.
+    let mut bad = vec![];
+    protobuf::encode_varint_field(&mut bad, 5, 8);
+    bad.extend_from_slice(&buf);
+    assert!(Record::decode(&bad).is_err());
+    let mut bad = vec![];
+    protobuf::encode_varint_field(&mut bad, 3, 1);
+    bad.extend_from_slice(&buf);
+    assert!(Record::decode(&bad).is_err());
+
+    // missing or malformed data.
+    let mut bad = vec![];
+    protobuf::encode_bytes_field(&mut bad, 8, &[0xaa; 64]);
+    assert!(Record::decode(&bad).is_err());
+    let mut bad = vec![];
+    protobuf::encode_bytes_field(&mut bad, 9, &[0xa0]);
+    assert!(Record::decode(&bad).is_err());
+
+    let mut map = match dag_cbor::decode(&data).unwrap() {
+        Ipld::Map(map) => map,
+        _ => unreachable!(),
+    };
+    map.insert(String::from("Validity"), Ipld::Bytes(b"tomorrow".to_vec()));
+    let mut bad = vec![];
+    protobuf::encode_bytes_field(&mut bad, 9, &dag_cbor::encode(&Ipld::Map(map)).unwrap());
+    assert!(Record::decode(&bad).is_err());
+
+    assert!(Record::decode(&vec![0; MAX_RECORD_SIZE + 1]).is_err());
+    let big = Record::new(&vec![0; MAX_RECORD_SIZE], (EOL, 0), 0, 0).unwrap();
+    assert!(big.encode().is_err());
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn test_ipns_verify() {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let name = ed25519_name(key.verifying_key().as_bytes());
+
+    let mut record = Record::new(b"/ipfs/bafkqaaa", (EOL, 0), 1, 0).unwrap();
+    let sig = key.sign(&record.to_signing_data());
+    record.set_signature_v2(&sig.to_bytes());
+
+    let record = Record::decode(&record.encode().unwrap()).unwrap();
+    assert!(record.verify(&name, 0).is_ok());
+    assert!(record.verify(&name, EOL + 1).is_err());
+    assert!(record.verify(&ed25519_name(&[1; 32]), 0).is_err());
+
+    let mut other = Record::new(b"/ipfs/bafkqaaa", (EOL, 0), 2, 0).unwrap();
+    other.set_signature_v2(&sig.to_bytes());
+    assert!(other.verify(&name, 0).is_err());
+    other.set_signature_v2(&[0; 10]);
+    assert!(other.verify(&name, 0).is_err());
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn test_ipns_verify_rfc8032() {
+    use core::convert::TryInto;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let secret = Base::Base16Lower.decode(RFC8032_SECRET).unwrap();
+    let key = SigningKey::from_bytes(&secret.as_slice().try_into().unwrap());
+    let public = Base::Base16Lower.decode(RFC8032_PUBLIC).unwrap();
+    assert_eq!(key.verifying_key().as_bytes()[..], public[..]);
+    let name = IpnsName::from_text(RFC8032_NAME).unwrap();
+
+    // V1+V2, both signatures present.
+    let mut record = Record::new(b"/ipfs/bafkqaaa", (EOL, 0), 1, 0).unwrap();
+    let sig_v1 = key.sign(&record.to_signing_data_v1());
+    let sig_v2 = key.sign(&record.to_signing_data());
+    record.set_signature_v1(&sig_v1.to_bytes());
+    record.set_signature_v2(&sig_v2.to_bytes());
+    let record = Record::decode(&record.encode().unwrap()).unwrap();
+    assert!(record.verify(&name, 0).is_ok());
+
+    // V2-only.
+    let mut record = Record::new(b"/ipfs/bafkqaaa", (EOL, 0), 1, 0).unwrap();
+    record.set_signature_v2(&sig_v2.to_bytes());
+    let record = Record::decode(&record.encode().unwrap()).unwrap();
+    assert!(record.verify(&name, 0).is_ok());
+
+    // V1-only records are rejected.
+    let mut record = Record::new(b"/ipfs/bafkqaaa", (EOL, 0), 1, 0).unwrap();
+    record.set_signature_v1(&sig_v1.to_bytes());
+    let record = Record::decode(&record.encode().unwrap()).unwrap();
+    assert!(record.verify(&name, 0).is_err());
+}
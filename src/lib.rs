@@ -1,8 +1,19 @@
 //! Package implement multiformat specifications.
+//!
+//! By default the package is built with the `std` feature. Disabling it
+//! builds the package for `#![no_std]` targets, with `alloc`, where all
+//! modules except [multiaddr], [car] and the UnixFS importer are available.
+//!
+//! The `ed25519` feature enables verification of Ed25519 signatures, for
+//! IPNS records.
 
-#![feature(box_syntax, box_patterns)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::{error, fmt, result};
+#[macro_use]
+extern crate alloc;
+
+use alloc::string::String;
+use core::{fmt, result};
 
 /// Short form to compose Error values.
 ///
@@ -50,21 +61,59 @@ macro_rules! err_at {
     }};
 }
 
+/// Split `$n` bytes from the front of `$data`, return [Error] if
+/// `$data` is shorter than `$n` bytes.
+macro_rules! read_slice {
+    ($data:expr, $n:expr, $prefix:expr) => {
+        if $data.len() < $n {
+            Err(Error::InsufficientBytes {
+                protocol: alloc::string::String::from($prefix),
+                needed: $n,
+            })
+        } else {
+            Ok((&$data[..$n], &$data[$n..]))
+        }
+    };
+}
+
 #[macro_use]
 extern crate data_encoding_macro;
 
+pub mod bitcoin;
+pub mod block;
+#[cfg(feature = "std")]
+pub mod car;
+pub mod cid;
+pub mod content_path;
+pub mod dag_pb;
+pub mod did_key;
+pub mod eth;
+pub mod ipld;
+pub mod ipns;
+#[cfg(feature = "std")]
 pub mod multiaddr;
 pub mod multibase;
 pub mod multicodec;
 pub mod multihash;
+pub mod multikey;
+pub mod peer_id;
+mod protobuf;
+#[cfg(feature = "serde")]
+mod serdes;
+pub mod swarm;
+pub mod unixfs;
+pub mod varsig;
 
 /// Type alias for Result return type, used by this package.
 pub type Result<T> = result::Result<T, Error>;
 
 /// Error variants that can be returned by this package's API.
 ///
-/// Each variant carries a prefix, typically identifying the
-/// error location.
+/// Variants with `(String, String)` carry a prefix, typically identifying
+/// the error location, and a message. Other variants carry typed details
+/// that callers can match on. Errors from `unsigned-varint`, `bs58` and
+/// `data-encoding` are wrapped and available via
+/// [std::error::Error::source].
 pub enum Error {
     Fatal(String, String),
     IOError(String, String),
@@ -73,6 +122,28 @@ pub enum Error {
     BadInput(String, String),
     BadAddr(String, String),
     NotImplemented(String, String),
+    /// Codec is unknown, or not supported where it was used.
+    UnknownCodec(u128),
+    /// Unsigned varint does not fit within its integer type.
+    VarintOverflow,
+    /// Input is too short, `protocol` needs `needed` bytes.
+    InsufficientBytes {
+        protocol: String,
+        needed: usize,
+    },
+    /// Character is not a known multibase prefix.
+    InvalidBase(char),
+    /// Digest is longer than the output of its hash algorithm.
+    DigestLengthMismatch {
+        expected: usize,
+        got: usize,
+    },
+    /// Malformed unsigned varint.
+    Varint(unsigned_varint::decode::Error),
+    /// Malformed base58 text.
+    Base58(bs58::decode::Error),
+    /// Malformed base encoded text.
+    DataEncoding(data_encoding::DecodeError),
 }
 
 impl fmt::Display for Error {
@@ -87,6 +158,18 @@ impl fmt::Display for Error {
             BadInput(p, msg) => write!(f, "{} BadInput: {}", p, msg),
             BadAddr(p, msg) => write!(f, "{} BadAddr: {}", p, msg),
             NotImplemented(p, msg) => write!(f, "{} NotImplemented: {}", p, msg),
+            UnknownCodec(code) => write!(f, "UnknownCodec: 0x{:x}", code),
+            VarintOverflow => write!(f, "VarintOverflow"),
+            InsufficientBytes { protocol, needed } => {
+                write!(f, "InsufficientBytes: {} needs {} bytes", protocol, needed)
+            }
+            InvalidBase(ch) => write!(f, "InvalidBase: {:?}", ch),
+            DigestLengthMismatch { expected, got } => {
+                write!(f, "DigestLengthMismatch: expected {} got {}", expected, got)
+            }
+            Varint(err) => write!(f, "Varint: {}", err),
+            Base58(err) => write!(f, "Base58: {}", err),
+            DataEncoding(err) => write!(f, "DataEncoding: {}", err),
         }
     }
 }
@@ -97,4 +180,35 @@ impl fmt::Debug for Error {
     }
 }
 
-impl error::Error for Error {}
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Varint(err) => Some(err),
+            Error::Base58(err) => Some(err),
+            Error::DataEncoding(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<unsigned_varint::decode::Error> for Error {
+    fn from(err: unsigned_varint::decode::Error) -> Error {
+        match err {
+            unsigned_varint::decode::Error::Overflow => Error::VarintOverflow,
+            err => Error::Varint(err),
+        }
+    }
+}
+
+impl From<bs58::decode::Error> for Error {
+    fn from(err: bs58::decode::Error) -> Error {
+        Error::Base58(err)
+    }
+}
+
+impl From<data_encoding::DecodeError> for Error {
+    fn from(err: data_encoding::DecodeError) -> Error {
+        Error::DataEncoding(err)
+    }
+}
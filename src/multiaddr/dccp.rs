@@ -24,7 +24,7 @@ impl Dccp {
     }
 
     pub(crate) fn to_text(&self) -> Result<String> {
-        Ok("/dccp".to_string() + &self.port.to_string())
+        Ok("/dccp/".to_string() + &self.port.to_string())
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -24,8 +24,8 @@ impl Dns {
     pub(crate) fn to_text(&self) -> Result<String> {
         use std::str::from_utf8;
 
-        let s = "/dns".to_string();
-        Ok(s + &err_at!(DecodeError, from_utf8(&self.addr))?)
+        let s = "/dns/".to_string();
+        Ok(s + err_at!(DecodeError, from_utf8(&self.addr))?)
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -33,7 +33,7 @@ impl Dns {
 
         let val = {
             let (addr, data) = {
-                let (n, data) = err_at!(DecodeError, uv_decode(data))?;
+                let (n, data) = uv_decode(data)?;
                 let (name, data) = read_slice!(data, (n as usize), "dns")?;
                 (name.to_vec(), data)
             };
@@ -23,7 +23,7 @@ impl Dns4 {
 
     pub(crate) fn to_text(&self) -> Result<String> {
         use std::str::from_utf8;
-        Ok("/dns4".to_string() + &err_at!(DecodeError, from_utf8(&self.addr))?)
+        Ok("/dns4/".to_string() + err_at!(DecodeError, from_utf8(&self.addr))?)
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -31,7 +31,7 @@ impl Dns4 {
 
         let val = {
             let (addr, data) = {
-                let (n, data) = err_at!(DecodeError, uv_decode(data))?;
+                let (n, data) = uv_decode(data)?;
                 let (name, data) = read_slice!(data, (n as usize), "dns4")?;
                 (name.to_vec(), data)
             };
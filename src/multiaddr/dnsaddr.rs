@@ -24,8 +24,8 @@ impl Dnsaddr {
     pub(crate) fn to_text(&self) -> Result<String> {
         use std::str::from_utf8;
 
-        let s = "/dnsaddr".to_string();
-        Ok(s + &err_at!(DecodeError, from_utf8(&self.addr))?)
+        let s = "/dnsaddr/".to_string();
+        Ok(s + err_at!(DecodeError, from_utf8(&self.addr))?)
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -33,7 +33,7 @@ impl Dnsaddr {
 
         let val = {
             let (addr, data) = {
-                let (n, data) = err_at!(DecodeError, uv_decode(data))?;
+                let (n, data) = uv_decode(data)?;
                 let (name, data) = read_slice!(data, (n as usize), "dnsaddr")?;
                 (name.to_vec(), data)
             };
@@ -55,4 +55,9 @@ impl Dnsaddr {
         data.extend_from_slice(&self.addr);
         Ok(data)
     }
+
+    pub fn as_str(&self) -> Result<&str> {
+        use std::str::from_utf8;
+        err_at!(DecodeError, from_utf8(&self.addr))
+    }
 }
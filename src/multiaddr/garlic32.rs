@@ -22,7 +22,7 @@ impl Garlic32 {
     }
 
     pub(crate) fn to_text(&self) -> Result<String> {
-        Ok("/garlic32".to_string() + &to_garlic32(&self.addr)?)
+        Ok("/garlic32/".to_string() + &to_garlic32(&self.addr)?)
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -30,7 +30,7 @@ impl Garlic32 {
 
         let val = {
             let (addr, data) = {
-                let (n, data) = err_at!(DecodeError, uv_decode(data))?;
+                let (n, data) = uv_decode(data)?;
                 let (name, data) = read_slice!(data, (n as usize), "garlic32")?;
                 (name.to_vec(), data)
             };
@@ -61,19 +61,14 @@ const GARLIC32: data_encoding::Encoding = new_encoding! {
 };
 
 fn parse_garlic32(addr: &str) -> Result<Vec<u8>> {
-    use std::iter::{repeat, FromIterator};
-
     // an i2p base32 address with a length of greater than 55
     // characters is using an Encrypted Leaseset v2. all other
     // base32 addresses will always be exactly 52 characters
     if addr.len() < 55 && addr.len() != 52 {
         err_at!(BadAddr, msg: "invalid i2p addr base32")?
     } else {
-        let addr = {
-            let iter = repeat('=').take(8 - (addr.len() % 8));
-            addr.to_string() + &String::from_iter(iter)
-        };
-        Ok(err_at!(BadAddr, GARLIC32.decode(addr.as_bytes()))?)
+        let addr = addr.to_string() + &"=".repeat(8 - (addr.len() % 8));
+        Ok(GARLIC32.decode(addr.as_bytes())?)
     }
 }
 
@@ -22,7 +22,7 @@ impl Garlic64 {
     }
 
     pub(crate) fn to_text(&self) -> Result<String> {
-        Ok("/garlic64".to_string() + &to_garlic64(&self.addr)?)
+        Ok("/garlic64/".to_string() + &to_garlic64(&self.addr)?)
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -30,7 +30,7 @@ impl Garlic64 {
 
         let val = {
             let (addr, data) = {
-                let (n, data) = err_at!(DecodeError, uv_decode(data))?;
+                let (n, data) = uv_decode(data)?;
                 let (name, data) = read_slice!(data, (n as usize), "garlic64")?;
                 (name.to_vec(), data)
             };
@@ -66,7 +66,7 @@ fn parse_garlic64(addr: &str) -> Result<Vec<u8>> {
     if addr.len() < 516 || addr.len() > 616 {
         err_at!(BadAddr, msg: "invalid i2p addr base64 {}", addr)
     } else {
-        Ok(err_at!(BadAddr, GARLIC64.decode(addr.as_bytes()))?)
+        Ok(GARLIC64.decode(addr.as_bytes())?)
     }
 }
 
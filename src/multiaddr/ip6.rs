@@ -30,7 +30,7 @@ impl Ip6 {
     }
 
     pub(crate) fn to_text(&self) -> Result<String> {
-        Ok("/ip6".to_string() + &self.addr.to_string())
+        Ok("/ip6/".to_string() + &self.addr.to_string())
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -57,6 +57,6 @@ impl Ip6 {
     }
 
     pub fn to_addr(&self) -> net::Ipv6Addr {
-        self.addr.clone()
+        self.addr
     }
 }
@@ -24,8 +24,8 @@ impl Ip6zone {
     pub(crate) fn to_text(&self) -> Result<String> {
         use std::str::from_utf8;
 
-        let s = "/ip6zone".to_string();
-        Ok(s + &err_at!(DecodeError, from_utf8(&self.addr))?)
+        let s = "/ip6zone/".to_string();
+        Ok(s + err_at!(DecodeError, from_utf8(&self.addr))?)
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -33,7 +33,7 @@ impl Ip6zone {
 
         let val = {
             let (addr, data) = {
-                let (n, data) = err_at!(DecodeError, uv_decode(data))?;
+                let (n, data) = uv_decode(data)?;
                 let (name, data) = read_slice!(data, (n as usize), "ip6zone")?;
                 (name.to_vec(), data)
             };
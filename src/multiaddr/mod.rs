@@ -1,15 +1,5 @@
 //! Module implement Multiaddr.
 
-macro_rules! read_slice {
-    ($data:expr, $n:expr, $prefix:expr) => {
-        if $data.len() < $n {
-            err_at!(DecodeError, msg: "{} insufficient bytes {}", $prefix, $n)
-        } else {
-            Ok((&$data[..$n], &$data[$n..]))
-        }
-    };
-}
-
 pub(crate) mod dccp;
 pub(crate) mod dns;
 pub(crate) mod dns4;
@@ -22,18 +12,22 @@ pub(crate) mod https;
 pub(crate) mod ip4;
 pub(crate) mod ip6;
 pub(crate) mod ip6zone;
+mod netaddr;
 pub(crate) mod onion;
 pub(crate) mod onion3;
 pub(crate) mod p2p;
 pub(crate) mod p2p_circuit;
 pub(crate) mod p2p_webrtc_direct;
+pub mod pattern;
 pub(crate) mod quic;
+mod resolve;
 pub(crate) mod sctp;
 pub(crate) mod tcp;
 pub(crate) mod udp;
 pub(crate) mod udt;
 pub(crate) mod unix;
 pub(crate) mod utp;
+mod validate;
 pub(crate) mod ws;
 pub(crate) mod wss;
 
@@ -49,6 +43,10 @@ use crate::{
     Error, Result,
 };
 
+pub use netaddr::{Action, Cidr, Filters};
+pub use resolve::{MemResolver, Resolver, SystemResolver, MAX_RESOLVE_DEPTH};
+pub use validate::{Rule, Violation};
+
 macro_rules! impl_multiaddr {
     ($(
         #[$doc:meta]
@@ -82,7 +80,7 @@ macro_rules! impl_multiaddr {
 
                 if parts.len() == 0 {
                     err_at!(BadAddr, msg: "empty multiaddr {}", text)
-                } else if parts[0].is_empty() {
+                } else if !parts[0].is_empty() {
                     err_at!(BadAddr, msg: "multiaddr must start with '/'")
                 } else if parts[1..].len() == 0 {
                     err_at!(BadAddr, msg: "empty multiaddr {}", text)
@@ -169,7 +167,7 @@ macro_rules! impl_multiaddr {
                             (Multiaddr::$var(val, Box::new(ma)), data)
                         }
                     )*
-                    code => err_at!(DecodeError, msg: "invalid code {}", code)?,
+                    code => Err(Error::UnknownCodec(code))?,
                 };
 
                 Ok((ma, data))
@@ -249,11 +247,11 @@ macro_rules! impl_multiaddr {
                 for comp in components.into_iter() {
                     ma = match comp {
                         $(
-                            Multiaddr::$var(val, box Multiaddr::None) => {
+                            Multiaddr::$var(val, tail) if *tail == Multiaddr::None => {
                                 Box::new(Multiaddr::$var(val, ma))
                             }
                         )*
-                        Multiaddr::Ipfs(val, box Multiaddr::None) => {
+                        Multiaddr::Ipfs(val, tail) if *tail == Multiaddr::None => {
                             Box::new(Multiaddr::Ipfs(val, ma))
                         }
                         _ => err_at!(Invalid, msg: "can't joint")?
@@ -272,17 +270,12 @@ impl Multiaddr {
     pub fn is_thin_wait(&self) -> bool {
         use Multiaddr::*;
 
-        let ma = match self {
-            Ip4(_, box tail) => tail.clone(),
-            Ip6(_, box tail) => tail.clone(),
+        let tail = match self {
+            Ip4(_, tail) => tail.as_ref(),
+            Ip6(_, tail) => tail.as_ref(),
             _ => return false,
         };
-        match ma {
-            Tcp(_, _) => true,
-            Udp(_, _) => true,
-            Multiaddr::None => true,
-            _ => false,
-        }
+        matches!(tail, Tcp(_, _) | Udp(_, _) | Multiaddr::None)
     }
 
     pub fn parse(self) -> Result<Self> {
@@ -0,0 +1,356 @@
+//! Module implement address classification and filtering for Multiaddr.
+//!
+//! Classification looks at the leading `ip4` or `ip6` component of the
+//! address, similar to [go-multiaddr/net]. Addresses can be filtered
+//! using CIDR ranges via [Filters].
+//!
+//! [go-multiaddr/net]: https://github.com/multiformats/go-multiaddr/tree/master/net
+
+use lazy_static::lazy_static;
+
+use std::{fmt, net, result, str::FromStr};
+
+use crate::{
+    multiaddr::{ip4::Ip4, ip6::Ip6, Multiaddr},
+    Error, Result,
+};
+
+lazy_static! {
+    // Private ranges, refer RFC-1918, RFC-6598 (shared address space),
+    // RFC-3927 and RFC-4193.
+    static ref PRIVATE: Vec<Cidr> = to_cidrs(&[
+        "10.0.0.0/8",
+        "100.64.0.0/10",
+        "172.16.0.0/12",
+        "192.168.0.0/16",
+        "169.254.0.0/16",
+        "fc00::/7",
+        "fe80::/10",
+    ]);
+
+    // Ranges that are neither private nor routable on the internet,
+    // refer RFC-6890.
+    static ref UNROUTABLE: Vec<Cidr> = to_cidrs(&[
+        "0.0.0.0/8",
+        "127.0.0.0/8",
+        "192.0.0.0/26",
+        "192.0.2.0/24",
+        "192.88.99.0/24",
+        "198.18.0.0/15",
+        "198.51.100.0/24",
+        "203.0.113.0/24",
+        "224.0.0.0/4",
+        "240.0.0.0/4",
+        "::/128",
+        "::1/128",
+        "100::/64",
+        "2001:2::/48",
+        "2001:db8::/32",
+        "ff00::/8",
+    ]);
+
+    // Well-known NAT64 prefix, refer RFC-6052.
+    static ref NAT64: Cidr = "64:ff9b::/96".parse().unwrap();
+}
+
+fn to_cidrs(texts: &[&str]) -> Vec<Cidr> {
+    texts.iter().map(|text| text.parse().unwrap()).collect()
+}
+
+/// Type implement an IP address range in CIDR notation, like
+/// `10.0.0.0/8` or `fe80::/10`.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct Cidr {
+    addr: net::IpAddr,
+    prefix: u8,
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Cidr> {
+        let mut parts = text.splitn(2, '/');
+        let (addr, prefix) = match (parts.next(), parts.next()) {
+            (Some(addr), Some(prefix)) => {
+                let addr: net::IpAddr = err_at!(BadInput, addr.parse(), "cidr {}", text)?;
+                let prefix: u8 = err_at!(BadInput, prefix.parse(), "cidr {}", text)?;
+                (addr, prefix)
+            }
+            (_, _) => err_at!(BadInput, msg: "cidr {}", text)?,
+        };
+        Cidr::new(addr, prefix)
+    }
+}
+
+impl Cidr {
+    /// Create a range of addresses sharing the leading `prefix` bits with
+    /// `addr`. Host bits in `addr` are cleared.
+    pub fn new(addr: net::IpAddr, prefix: u8) -> Result<Cidr> {
+        let addr = match addr {
+            net::IpAddr::V4(ip) if prefix <= 32 => {
+                let bits = u32::from(ip) & mask4(prefix);
+                net::IpAddr::V4(bits.into())
+            }
+            net::IpAddr::V6(ip) if prefix <= 128 => {
+                let bits = u128::from(ip) & mask6(prefix);
+                net::IpAddr::V6(bits.into())
+            }
+            _ => err_at!(BadInput, msg: "cidr prefix {} for {}", prefix, addr)?,
+        };
+
+        Ok(Cidr { addr, prefix })
+    }
+
+    /// Return whether `ip` falls within this range. IPv4 ranges don't
+    /// contain IPv6 addresses and vice-versa.
+    pub fn contains(&self, ip: &net::IpAddr) -> bool {
+        match (self.addr, ip) {
+            (net::IpAddr::V4(addr), net::IpAddr::V4(ip)) => {
+                (u32::from(*ip) & mask4(self.prefix)) == u32::from(addr)
+            }
+            (net::IpAddr::V6(addr), net::IpAddr::V6(ip)) => {
+                (u128::from(*ip) & mask6(self.prefix)) == u128::from(addr)
+            }
+            (_, _) => false,
+        }
+    }
+}
+
+fn mask4(prefix: u8) -> u32 {
+    u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0)
+}
+
+fn mask6(prefix: u8) -> u128 {
+    u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0)
+}
+
+/// Action to take on addresses matching a filter, refer [Filters].
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Action {
+    Accept,
+    Deny,
+}
+
+/// Type implement CIDR based allow/deny filters for multiaddrs.
+///
+/// Rules are matched against the leading IP address of a multiaddr, the
+/// last matching rule decides the action. Addresses not matching any
+/// rule, or without a leading IP address, get the default action.
+#[derive(Clone, Debug)]
+pub struct Filters {
+    default: Action,
+    rules: Vec<(Cidr, Action)>,
+}
+
+impl Default for Filters {
+    fn default() -> Filters {
+        Filters::new(Action::Accept)
+    }
+}
+
+impl Filters {
+    /// Create filters with no rules and `default` action.
+    pub fn new(default: Action) -> Filters {
+        Filters {
+            default,
+            rules: Vec::default(),
+        }
+    }
+
+    /// Accept addresses within `cidr`.
+    pub fn allow(&mut self, cidr: Cidr) -> &mut Self {
+        self.rules.push((cidr, Action::Accept));
+        self
+    }
+
+    /// Deny addresses within `cidr`.
+    pub fn deny(&mut self, cidr: Cidr) -> &mut Self {
+        self.rules.push((cidr, Action::Deny));
+        self
+    }
+
+    /// Return the action for `ma`.
+    pub fn action(&self, ma: &Multiaddr) -> Action {
+        match ma.to_ip_addr() {
+            Some(ip) => {
+                let mut iter = self.rules.iter().rev();
+                match iter.find(|(cidr, _)| cidr.contains(&ip)) {
+                    Some((_, action)) => *action,
+                    None => self.default,
+                }
+            }
+            None => self.default,
+        }
+    }
+
+    /// Return whether `ma` is accepted by the filters.
+    pub fn is_allowed(&self, ma: &Multiaddr) -> bool {
+        self.action(ma) == Action::Accept
+    }
+
+    /// Return the accepted addresses from `addrs`.
+    pub fn filter(&self, addrs: Vec<Multiaddr>) -> Vec<Multiaddr> {
+        addrs.into_iter().filter(|ma| self.is_allowed(ma)).collect()
+    }
+}
+
+impl Multiaddr {
+    /// Return the IP address of the leading `ip4` or `ip6` component,
+    /// skipping an `ip6zone` component if present.
+    pub fn to_ip_addr(&self) -> Option<net::IpAddr> {
+        match self.clone().parse().ok()? {
+            Multiaddr::Ip4(val, _) => Some(net::IpAddr::V4(val.to_addr())),
+            Multiaddr::Ip6(val, _) => Some(net::IpAddr::V6(val.to_addr())),
+            Multiaddr::Ip6zone(_, tail) => match *tail {
+                Multiaddr::Ip6(val, _) => Some(net::IpAddr::V6(val.to_addr())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Return whether the address is a loopback address, `127.0.0.0/8`
+    /// or `::1`, including IPv4 loopback mapped into IPv6.
+    pub fn is_loopback(&self) -> bool {
+        match self.to_ip_addr() {
+            Some(ip) => to_canonical(ip).is_loopback(),
+            None => false,
+        }
+    }
+
+    /// Return whether the address falls within private ranges. This
+    /// includes RFC-1918 ranges, the carrier-grade NAT range, unique local
+    /// IPv6 addresses and link-local addresses.
+    pub fn is_private(&self) -> bool {
+        match self.to_ip_addr() {
+            Some(ip) => in_ranges(&PRIVATE, &to_canonical(ip)),
+            None => false,
+        }
+    }
+
+    /// Return whether the address is routable on the public internet.
+    ///
+    /// IP addresses must be neither private nor reserved, NAT64 addresses
+    /// are public if the embedded IPv4 address is. `dns*` addresses are
+    /// public unless the name is `localhost` or ends with `.localhost` or
+    /// `.local`.
+    pub fn is_public(&self) -> bool {
+        let ma = match self.clone().parse() {
+            Ok(ma) => ma,
+            Err(_) => return false,
+        };
+
+        let name = match &ma {
+            Multiaddr::Dns(val, _) => val.as_str().ok(),
+            Multiaddr::Dns4(val, _) => val.as_str().ok(),
+            Multiaddr::Dns6(val, _) => val.as_str().ok(),
+            Multiaddr::Dnsaddr(val, _) => val.as_str().ok(),
+            _ => None,
+        };
+        if let Some(name) = name {
+            let name = name.trim_end_matches('.').to_lowercase();
+            return !(name == "localhost"
+                || name.ends_with(".localhost")
+                || name.ends_with(".local"));
+        }
+
+        match ma.to_ip_addr().map(to_canonical) {
+            Some(net::IpAddr::V6(ip)) if NAT64.contains(&net::IpAddr::V6(ip)) => {
+                let octets = ip.octets();
+                let ip4 = net::Ipv4Addr::new(octets[12], octets[13], octets[14], octets[15]);
+                is_public_ip(&net::IpAddr::V4(ip4))
+            }
+            Some(ip) => is_public_ip(&ip),
+            None => false,
+        }
+    }
+
+    /// Return whether the address is a link-local address, `169.254.0.0/16`
+    /// or `fe80::/10`.
+    pub fn is_link_local(&self) -> bool {
+        match self.to_ip_addr().map(to_canonical) {
+            Some(net::IpAddr::V4(ip)) => ip.is_link_local(),
+            Some(net::IpAddr::V6(ip)) => (ip.segments()[0] & 0xffc0) == 0xfe80,
+            None => false,
+        }
+    }
+
+    /// Return whether the address is the unspecified address, `0.0.0.0`
+    /// or `::`, typically used to listen on all interfaces.
+    pub fn is_ip_unspecified(&self) -> bool {
+        match self.to_ip_addr() {
+            Some(ip) => ip.is_unspecified(),
+            None => false,
+        }
+    }
+
+    /// Return whether the address is an IPv6 address within the NAT64
+    /// well-known prefix `64:ff9b::/96`.
+    pub fn is_nat64(&self) -> bool {
+        match self.to_ip_addr() {
+            Some(ip) => NAT64.contains(&ip),
+            None => false,
+        }
+    }
+
+    /// Expand an unspecified listen address into one address per
+    /// interface address in `ifaces`, of the same IP family. Remaining
+    /// components, and a leading `ip6zone` component, are kept as is.
+    /// Other addresses are returned as is.
+    pub fn expand_unspecified(&self, ifaces: &[net::IpAddr]) -> Result<Vec<Multiaddr>> {
+        let ma = self.clone().parse()?;
+        if !ma.is_ip_unspecified() {
+            return Ok(vec![ma]);
+        }
+
+        let mut addrs = vec![];
+        for ip in ifaces.iter() {
+            match (&ma, ip) {
+                (Multiaddr::Ip4(_, tail), net::IpAddr::V4(ip)) => {
+                    addrs.push(Multiaddr::Ip4(Ip4::from(*ip), tail.clone()))
+                }
+                (Multiaddr::Ip6(_, tail), net::IpAddr::V6(ip)) => {
+                    addrs.push(Multiaddr::Ip6(Ip6::from(*ip), tail.clone()))
+                }
+                (Multiaddr::Ip6zone(zone, inner), net::IpAddr::V6(ip)) => {
+                    if let Multiaddr::Ip6(_, tail) = inner.as_ref() {
+                        let inner = Multiaddr::Ip6(Ip6::from(*ip), tail.clone());
+                        addrs.push(Multiaddr::Ip6zone(zone.clone(), Box::new(inner)))
+                    }
+                }
+                (_, _) => (),
+            }
+        }
+
+        Ok(addrs)
+    }
+}
+
+fn in_ranges(cidrs: &[Cidr], ip: &net::IpAddr) -> bool {
+    cidrs.iter().any(|cidr| cidr.contains(ip))
+}
+
+fn is_public_ip(ip: &net::IpAddr) -> bool {
+    !in_ranges(&PRIVATE, ip) && !in_ranges(&UNROUTABLE, ip)
+}
+
+// IPv4 addresses mapped into IPv6 are classified as IPv4.
+fn to_canonical(ip: net::IpAddr) -> net::IpAddr {
+    match ip {
+        net::IpAddr::V6(ip6) => match ip6.to_ipv4_mapped() {
+            Some(ip4) => net::IpAddr::V4(ip4),
+            None => ip,
+        },
+        ip => ip,
+    }
+}
+
+#[cfg(test)]
+#[path = "netaddr_test.rs"]
+mod netaddr_test;
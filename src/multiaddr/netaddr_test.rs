@@ -0,0 +1,135 @@
+use super::*;
+
+fn ma(text: &str) -> Multiaddr {
+    Multiaddr::from_text(text).unwrap()
+}
+
+#[test]
+fn test_address_class() {
+    // (address, loopback, private, public, link-local, unspecified)
+    let testcases = vec![
+        ("/ip4/127.0.0.1/tcp/1", true, false, false, false, false),
+        ("/ip4/10.1.2.3/tcp/1", false, true, false, false, false),
+        ("/ip4/172.20.0.1", false, true, false, false, false),
+        ("/ip4/192.168.1.1/udp/1", false, true, false, false, false),
+        ("/ip4/100.64.0.1", false, true, false, false, false),
+        ("/ip4/169.254.1.1", false, true, false, true, false),
+        ("/ip4/8.8.8.8/tcp/53", false, false, true, false, false),
+        ("/ip4/0.0.0.0/tcp/4001", false, false, false, false, true),
+        ("/ip4/192.0.2.1", false, false, false, false, false),
+        ("/ip4/224.0.0.1", false, false, false, false, false),
+        ("/ip6/::1/tcp/1", true, false, false, false, false),
+        ("/ip6/::/tcp/1", false, false, false, false, true),
+        ("/ip6/fe80::1", false, true, false, true, false),
+        ("/ip6zone/eth0/ip6/fe80::1", false, true, false, true, false),
+        ("/ip6/fd00::1", false, true, false, false, false),
+        ("/ip6/2001:db8::1", false, false, false, false, false),
+        ("/ip6/2606:4700::1111", false, false, true, false, false),
+        ("/ip6/::ffff:127.0.0.1", true, false, false, false, false),
+        ("/ip6/::ffff:10.0.0.1", false, true, false, false, false),
+        ("/ip6/64:ff9b::808:808", false, false, true, false, false),
+        ("/ip6/64:ff9b::a00:1", false, false, false, false, false),
+        ("/dns4/example.com/tcp/1", false, false, true, false, false),
+        ("/dns/localhost/tcp/1", false, false, false, false, false),
+        ("/dns4/printer.local", false, false, false, false, false),
+        ("/unix/tmp/a.sock", false, false, false, false, false),
+    ];
+
+    for (text, lo, pr, pu, ll, un) in testcases.into_iter() {
+        let ma = ma(text);
+        assert_eq!(ma.is_loopback(), lo, "loopback {}", text);
+        assert_eq!(ma.is_private(), pr, "private {}", text);
+        assert_eq!(ma.is_public(), pu, "public {}", text);
+        assert_eq!(ma.is_link_local(), ll, "link-local {}", text);
+        assert_eq!(ma.is_ip_unspecified(), un, "unspecified {}", text);
+    }
+
+    assert!(ma("/ip6/64:ff9b::808:808").is_nat64());
+    assert!(!ma("/ip6/2606:4700::1111").is_nat64());
+
+    let data = ma("/ip4/8.8.8.8/tcp/53").encode().unwrap();
+    assert!(Multiaddr::Binary(data).is_public());
+}
+
+#[test]
+fn test_expand_unspecified() {
+    let ifaces: Vec<net::IpAddr> = vec![
+        "127.0.0.1".parse().unwrap(),
+        "::1".parse().unwrap(),
+        "192.168.1.10".parse().unwrap(),
+    ];
+
+    let addrs = ma("/ip4/0.0.0.0/tcp/4001/ws")
+        .expand_unspecified(&ifaces)
+        .unwrap();
+    assert_eq!(
+        addrs,
+        vec![
+            ma("/ip4/127.0.0.1/tcp/4001/ws"),
+            ma("/ip4/192.168.1.10/tcp/4001/ws")
+        ]
+    );
+
+    let addrs = ma("/ip6/::/udp/1/quic")
+        .expand_unspecified(&ifaces)
+        .unwrap();
+    assert_eq!(addrs, vec![ma("/ip6/::1/udp/1/quic")]);
+
+    let addrs = ma("/ip6zone/eth0/ip6/::/tcp/1")
+        .expand_unspecified(&ifaces)
+        .unwrap();
+    assert_eq!(addrs, vec![ma("/ip6zone/eth0/ip6/::1/tcp/1")]);
+    let addrs = ma("/ip6zone/eth0/ip6/::/tcp/1")
+        .expand_unspecified(&ifaces[..1])
+        .unwrap();
+    assert_eq!(addrs, vec![]);
+
+    let addrs = ma("/ip4/1.2.3.4/tcp/1")
+        .expand_unspecified(&ifaces)
+        .unwrap();
+    assert_eq!(addrs, vec![ma("/ip4/1.2.3.4/tcp/1")]);
+}
+
+#[test]
+fn test_cidr() {
+    let cidr: Cidr = "10.1.2.3/8".parse().unwrap();
+    assert_eq!(cidr.to_string(), "10.0.0.0/8");
+    assert!(cidr.contains(&"10.255.0.1".parse().unwrap()));
+    assert!(!cidr.contains(&"11.0.0.1".parse().unwrap()));
+    assert!(!cidr.contains(&"::1".parse().unwrap()));
+
+    let cidr: Cidr = "0.0.0.0/0".parse().unwrap();
+    assert!(cidr.contains(&"1.2.3.4".parse().unwrap()));
+    let cidr: Cidr = "fe80::/10".parse().unwrap();
+    assert!(cidr.contains(&"febf::1".parse().unwrap()));
+    assert!(!cidr.contains(&"fec0::1".parse().unwrap()));
+    let cidr: Cidr = "::1/128".parse().unwrap();
+    assert!(cidr.contains(&"::1".parse().unwrap()));
+
+    assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+    assert!("10.0.0.0".parse::<Cidr>().is_err());
+    assert!("10.0.0/8".parse::<Cidr>().is_err());
+}
+
+#[test]
+fn test_filters() {
+    let mut filters = Filters::default();
+    filters
+        .deny("10.0.0.0/8".parse().unwrap())
+        .allow("10.1.0.0/16".parse().unwrap());
+
+    assert!(filters.is_allowed(&ma("/ip4/8.8.8.8/tcp/1")));
+    assert!(!filters.is_allowed(&ma("/ip4/10.2.0.1/tcp/1")));
+    assert!(filters.is_allowed(&ma("/ip4/10.1.0.1/tcp/1")));
+    assert!(filters.is_allowed(&ma("/dns4/example.com/tcp/1")));
+
+    let mut filters = Filters::new(Action::Deny);
+    filters.allow("fd00::/8".parse().unwrap());
+    let addrs = vec![
+        ma("/ip6/fd00::1/tcp/1"),
+        ma("/ip6/fe80::1/tcp/1"),
+        ma("/ip4/1.2.3.4/tcp/1"),
+    ];
+    assert_eq!(filters.filter(addrs), vec![ma("/ip6/fd00::1/tcp/1")]);
+    assert_eq!(filters.action(&ma("/unix/a.sock")), Action::Deny);
+}
@@ -25,7 +25,7 @@ impl Onion {
     }
 
     pub(crate) fn to_text(&self) -> Result<String> {
-        Ok("/onion".to_string() + &to_onion_text(&self.hash, self.port)?)
+        Ok("/onion/".to_string() + &to_onion_text(&self.hash, self.port)?)
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -54,6 +54,10 @@ impl Onion {
         data.extend_from_slice(&self.port.to_be_bytes());
         Ok(data)
     }
+
+    pub fn to_port(&self) -> u16 {
+        self.port
+    }
 }
 
 fn parse_onion_addr(addr: &str) -> Result<(Vec<u8>, u16)> {
@@ -64,7 +68,7 @@ fn parse_onion_addr(addr: &str) -> Result<(Vec<u8>, u16)> {
         (Some(base_hash), Some(_)) if base_hash.len() != 16 => err_at!(BadAddr, msg: "{}", addr)?,
         (Some(base_hash), Some(port)) => {
             let base_hash = base_hash.to_uppercase();
-            let hash = err_at!(BadAddr, BASE32.decode(base_hash.as_bytes()))?;
+            let hash = BASE32.decode(base_hash.as_bytes())?;
             if hash.len() != 10 {
                 err_at!(BadAddr, msg: "base_hash: {}", base_hash)?
             }
@@ -84,6 +88,6 @@ fn parse_onion_addr(addr: &str) -> Result<(Vec<u8>, u16)> {
 fn to_onion_text(hash: &[u8], port: u16) -> Result<String> {
     use data_encoding::BASE32;
 
-    let s = BASE32.encode(&hash) + ":" + &port.to_string();
+    let s = BASE32.encode(hash) + ":" + &port.to_string();
     Ok(s)
 }
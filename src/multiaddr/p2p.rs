@@ -1,28 +1,48 @@
+use std::str::FromStr;
+
 use crate::{
-    multibase::Multibase,
     multicodec::{self, Multicodec},
     multihash::Multihash,
+    peer_id::PeerId,
     Error, Result,
 };
 
-#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, Debug)]
 pub struct P2p {
-    peer_id: PeerId,
+    peer_id: Id,
+}
+
+// Text and binary form of the same peer-id shall compare equal.
+impl PartialEq for P2p {
+    fn eq(&self, other: &P2p) -> bool {
+        match (self.peer_id.to_bytes(), other.peer_id.to_bytes()) {
+            (Ok(a), Ok(b)) => a == b,
+            (_, _) => self.peer_id == other.peer_id,
+        }
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-enum PeerId {
+enum Id {
     Text(String),
     Binr(Vec<u8>),
 }
 
-impl PeerId {
+impl From<PeerId> for P2p {
+    fn from(peer_id: PeerId) -> Self {
+        P2p {
+            peer_id: Id::Binr(peer_id.to_bytes()),
+        }
+    }
+}
+
+impl Id {
     fn to_text(&self) -> Result<Self> {
         let val = match self {
-            val @ PeerId::Text(_) => val.clone(),
-            PeerId::Binr(buf) => {
+            val @ Id::Text(_) => val.clone(),
+            Id::Binr(buf) => {
                 let (mh, _) = Multihash::decode(buf)?;
-                PeerId::Text(bs58::encode(mh.encode()?).into_string())
+                Id::Text(bs58::encode(mh.encode()?).into_string())
             }
         };
 
@@ -31,8 +51,8 @@ impl PeerId {
 
     fn to_bytes(&self) -> Result<Self> {
         let val = match self {
-            val @ PeerId::Binr(_) => val.clone(),
-            PeerId::Text(txt) => PeerId::Binr(text_to_bytes(txt)?),
+            val @ Id::Binr(_) => val.clone(),
+            Id::Text(txt) => Id::Binr(PeerId::from_str(txt)?.to_bytes()),
         };
 
         Ok(val)
@@ -43,7 +63,7 @@ impl P2p {
     pub(crate) fn from_text<'a, 'b>(parts: &'a [&'b str]) -> Result<(Self, &'a [&'b str])> {
         let val = match parts {
             [peer_id, tail @ ..] => {
-                let peer_id = PeerId::Text(peer_id.to_string());
+                let peer_id = Id::Text(peer_id.to_string());
                 (P2p { peer_id }, tail)
             }
             _ => err_at!(BadAddr, msg: "p2p {:?}", parts)?,
@@ -54,7 +74,7 @@ impl P2p {
 
     pub(crate) fn to_text(&self) -> Result<String> {
         match self.peer_id.to_text()? {
-            PeerId::Text(txt) => Ok("/p2p".to_string() + &txt),
+            Id::Text(txt) => Ok("/p2p/".to_string() + &txt),
             _ => unreachable!(),
         }
     }
@@ -64,11 +84,11 @@ impl P2p {
 
         let val = {
             let (addr, data) = {
-                let (n, data) = err_at!(DecodeError, uv_decode(data))?;
+                let (n, data) = uv_decode(data)?;
                 read_slice!(data, (n as usize), "p2p")?
             };
             let val = P2p {
-                peer_id: PeerId::Binr(addr.to_vec()),
+                peer_id: Id::Binr(addr.to_vec()),
             };
             (val, data)
         };
@@ -82,7 +102,7 @@ impl P2p {
         let mut buf = [0_u8; 19];
 
         let addr = match self.peer_id.to_bytes()? {
-            PeerId::Binr(buf) => buf,
+            Id::Binr(buf) => buf,
             _ => unreachable!(),
         };
 
@@ -94,45 +114,16 @@ impl P2p {
 
     pub fn to_peer_id(&self) -> Result<String> {
         match self.peer_id.to_text()? {
-            PeerId::Text(txt) => Ok(txt),
+            Id::Text(txt) => Ok(txt),
             _ => unreachable!(),
         }
     }
-}
 
-fn text_to_bytes(text: &str) -> Result<Vec<u8>> {
-    let mut chars = text.chars();
-    let mh = match (chars.next(), chars.next()) {
-        (Some('Q'), Some('m')) | (Some('1'), Some(_)) => {
-            // legacy format base58btc.
-            let bytes = err_at!(BadInput, bs58::decode(text.as_bytes()).into_vec())?;
-            let (mh, _) = Multihash::decode(&bytes)?;
-            mh
+    /// Return the peer identity addressed by this component.
+    pub fn peer_id(&self) -> Result<PeerId> {
+        match &self.peer_id {
+            Id::Text(txt) => PeerId::from_str(txt),
+            Id::Binr(buf) => PeerId::from_bytes(buf),
         }
-        _ => {
-            let bytes = {
-                let mb = Multibase::from_text(text)?;
-                match mb.to_bytes() {
-                    Some(bytes) => bytes,
-                    None => err_at!(BadInput, msg: "{}", text)?,
-                }
-            };
-            // <multicodec-cidv1><libp2p-key-codec><multihash>
-            let (codec, bytes) = Multicodec::decode(&bytes)?;
-            match codec.to_code() {
-                multicodec::CID_V1 => (),
-                _ => err_at!(BadInput, msg: "CID {}", codec)?,
-            }
-
-            let (codec, bytes) = Multicodec::decode(bytes)?;
-            match codec.to_code() {
-                multicodec::LIBP2P_KEY => (),
-                _ => err_at!(BadInput, msg: "codec {}", codec)?,
-            }
-            let (mh, _) = Multihash::decode(bytes)?;
-            mh
-        }
-    };
-
-    mh.encode()
+    }
 }
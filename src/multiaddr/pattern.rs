@@ -0,0 +1,215 @@
+//! Module implement pattern matching on Multiaddr protocols, similar to
+//! [go-multiaddr-fmt].
+//!
+//! A [Pattern] describes a sequence of protocols, composed using the
+//! [Pattern::And], [Pattern::Or] and [Pattern::Optional] combinators.
+//! Commonly used patterns are pre-defined in this module:
+//!
+//! ```
+//! use multiformats::multiaddr::{pattern, Multiaddr};
+//!
+//! let ma = Multiaddr::from_text("/ip4/1.2.3.4/tcp/80/ws").unwrap();
+//! assert!(pattern::WEBSOCKETS.matches(&ma));
+//! assert!(!pattern::TCP.matches(&ma));
+//! assert!(pattern::TCP.matches_prefix(&ma));
+//! ```
+//!
+//! [go-multiaddr-fmt]: https://github.com/multiformats/go-multiaddr-fmt
+
+use lazy_static::lazy_static;
+
+use crate::{multiaddr::Multiaddr, multicodec};
+
+/// Type implement a pattern over the protocols of a multiaddr.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Pattern {
+    /// Match a single component of protocol, identified by its multicodec.
+    Proto(u128),
+    /// Match all patterns, one after the other.
+    And(Vec<Pattern>),
+    /// Match any one of the patterns.
+    Or(Vec<Pattern>),
+    /// Match the pattern, or nothing.
+    Optional(Box<Pattern>),
+    /// Match the pattern, and capture the matching components by name.
+    Capture(String, Box<Pattern>),
+}
+
+// A candidate match, position after the match and the captured
+// (name, start, end) spans.
+type Candidate = (usize, Vec<(String, usize, usize)>);
+
+impl Pattern {
+    /// Shorthand for [Pattern::Capture].
+    pub fn capture(name: &str, pattern: Pattern) -> Pattern {
+        Pattern::Capture(name.to_string(), Box::new(pattern))
+    }
+
+    /// Shorthand for [Pattern::Optional].
+    pub fn optional(pattern: Pattern) -> Pattern {
+        Pattern::Optional(Box::new(pattern))
+    }
+
+    /// Return whether the pattern matches the whole of `ma`.
+    pub fn matches(&self, ma: &Multiaddr) -> bool {
+        self.captures(ma).is_some()
+    }
+
+    /// Return whether the pattern matches the leading components of `ma`.
+    pub fn matches_prefix(&self, ma: &Multiaddr) -> bool {
+        match to_codes(ma) {
+            Some(codes) => !self.walk(&codes, 0).is_empty(),
+            None => false,
+        }
+    }
+
+    /// Match the pattern with the whole of `ma`, return the captured
+    /// components, in pattern order, as (name, multiaddr) pairs. Return
+    /// None if pattern does not match.
+    ///
+    /// When there are several ways to match, greedy matches are
+    /// preferred for [Pattern::Optional] and earlier alternatives for
+    /// [Pattern::Or].
+    pub fn captures(&self, ma: &Multiaddr) -> Option<Vec<(String, Multiaddr)>> {
+        let comps = ma.clone().split().ok()?;
+        let codes = to_codes_of(&comps)?;
+
+        let (_, spans) = self
+            .walk(&codes, 0)
+            .into_iter()
+            .find(|(end, _)| *end == codes.len())?;
+
+        let mut captures = vec![];
+        for (name, start, end) in spans.into_iter() {
+            let ma = Multiaddr::join(comps[start..end].to_vec()).ok()?;
+            captures.push((name, ma));
+        }
+        Some(captures)
+    }
+
+    fn walk(&self, codes: &[u128], pos: usize) -> Vec<Candidate> {
+        match self {
+            Pattern::Proto(code) => match codes.get(pos) {
+                Some(c) if c == code => vec![(pos + 1, vec![])],
+                _ => vec![],
+            },
+            Pattern::And(patterns) => {
+                let mut candidates: Vec<Candidate> = vec![(pos, vec![])];
+                for pattern in patterns.iter() {
+                    let mut next = vec![];
+                    for (pos, spans) in candidates.into_iter() {
+                        for (end, more) in pattern.walk(codes, pos).into_iter() {
+                            let mut spans = spans.clone();
+                            spans.extend(more);
+                            next.push((end, spans));
+                        }
+                    }
+                    candidates = next;
+                }
+                candidates
+            }
+            Pattern::Or(patterns) => {
+                let iter = patterns.iter().flat_map(|p| p.walk(codes, pos));
+                iter.collect()
+            }
+            Pattern::Optional(pattern) => {
+                let mut candidates = pattern.walk(codes, pos);
+                candidates.push((pos, vec![]));
+                candidates
+            }
+            Pattern::Capture(name, pattern) => {
+                let mut candidates = pattern.walk(codes, pos);
+                for (end, spans) in candidates.iter_mut() {
+                    spans.insert(0, (name.clone(), pos, *end));
+                }
+                candidates
+            }
+        }
+    }
+}
+
+fn to_codes(ma: &Multiaddr) -> Option<Vec<u128>> {
+    to_codes_of(&ma.clone().split().ok()?)
+}
+
+fn to_codes_of(comps: &[Multiaddr]) -> Option<Vec<u128>> {
+    let iter = comps.iter().map(|c| c.to_multicodec().map(|c| c.to_code()));
+    iter.collect()
+}
+
+fn proto(code: u128) -> Pattern {
+    Pattern::Proto(code)
+}
+
+lazy_static! {
+    /// `/dns4`
+    pub static ref DNS4: Pattern = proto(multicodec::DNS4);
+    /// `/dns6`
+    pub static ref DNS6: Pattern = proto(multicodec::DNS6);
+    /// `/dns`, `/dnsaddr`, `/dns4` or `/dns6`
+    pub static ref DNS: Pattern = Pattern::Or(vec![
+        proto(multicodec::DNS),
+        proto(multicodec::DNSADDR),
+        DNS4.clone(),
+        DNS6.clone(),
+    ]);
+    /// `/ip4` or `/ip6`
+    pub static ref IP: Pattern = Pattern::Or(vec![proto(multicodec::IP4), proto(multicodec::IP6)]);
+    /// TCP over IP or DNS, `/ip4/../tcp/..`
+    pub static ref TCP: Pattern = Pattern::Or(vec![
+        Pattern::And(vec![DNS.clone(), proto(multicodec::TCP)]),
+        Pattern::And(vec![IP.clone(), proto(multicodec::TCP)]),
+    ]);
+    /// UDP over IP or DNS, `/ip4/../udp/..`
+    pub static ref UDP: Pattern = Pattern::Or(vec![
+        Pattern::And(vec![DNS.clone(), proto(multicodec::UDP)]),
+        Pattern::And(vec![IP.clone(), proto(multicodec::UDP)]),
+    ]);
+    /// `/ip4/../udp/../utp`
+    pub static ref UTP: Pattern = Pattern::And(vec![UDP.clone(), proto(multicodec::UTP)]);
+    /// `/ip4/../udp/../quic`
+    pub static ref QUIC: Pattern = Pattern::And(vec![UDP.clone(), proto(multicodec::QUIC)]);
+    /// Unreliable transports, same as [struct@UDP].
+    pub static ref UNRELIABLE: Pattern = Pattern::Or(vec![UDP.clone()]);
+    /// Reliable transports, [struct@TCP], [struct@UTP] or [struct@QUIC].
+    pub static ref RELIABLE: Pattern = Pattern::Or(vec![TCP.clone(), UTP.clone(), QUIC.clone()]);
+    /// `/ip4/../tcp/../ws` or `/ip4/../tcp/../wss`
+    pub static ref WEBSOCKETS: Pattern = Pattern::Or(vec![
+        Pattern::And(vec![TCP.clone(), proto(multicodec::WS)]),
+        Pattern::And(vec![TCP.clone(), proto(multicodec::WSS)]),
+    ]);
+    /// `/ip4/../tcp/../http`
+    pub static ref HTTP: Pattern = Pattern::Or(vec![
+        Pattern::And(vec![TCP.clone(), proto(multicodec::HTTP)]),
+        Pattern::And(vec![IP.clone(), proto(multicodec::HTTP)]),
+    ]);
+    /// `/ip4/../tcp/../https`
+    pub static ref HTTPS: Pattern = Pattern::Or(vec![
+        Pattern::And(vec![TCP.clone(), proto(multicodec::HTTPS)]),
+        Pattern::And(vec![IP.clone(), proto(multicodec::HTTPS)]),
+    ]);
+    /// `/ip4/../tcp/../http/p2p-webrtc-direct`
+    pub static ref WEBRTC: Pattern = Pattern::Or(vec![
+        Pattern::And(vec![HTTP.clone(), proto(multicodec::P2P_WEBRTC_DIRECT)]),
+        Pattern::And(vec![HTTPS.clone(), proto(multicodec::P2P_WEBRTC_DIRECT)]),
+    ]);
+    /// Peer over a reliable transport, `/ip4/../tcp/../p2p/..`
+    pub static ref P2P: Pattern = Pattern::And(vec![
+        Pattern::Or(vec![WEBSOCKETS.clone(), RELIABLE.clone()]),
+        proto(multicodec::P2P),
+    ]);
+    /// Circuit relay address, `[/<relay-addr>]/p2p-circuit[/p2p/<peer>]`,
+    /// where `<relay-addr>` is a [struct@P2P] address or `/p2p/<relay>`.
+    pub static ref CIRCUIT: Pattern = Pattern::And(vec![
+        Pattern::optional(Pattern::And(vec![
+            Pattern::optional(Pattern::Or(vec![WEBSOCKETS.clone(), RELIABLE.clone()])),
+            proto(multicodec::P2P),
+        ])),
+        proto(multicodec::P2P_CIRCUIT),
+        Pattern::optional(proto(multicodec::P2P)),
+    ]);
+}
+
+#[cfg(test)]
+#[path = "pattern_test.rs"]
+mod pattern_test;
@@ -0,0 +1,109 @@
+use super::*;
+
+const PEER: &str = "QmNnooDu7bfjPFoTZYxMNLWUQJyrVwtbZg5gBMjTezGAJN";
+const RELAY: &str = "QmQCU2EcMqAqQPR2i9bChDtGNJchTbq5TbXJJ16u19uLTa";
+
+fn ma(text: &str) -> Multiaddr {
+    Multiaddr::from_text(text).unwrap()
+}
+
+#[test]
+fn test_predefined() {
+    let testcases: Vec<(&Pattern, String, bool)> = vec![
+        (&TCP, "/ip4/1.2.3.4/tcp/80".to_string(), true),
+        (&TCP, "/dns4/example.com/tcp/80".to_string(), true),
+        (&TCP, "/ip4/1.2.3.4/udp/80".to_string(), false),
+        (&TCP, "/ip4/1.2.3.4/tcp/80/ws".to_string(), false),
+        (&UDP, "/ip6/::1/udp/80".to_string(), true),
+        (&QUIC, "/ip6/::1/udp/80/quic".to_string(), true),
+        (&QUIC, "/ip6/::1/udp/80".to_string(), false),
+        (&RELIABLE, "/ip4/1.2.3.4/tcp/80".to_string(), true),
+        (&RELIABLE, "/ip4/1.2.3.4/udp/80/quic".to_string(), true),
+        (&RELIABLE, "/ip4/1.2.3.4/udp/80".to_string(), false),
+        (&UNRELIABLE, "/ip4/1.2.3.4/udp/80".to_string(), true),
+        (
+            &WEBSOCKETS,
+            "/dns/example.com/tcp/443/wss".to_string(),
+            true,
+        ),
+        (&WEBSOCKETS, "/ip4/1.2.3.4/tcp/80/ws".to_string(), true),
+        (&WEBSOCKETS, "/ip4/1.2.3.4/udp/80/ws".to_string(), false),
+        (
+            &WEBRTC,
+            "/ip4/1.2.3.4/tcp/80/http/p2p-webrtc-direct".to_string(),
+            true,
+        ),
+        (&DNS, "/dnsaddr/bootstrap.libp2p.io".to_string(), true),
+        (&DNS, "/ip4/1.2.3.4".to_string(), false),
+        (&P2P, format!("/ip4/1.2.3.4/tcp/80/p2p/{}", PEER), true),
+        (&P2P, format!("/p2p/{}", PEER), false),
+        (
+            &CIRCUIT,
+            format!("/ip4/1.2.3.4/tcp/80/p2p/{}/p2p-circuit/p2p/{}", RELAY, PEER),
+            true,
+        ),
+        (&CIRCUIT, format!("/p2p/{}/p2p-circuit", RELAY), true),
+        (&CIRCUIT, format!("/p2p-circuit/p2p/{}", PEER), true),
+        (&CIRCUIT, format!("/ip4/1.2.3.4/tcp/80/p2p/{}", PEER), false),
+    ];
+
+    for (pattern, text, res) in testcases.into_iter() {
+        assert_eq!(pattern.matches(&ma(&text)), res, "{}", text);
+    }
+
+    assert!(TCP.matches_prefix(&ma("/ip4/1.2.3.4/tcp/80/ws")));
+    assert!(!UDP.matches_prefix(&ma("/ip4/1.2.3.4/tcp/80/ws")));
+
+    // binary form matches just as well.
+    let data = ma("/ip4/1.2.3.4/udp/80/quic").encode().unwrap();
+    assert!(QUIC.matches(&Multiaddr::Binary(data)));
+}
+
+#[test]
+fn test_combinators() {
+    let pattern = Pattern::And(vec![
+        Pattern::Proto(multicodec::IP4),
+        Pattern::optional(Pattern::Proto(multicodec::TCP)),
+        Pattern::Or(vec![
+            Pattern::Proto(multicodec::WS),
+            Pattern::Proto(multicodec::TCP),
+        ]),
+    ]);
+
+    assert!(pattern.matches(&ma("/ip4/1.2.3.4/ws")));
+    assert!(pattern.matches(&ma("/ip4/1.2.3.4/tcp/1/ws")));
+    // optional must give way for the Or() to match.
+    assert!(pattern.matches(&ma("/ip4/1.2.3.4/tcp/1")));
+    assert!(pattern.matches(&ma("/ip4/1.2.3.4/tcp/1/tcp/2")));
+    assert!(!pattern.matches(&ma("/ip4/1.2.3.4")));
+    assert!(!pattern.matches(&ma("/ip4/1.2.3.4/tcp/1/tcp/2/ws")));
+}
+
+#[test]
+fn test_captures() {
+    let pattern = Pattern::And(vec![
+        Pattern::capture(
+            "transport",
+            Pattern::Or(vec![WEBSOCKETS.clone(), TCP.clone()]),
+        ),
+        Pattern::optional(Pattern::capture("peer", Pattern::Proto(multicodec::P2P))),
+    ]);
+
+    let caps = pattern.captures(&ma("/ip4/1.2.3.4/tcp/80/ws")).unwrap();
+    assert_eq!(
+        caps,
+        vec![("transport".to_string(), ma("/ip4/1.2.3.4/tcp/80/ws"))]
+    );
+
+    let text = format!("/dns4/example.com/tcp/80/p2p/{}", PEER);
+    let caps = pattern.captures(&ma(&text)).unwrap();
+    assert_eq!(
+        caps,
+        vec![
+            ("transport".to_string(), ma("/dns4/example.com/tcp/80")),
+            ("peer".to_string(), ma(&format!("/p2p/{}", PEER))),
+        ]
+    );
+
+    assert_eq!(pattern.captures(&ma("/ip4/1.2.3.4/udp/80")), None);
+}
@@ -0,0 +1,507 @@
+//! Module implement name resolution for Multiaddr.
+//!
+//! `dns`, `dns4` and `dns6` components are resolved into concrete `ip4`
+//! and `ip6` components. `dnsaddr` components are resolved by following
+//! the `dnsaddr=<multiaddr>` TXT records published under
+//! `_dnsaddr.<name>`. Refer [dnsaddr] spec for details.
+//!
+//! [dnsaddr]: https://github.com/multiformats/multiaddr/blob/master/protocols/DNSADDR.md
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fs,
+    io::{Read, Write},
+    net, time,
+};
+
+use crate::{
+    multiaddr::{ip4::Ip4, ip6::Ip6, Multiaddr},
+    Error, Result,
+};
+
+/// Maximum levels of `dnsaddr` indirection followed by [Multiaddr::resolve].
+pub const MAX_RESOLVE_DEPTH: usize = 8;
+
+const DNS_PORT: u16 = 53;
+const DNS_TYPE_A: u16 = 1;
+const DNS_TYPE_TXT: u16 = 16;
+const DNS_TYPE_AAAA: u16 = 28;
+const DNS_CLASS_IN: u16 = 1;
+const DNS_TYPE_OPT: u16 = 41;
+// UDP payload size advertised using EDNS0, refer RFC-6891.
+const EDNS_UDP_SIZE: u16 = 4096;
+
+/// Trait abstracts the name lookups required to resolve a multiaddr.
+///
+/// Lookups that find no records shall return an empty list, errors are
+/// reserved for failures in the resolution itself.
+pub trait Resolver {
+    /// Return the IPv4 addresses (A records) for `name`.
+    fn lookup_ip4(&self, name: &str) -> Result<Vec<net::Ipv4Addr>>;
+
+    /// Return the IPv6 addresses (AAAA records) for `name`.
+    fn lookup_ip6(&self, name: &str) -> Result<Vec<net::Ipv6Addr>>;
+
+    /// Return the TXT records for `name`, character-strings within a
+    /// single record are concatenated.
+    fn lookup_txt(&self, name: &str) -> Result<Vec<String>>;
+}
+
+/// Resolver using the host's name service.
+///
+/// A, AAAA and TXT lookups are sent over UDP to the configured
+/// nameservers, which default to the ones listed in `/etc/resolv.conf`.
+/// Queries advertise a 4096 byte UDP payload using EDNS0, answers still
+/// truncated are fetched again over TCP. Non-existent names (NXDOMAIN)
+/// have no records.
+///
+/// `/etc/hosts` is not consulted, `localhost` and names under it resolve
+/// to the loopback address without a query, refer RFC-6761.
+#[derive(Clone, Debug)]
+pub struct SystemResolver {
+    nameservers: Vec<net::SocketAddr>,
+    timeout: time::Duration,
+}
+
+impl SystemResolver {
+    /// Create a resolver using nameservers from `/etc/resolv.conf`.
+    pub fn new() -> Result<SystemResolver> {
+        let text = err_at!(IOError, fs::read_to_string("/etc/resolv.conf"))?;
+        let nameservers = parse_resolv_conf(&text);
+        if nameservers.is_empty() {
+            err_at!(Invalid, msg: "no nameserver in /etc/resolv.conf")?
+        }
+
+        Ok(Self::with_nameservers(nameservers))
+    }
+
+    /// Create a resolver sending queries to `nameservers`, tried in
+    /// order.
+    pub fn with_nameservers(nameservers: Vec<net::SocketAddr>) -> SystemResolver {
+        SystemResolver {
+            nameservers,
+            timeout: time::Duration::from_secs(5),
+        }
+    }
+
+    /// Set the per-nameserver timeout for queries, default is 5 seconds.
+    /// Applies separately to the UDP query and its TCP retry.
+    pub fn set_timeout(&mut self, timeout: time::Duration) -> &mut Self {
+        self.timeout = timeout;
+        self
+    }
+
+    // Return the rdata of `qtype` records for `name`, from the first
+    // nameserver that answers.
+    fn query(&self, name: &str, qtype: u16) -> Result<Vec<Vec<u8>>> {
+        let mut last_err = None;
+        for ns in self.nameservers.iter() {
+            match self.query_ns(ns, name, qtype) {
+                Ok(rdatas) => return Ok(rdatas),
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        match last_err {
+            Some(err) => Err(err),
+            None => err_at!(Invalid, msg: "no nameserver to lookup {}", name),
+        }
+    }
+
+    fn query_ns(&self, ns: &net::SocketAddr, name: &str, qtype: u16) -> Result<Vec<Vec<u8>>> {
+        let mut id = [0_u8; 2];
+        err_at!(IOError, getrandom::getrandom(&mut id))?;
+        let id = u16::from_be_bytes(id);
+        let query = dns_query(id, name, qtype)?;
+
+        let resp = self.query_udp(ns, id, &query)?;
+        match is_truncated(&resp) {
+            // answer doesn't fit in UDP payload, retry over TCP.
+            true => parse_response(id, qtype, &self.query_tcp(ns, &query)?),
+            false => parse_response(id, qtype, &resp),
+        }
+    }
+
+    fn query_udp(&self, ns: &net::SocketAddr, id: u16, query: &[u8]) -> Result<Vec<u8>> {
+        let local: net::SocketAddr = match ns {
+            net::SocketAddr::V4(_) => (net::Ipv4Addr::UNSPECIFIED, 0).into(),
+            net::SocketAddr::V6(_) => (net::Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+        let sock = err_at!(IOError, net::UdpSocket::bind(local))?;
+        err_at!(IOError, sock.connect(ns))?;
+        err_at!(IOError, sock.send(query))?;
+
+        // skip datagrams from other sources or for other queries, they are
+        // stale or spoofed answers.
+        let deadline = time::Instant::now() + self.timeout;
+        let mut buf = vec![0; EDNS_UDP_SIZE as usize];
+        loop {
+            let timeout = match deadline.checked_duration_since(time::Instant::now()) {
+                Some(timeout) if !timeout.is_zero() => timeout,
+                _ => err_at!(IOError, msg: "nameserver {} timed out", ns)?,
+            };
+            err_at!(IOError, sock.set_read_timeout(Some(timeout)))?;
+            let (n, from) = err_at!(IOError, sock.recv_from(&mut buf), "nameserver {}", ns)?;
+            if from == *ns && buf[..n].starts_with(&id.to_be_bytes()) {
+                buf.truncate(n);
+                break Ok(buf);
+            }
+        }
+    }
+
+    // DNS over TCP, messages are prefixed with their 2 byte length, refer
+    // RFC-7766.
+    fn query_tcp(&self, ns: &net::SocketAddr, query: &[u8]) -> Result<Vec<u8>> {
+        let conn = net::TcpStream::connect_timeout(ns, self.timeout);
+        let mut conn = err_at!(IOError, conn, "nameserver {}", ns)?;
+        err_at!(IOError, conn.set_read_timeout(Some(self.timeout)))?;
+        err_at!(IOError, conn.set_write_timeout(Some(self.timeout)))?;
+
+        let mut msg = (query.len() as u16).to_be_bytes().to_vec();
+        msg.extend_from_slice(query);
+        err_at!(IOError, conn.write_all(&msg), "nameserver {}", ns)?;
+
+        let mut len = [0_u8; 2];
+        err_at!(IOError, conn.read_exact(&mut len), "nameserver {}", ns)?;
+        let mut buf = vec![0; u16::from_be_bytes(len) as usize];
+        err_at!(IOError, conn.read_exact(&mut buf), "nameserver {}", ns)?;
+        Ok(buf)
+    }
+}
+
+impl Resolver for SystemResolver {
+    fn lookup_ip4(&self, name: &str) -> Result<Vec<net::Ipv4Addr>> {
+        if is_localhost(name) {
+            return Ok(vec![net::Ipv4Addr::LOCALHOST]);
+        }
+
+        let mut addrs = vec![];
+        for rdata in self.query(name, DNS_TYPE_A)?.into_iter() {
+            match <[u8; 4]>::try_from(rdata.as_slice()) {
+                Ok(octets) => addrs.push(octets.into()),
+                Err(_) => err_at!(DecodeError, msg: "dns A record length {}", rdata.len())?,
+            }
+        }
+        Ok(addrs)
+    }
+
+    fn lookup_ip6(&self, name: &str) -> Result<Vec<net::Ipv6Addr>> {
+        if is_localhost(name) {
+            return Ok(vec![net::Ipv6Addr::LOCALHOST]);
+        }
+
+        let mut addrs = vec![];
+        for rdata in self.query(name, DNS_TYPE_AAAA)?.into_iter() {
+            match <[u8; 16]>::try_from(rdata.as_slice()) {
+                Ok(octets) => addrs.push(octets.into()),
+                Err(_) => err_at!(DecodeError, msg: "dns AAAA record length {}", rdata.len())?,
+            }
+        }
+        Ok(addrs)
+    }
+
+    fn lookup_txt(&self, name: &str) -> Result<Vec<String>> {
+        let mut records = vec![];
+        for rdata in self.query(name, DNS_TYPE_TXT)?.into_iter() {
+            records.push(parse_txt_rdata(&rdata)?);
+        }
+        Ok(records)
+    }
+}
+
+/// In-memory resolver, answers lookups from records added to it.
+///
+/// Useful for testing and for pinning names to known addresses.
+#[derive(Clone, Default, Debug)]
+pub struct MemResolver {
+    ip4: HashMap<String, Vec<net::Ipv4Addr>>,
+    ip6: HashMap<String, Vec<net::Ipv6Addr>>,
+    txt: HashMap<String, Vec<String>>,
+}
+
+impl MemResolver {
+    /// Create an empty resolver.
+    pub fn new() -> MemResolver {
+        MemResolver::default()
+    }
+
+    /// Add an A record for `name`.
+    pub fn add_ip4(&mut self, name: &str, addr: net::Ipv4Addr) -> &mut Self {
+        self.ip4.entry(to_key(name)).or_default().push(addr);
+        self
+    }
+
+    /// Add an AAAA record for `name`.
+    pub fn add_ip6(&mut self, name: &str, addr: net::Ipv6Addr) -> &mut Self {
+        self.ip6.entry(to_key(name)).or_default().push(addr);
+        self
+    }
+
+    /// Add a TXT record for `name`.
+    pub fn add_txt(&mut self, name: &str, record: &str) -> &mut Self {
+        let record = record.to_string();
+        self.txt.entry(to_key(name)).or_default().push(record);
+        self
+    }
+}
+
+impl Resolver for MemResolver {
+    fn lookup_ip4(&self, name: &str) -> Result<Vec<net::Ipv4Addr>> {
+        Ok(self.ip4.get(&to_key(name)).cloned().unwrap_or_default())
+    }
+
+    fn lookup_ip6(&self, name: &str) -> Result<Vec<net::Ipv6Addr>> {
+        Ok(self.ip6.get(&to_key(name)).cloned().unwrap_or_default())
+    }
+
+    fn lookup_txt(&self, name: &str) -> Result<Vec<String>> {
+        Ok(self.txt.get(&to_key(name)).cloned().unwrap_or_default())
+    }
+}
+
+impl Multiaddr {
+    /// Resolve the leading `dns`, `dns4`, `dns6` or `dnsaddr` component
+    /// of this multiaddr into concrete addresses, using `resolver`.
+    ///
+    /// * `/dns4/<name>/..` and `/dns6/<name>/..` are expanded into one
+    ///   address per A or AAAA record, `/dns/<name>/..` into both.
+    /// * `/dnsaddr/<name>/..` is expanded into the addresses found in the
+    ///   `dnsaddr=` TXT records of `_dnsaddr.<name>`, recursively. When
+    ///   components follow the `dnsaddr` component, like a trailing
+    ///   `/p2p/<id>`, only records ending with the same components are
+    ///   kept.
+    ///
+    /// Addresses that don't start with a name component are returned as
+    /// is. Recursion beyond [MAX_RESOLVE_DEPTH] is an error.
+    pub fn resolve<R>(&self, resolver: &R) -> Result<Vec<Multiaddr>>
+    where
+        R: Resolver + ?Sized,
+    {
+        do_resolve(self.clone().parse()?, resolver, 0)
+    }
+
+    /// Return whether this multiaddr starts with a component that can be
+    /// resolved using [Self::resolve].
+    pub fn is_resolvable(&self) -> bool {
+        matches!(
+            self,
+            Multiaddr::Dns(_, _)
+                | Multiaddr::Dns4(_, _)
+                | Multiaddr::Dns6(_, _)
+                | Multiaddr::Dnsaddr(_, _)
+        )
+    }
+}
+
+fn do_resolve<R>(maddr: Multiaddr, resolver: &R, depth: usize) -> Result<Vec<Multiaddr>>
+where
+    R: Resolver + ?Sized,
+{
+    if depth > MAX_RESOLVE_DEPTH {
+        err_at!(BadAddr, msg: "resolve depth exceeds {}", MAX_RESOLVE_DEPTH)?
+    }
+
+    let addrs = match maddr {
+        Multiaddr::Dns(val, tail) => {
+            let name = val.as_str()?;
+            let mut addrs = to_ip4_addrs(resolver.lookup_ip4(name)?, &tail);
+            addrs.extend(to_ip6_addrs(resolver.lookup_ip6(name)?, &tail));
+            addrs
+        }
+        Multiaddr::Dns4(val, tail) => to_ip4_addrs(resolver.lookup_ip4(val.as_str()?)?, &tail),
+        Multiaddr::Dns6(val, tail) => to_ip6_addrs(resolver.lookup_ip6(val.as_str()?)?, &tail),
+        Multiaddr::Dnsaddr(val, tail) => {
+            let name = "_dnsaddr.".to_string() + val.as_str()?;
+            let suffix = to_encoded_components(*tail)?;
+
+            let mut addrs = vec![];
+            for record in resolver.lookup_txt(&name)?.into_iter() {
+                // records not meant for multiaddr, or malformed, are skipped.
+                let maddr = match record.strip_prefix("dnsaddr=") {
+                    Some(text) => match Multiaddr::from_text(text) {
+                        Ok(maddr) => maddr,
+                        Err(_) => continue,
+                    },
+                    None => continue,
+                };
+                if to_encoded_components(maddr.clone())?.ends_with(&suffix) {
+                    addrs.extend(do_resolve(maddr, resolver, depth + 1)?);
+                }
+            }
+            addrs
+        }
+        maddr => vec![maddr],
+    };
+
+    Ok(addrs)
+}
+
+fn to_ip4_addrs(ips: Vec<net::Ipv4Addr>, tail: &Multiaddr) -> Vec<Multiaddr> {
+    let iter = ips.into_iter().map(|ip| {
+        let tail = Box::new(tail.clone());
+        Multiaddr::Ip4(Ip4::from(ip), tail)
+    });
+    iter.collect()
+}
+
+fn to_ip6_addrs(ips: Vec<net::Ipv6Addr>, tail: &Multiaddr) -> Vec<Multiaddr> {
+    let iter = ips.into_iter().map(|ip| {
+        let tail = Box::new(tail.clone());
+        Multiaddr::Ip6(Ip6::from(ip), tail)
+    });
+    iter.collect()
+}
+
+// Components are compared in their binary form, so that text and binary
+// representation of the same component, like a peer-id, match.
+fn to_encoded_components(maddr: Multiaddr) -> Result<Vec<Vec<u8>>> {
+    let mut comps = vec![];
+    for comp in maddr.split()?.into_iter() {
+        comps.push(comp.encode()?);
+    }
+    Ok(comps)
+}
+
+fn to_key(name: &str) -> String {
+    name.trim_end_matches('.').to_lowercase()
+}
+
+fn is_localhost(name: &str) -> bool {
+    let name = to_key(name);
+    name == "localhost" || name.ends_with(".localhost")
+}
+
+fn parse_resolv_conf(text: &str) -> Vec<net::SocketAddr> {
+    let mut nameservers = vec![];
+    for line in text.lines() {
+        let mut words = line.split_whitespace();
+        if let (Some("nameserver"), Some(addr)) = (words.next(), words.next()) {
+            if let Some(addr) = parse_nameserver(addr) {
+                nameservers.push(addr)
+            }
+        }
+    }
+    nameservers
+}
+
+// Link-local nameservers are unroutable without their zone, numeric zone
+// is the scope-id, entries zoned by interface name are skipped.
+fn parse_nameserver(addr: &str) -> Option<net::SocketAddr> {
+    match addr.split_once('%') {
+        Some((ip, zone)) => {
+            let ip = ip.parse::<net::Ipv6Addr>().ok()?;
+            let scope_id = zone.parse::<u32>().ok()?;
+            Some(net::SocketAddrV6::new(ip, DNS_PORT, 0, scope_id).into())
+        }
+        None => Some((addr.parse::<net::IpAddr>().ok()?, DNS_PORT).into()),
+    }
+}
+
+// Compose a recursive DNS query for `qtype` records of `name`, refer
+// RFC-1035, with an EDNS0 OPT record, refer RFC-6891.
+fn dns_query(id: u16, name: &str, qtype: u16) -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    buf.extend_from_slice(&id.to_be_bytes());
+    buf.extend_from_slice(&0x0100_u16.to_be_bytes()); // recursion desired
+    buf.extend_from_slice(&1_u16.to_be_bytes()); // qdcount
+    buf.extend_from_slice(&[0, 0, 0, 0, 0, 1]); // ancount, nscount, arcount
+
+    for label in name.trim_end_matches('.').split('.') {
+        match label.len() {
+            n if n == 0 || n > 63 => err_at!(BadInput, msg: "dns name {:?}", name)?,
+            n => {
+                buf.push(n as u8);
+                buf.extend_from_slice(label.as_bytes());
+            }
+        }
+    }
+    buf.push(0);
+    buf.extend_from_slice(&qtype.to_be_bytes());
+    buf.extend_from_slice(&DNS_CLASS_IN.to_be_bytes());
+
+    // OPT pseudo-RR, root name, class is the UDP payload size, zero
+    // extended-rcode, version and flags, no options.
+    buf.push(0);
+    buf.extend_from_slice(&DNS_TYPE_OPT.to_be_bytes());
+    buf.extend_from_slice(&EDNS_UDP_SIZE.to_be_bytes());
+    buf.extend_from_slice(&[0, 0, 0, 0, 0, 0]); // ttl, rdlength
+
+    Ok(buf)
+}
+
+// Whether the response has its TC bit set.
+fn is_truncated(buf: &[u8]) -> bool {
+    matches!(buf.get(2), Some(b) if (b & 0x02) != 0)
+}
+
+// Parse the response for a query composed using dns_query(), return the
+// rdata of answers of `qtype`, other answers like CNAME are skipped.
+fn parse_response(id: u16, qtype: u16, buf: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let (header, mut data) = read_slice!(buf, 12, "dns-header")?;
+
+    let u16_at = |bs: &[u8], off: usize| u16::from_be_bytes([bs[off], bs[off + 1]]);
+    if u16_at(header, 0) != id {
+        err_at!(DecodeError, msg: "dns response id mismatch")?
+    }
+    let flags = u16_at(header, 2);
+    if (flags & 0x8000) == 0 {
+        err_at!(DecodeError, msg: "dns response is not an answer")?
+    }
+    if (flags & 0x0200) != 0 {
+        err_at!(DecodeError, msg: "dns response truncated")?
+    }
+    match flags & 0x000f {
+        0 => (),
+        3 => return Ok(vec![]), // NXDOMAIN
+        rcode => err_at!(DecodeError, msg: "dns response code {}", rcode)?,
+    }
+
+    let (qdcount, ancount) = (u16_at(header, 4), u16_at(header, 6));
+    for _ in 0..qdcount {
+        data = skip_name(data)?;
+        data = read_slice!(data, 4, "dns-question")?.1;
+    }
+
+    let mut rdatas = vec![];
+    for _ in 0..ancount {
+        data = skip_name(data)?;
+        let (rr, rest) = read_slice!(data, 10, "dns-rr")?;
+        let (typ, class) = (u16_at(rr, 0), u16_at(rr, 2));
+        let (rdata, rest) = read_slice!(rest, u16_at(rr, 8) as usize, "dns-rdata")?;
+        data = rest;
+
+        if typ == qtype && class == DNS_CLASS_IN {
+            rdatas.push(rdata.to_vec());
+        }
+    }
+
+    Ok(rdatas)
+}
+
+// Concatenate the character-strings of a TXT record.
+fn parse_txt_rdata(mut rdata: &[u8]) -> Result<String> {
+    let mut record = vec![];
+    while let Some((n, rest)) = rdata.split_first() {
+        let (s, rest) = read_slice!(rest, *n as usize, "dns-txt")?;
+        record.extend_from_slice(s);
+        rdata = rest;
+    }
+    err_at!(DecodeError, String::from_utf8(record))
+}
+
+fn skip_name(mut data: &[u8]) -> Result<&[u8]> {
+    loop {
+        match data.split_first() {
+            Some((0, rest)) => break Ok(rest),
+            // compression pointer, terminates the name.
+            Some((n, _)) if (n & 0xc0) == 0xc0 => break Ok(read_slice!(data, 2, "dns-name")?.1),
+            Some((n, rest)) => data = read_slice!(rest, *n as usize, "dns-label")?.1,
+            None => err_at!(DecodeError, msg: "dns name truncated")?,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "resolve_test.rs"]
+mod resolve_test;
@@ -0,0 +1,367 @@
+use super::*;
+
+const PEER1: &str = "QmNnooDu7bfjPFoTZYxMNLWUQJyrVwtbZg5gBMjTezGAJN";
+const PEER2: &str = "QmQCU2EcMqAqQPR2i9bChDtGNJchTbq5TbXJJ16u19uLTa";
+
+fn parse_txt_response(id: u16, buf: &[u8]) -> Result<Vec<String>> {
+    let rdatas = parse_response(id, DNS_TYPE_TXT, buf)?;
+    rdatas.iter().map(|rdata| parse_txt_rdata(rdata)).collect()
+}
+
+fn to_texts(addrs: Vec<Multiaddr>) -> Vec<String> {
+    addrs.into_iter().map(|ma| ma.to_text().unwrap()).collect()
+}
+
+#[test]
+fn test_resolve_dns() {
+    let mut resolver = MemResolver::new();
+    resolver
+        .add_ip4("example.com", "1.2.3.4".parse().unwrap())
+        .add_ip4("example.com", "5.6.7.8".parse().unwrap())
+        .add_ip6("Example.com.", "::1".parse().unwrap());
+
+    let ma = Multiaddr::from_text("/dns4/example.com/tcp/1").unwrap();
+    assert!(ma.is_resolvable());
+    assert_eq!(
+        to_texts(ma.resolve(&resolver).unwrap()),
+        vec!["/ip4/1.2.3.4/tcp/1", "/ip4/5.6.7.8/tcp/1"]
+    );
+
+    let ma = Multiaddr::from_text("/dns6/example.com/udp/2").unwrap();
+    assert_eq!(
+        to_texts(ma.resolve(&resolver).unwrap()),
+        vec!["/ip6/::1/udp/2"]
+    );
+
+    let ma = Multiaddr::from_text("/dns/example.com/tcp/3/ws").unwrap();
+    assert_eq!(
+        to_texts(ma.resolve(&resolver).unwrap()),
+        vec![
+            "/ip4/1.2.3.4/tcp/3/ws",
+            "/ip4/5.6.7.8/tcp/3/ws",
+            "/ip6/::1/tcp/3/ws"
+        ]
+    );
+
+    let ma = Multiaddr::from_text("/dns4/unknown.com/tcp/1").unwrap();
+    assert_eq!(ma.resolve(&resolver).unwrap(), vec![]);
+
+    let ma = Multiaddr::from_text("/ip4/9.9.9.9/tcp/1").unwrap();
+    assert!(!ma.is_resolvable());
+    assert_eq!(ma.resolve(&resolver).unwrap(), vec![ma]);
+}
+
+#[test]
+fn test_resolve_dnsaddr() {
+    let mut resolver = MemResolver::new();
+    resolver
+        .add_txt(
+            "_dnsaddr.bootstrap.io",
+            &format!("dnsaddr=/dnsaddr/sjc.bootstrap.io/p2p/{}", PEER1),
+        )
+        .add_txt(
+            "_dnsaddr.bootstrap.io",
+            &format!("dnsaddr=/ip4/10.0.0.2/tcp/4001/p2p/{}", PEER2),
+        )
+        .add_txt("_dnsaddr.bootstrap.io", "v=spf1 -all")
+        .add_txt("_dnsaddr.bootstrap.io", "dnsaddr=/not/a/multiaddr")
+        .add_txt(
+            "_dnsaddr.sjc.bootstrap.io",
+            &format!("dnsaddr=/dns4/node.sjc.io/tcp/4001/p2p/{}", PEER1),
+        )
+        .add_txt(
+            "_dnsaddr.sjc.bootstrap.io",
+            &format!("dnsaddr=/ip6/::2/udp/4001/quic/p2p/{}", PEER1),
+        )
+        .add_ip4("node.sjc.io", "10.0.0.1".parse().unwrap());
+
+    let ma = Multiaddr::from_text("/dnsaddr/bootstrap.io").unwrap();
+    assert_eq!(
+        to_texts(ma.resolve(&resolver).unwrap()),
+        vec![
+            format!("/ip4/10.0.0.1/tcp/4001/p2p/{}", PEER1),
+            format!("/ip6/::2/udp/4001/quic/p2p/{}", PEER1),
+            format!("/ip4/10.0.0.2/tcp/4001/p2p/{}", PEER2),
+        ]
+    );
+
+    let ma = Multiaddr::from_text(&format!("/dnsaddr/bootstrap.io/p2p/{}", PEER2)).unwrap();
+    assert_eq!(
+        to_texts(ma.resolve(&resolver).unwrap()),
+        vec![format!("/ip4/10.0.0.2/tcp/4001/p2p/{}", PEER2)]
+    );
+
+    // binary form of the peer-id shall match the text form in records.
+    let data = Multiaddr::from_text(&format!("/dnsaddr/bootstrap.io/p2p/{}", PEER1))
+        .unwrap()
+        .encode()
+        .unwrap();
+    assert_eq!(
+        to_texts(Multiaddr::Binary(data).resolve(&resolver).unwrap()),
+        vec![
+            format!("/ip4/10.0.0.1/tcp/4001/p2p/{}", PEER1),
+            format!("/ip6/::2/udp/4001/quic/p2p/{}", PEER1),
+        ]
+    );
+}
+
+#[test]
+fn test_resolve_depth() {
+    let mut resolver = MemResolver::new();
+    resolver.add_txt("_dnsaddr.loop.io", "dnsaddr=/dnsaddr/loop.io");
+
+    let ma = Multiaddr::from_text("/dnsaddr/loop.io").unwrap();
+    assert!(ma.resolve(&resolver).is_err());
+}
+
+#[test]
+fn test_txt_wire_format() {
+    let query = dns_query(0x1234, "_dnsaddr.a.io.", DNS_TYPE_TXT).unwrap();
+    let question = b"\x08_dnsaddr\x01a\x02io\x00\x00\x10\x00\x01";
+    let mut refq = vec![0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 1];
+    refq.extend_from_slice(question);
+    // OPT record advertising 4096 byte UDP payload.
+    refq.extend_from_slice(&[0, 0, 41, 0x10, 0x00, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(query, refq);
+
+    assert!(dns_query(0x1234, "a..io", DNS_TYPE_TXT).is_err());
+
+    // response with the question echoed, a TXT answer with two
+    // character-strings and a CNAME answer that must be skipped.
+    let mut resp = vec![0x12, 0x34, 0x81, 0x80, 0, 1, 0, 2, 0, 0, 0, 0];
+    resp.extend_from_slice(question);
+    resp.extend_from_slice(&[0xc0, 0x0c, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xc0, 0x0c]);
+    resp.extend_from_slice(&[0xc0, 0x0c, 0, 16, 0, 1, 0, 0, 0, 60, 0, 16]);
+    resp.extend_from_slice(b"\x08dnsaddr=\x06/ip4/1");
+    assert_eq!(
+        parse_txt_response(0x1234, &resp).unwrap(),
+        vec!["dnsaddr=/ip4/1".to_string()]
+    );
+
+    assert!(parse_txt_response(0x4321, &resp).is_err());
+    assert!(parse_txt_response(0x1234, &resp[..resp.len() - 1]).is_err());
+
+    let mut nxdomain = resp[..12].to_vec();
+    nxdomain[3] = 0x83;
+    assert_eq!(
+        parse_txt_response(0x1234, &nxdomain).unwrap(),
+        Vec::<String>::new()
+    );
+
+    // truncated response, TC bit set.
+    let mut truncated = resp.clone();
+    truncated[2] |= 0x02;
+    assert!(is_truncated(&truncated));
+    assert!(!is_truncated(&resp));
+    assert!(parse_txt_response(0x1234, &truncated).is_err());
+}
+
+#[test]
+fn test_txt_large_response() {
+    // dnsaddr records as published for bootstrap nodes, over 512 bytes.
+    let records: Vec<String> = (0..8)
+        .map(|i| {
+            format!(
+                "dnsaddr=/ip4/147.75.87.{}/udp/4001/quic-v1/webtransport/certhash/{}/p2p/{}",
+                i, "uEiAkH5a4DPGKUuOBjYw0CgwjvcJCJMD2K_1aluKR_tpevQ", PEER1
+            )
+        })
+        .collect();
+
+    let resp = txt_response(&[0x12, 0x34], b"\x08_dnsaddr\x01a\x02io\x00", &records);
+    assert!(resp.len() > 512, "{}", resp.len());
+    assert_eq!(parse_txt_response(0x1234, &resp).unwrap(), records);
+}
+
+// Compose a response, with the question for `qname`, answering TXT
+// `records` as single character-string each.
+fn txt_response(id: &[u8], qname: &[u8], records: &[String]) -> Vec<u8> {
+    let mut resp = id.to_vec();
+    resp.extend_from_slice(&[0x81, 0x80, 0, 1, 0, records.len() as u8, 0, 0, 0, 0]);
+    resp.extend_from_slice(qname);
+    resp.extend_from_slice(&[0, 16, 0, 1]);
+    for txt in records.iter() {
+        resp.extend_from_slice(&[0xc0, 0x0c, 0, 16, 0, 1, 0, 0, 0, 60, 0]);
+        resp.push(txt.len() as u8 + 1);
+        resp.push(txt.len() as u8);
+        resp.extend_from_slice(txt.as_bytes());
+    }
+    resp
+}
+
+#[test]
+fn test_system_resolver_query() {
+    use std::{net::UdpSocket, thread};
+
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let spoofer = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let ns = server.local_addr().unwrap();
+
+    let handle = thread::spawn(move || {
+        let mut buf = vec![0; 512];
+        let (n, client) = server.recv_from(&mut buf).unwrap();
+        let query = buf[..n].to_vec();
+
+        // question name, without its type and class, and the OPT record.
+        let qname = &query[12..query.len() - 15];
+        let answer = |id: &[u8], txt: &str| txt_response(id, qname, &[txt.to_string()]);
+        let other_id = [query[0] ^ 0xff, query[1]];
+        // wrong source, then wrong id, then the real answer.
+        spoofer
+            .send_to(&answer(&query[..2], "dnsaddr=/ip4/6.6.6.6"), client)
+            .unwrap();
+        server
+            .send_to(&answer(&other_id, "dnsaddr=/ip4/6.6.6.6"), client)
+            .unwrap();
+        server
+            .send_to(&answer(&query[..2], "dnsaddr=/ip4/1.2.3.4"), client)
+            .unwrap();
+        query
+    });
+
+    let mut resolver = SystemResolver::with_nameservers(vec![ns]);
+    resolver.set_timeout(time::Duration::from_secs(5));
+    let records = resolver.lookup_txt("_dnsaddr.a.io").unwrap();
+    assert_eq!(records, vec!["dnsaddr=/ip4/1.2.3.4".to_string()]);
+
+    let query = handle.join().unwrap();
+    assert_eq!(
+        &query[2..],
+        &dns_query(0, "_dnsaddr.a.io", DNS_TYPE_TXT).unwrap()[2..]
+    );
+}
+
+#[test]
+fn test_system_resolver_tcp_fallback() {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, UdpSocket},
+        thread,
+    };
+
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let ns = server.local_addr().unwrap();
+    let listener = TcpListener::bind(ns).unwrap();
+    let records: Vec<String> = (0..8)
+        .map(|i| format!("dnsaddr=/ip4/10.0.0.{}/tcp/4001/p2p/{}", i, PEER1))
+        .collect();
+
+    let answers = records.clone();
+    let handle = thread::spawn(move || {
+        let mut buf = vec![0; 512];
+        let (n, client) = server.recv_from(&mut buf).unwrap();
+        let qname = buf[12..n - 15].to_vec();
+
+        // empty answer with TC bit set, over UDP.
+        let mut resp = txt_response(&buf[..2], &qname, &[]);
+        resp[2] |= 0x02;
+        server.send_to(&resp, client).unwrap();
+
+        // full answer over TCP.
+        let (mut conn, _) = listener.accept().unwrap();
+        let mut len = [0; 2];
+        conn.read_exact(&mut len).unwrap();
+        let mut query = vec![0; u16::from_be_bytes(len) as usize];
+        conn.read_exact(&mut query).unwrap();
+        let resp = txt_response(&query[..2], &qname, &answers);
+        conn.write_all(&(resp.len() as u16).to_be_bytes()).unwrap();
+        conn.write_all(&resp).unwrap();
+        query
+    });
+
+    let resolver = SystemResolver::with_nameservers(vec![ns]);
+    assert_eq!(resolver.lookup_txt("_dnsaddr.a.io").unwrap(), records);
+
+    let query = handle.join().unwrap();
+    assert_eq!(
+        &query[2..],
+        &dns_query(0, "_dnsaddr.a.io", DNS_TYPE_TXT).unwrap()[2..]
+    );
+}
+
+#[test]
+fn test_system_resolver_ip() {
+    use std::{net::UdpSocket, thread};
+
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let ns = server.local_addr().unwrap();
+
+    let handle = thread::spawn(move || {
+        let mut buf = vec![0; 512];
+        for _ in 0..4 {
+            let (n, client) = server.recv_from(&mut buf).unwrap();
+            // question name and type, without the OPT record.
+            let question = &buf[12..n - 11];
+            let qtype =
+                u16::from_be_bytes([question[question.len() - 4], question[question.len() - 3]]);
+            let known = question.starts_with(b"\x04node\x02io\x00");
+
+            let mut resp = buf[..2].to_vec();
+            match known {
+                true => resp.extend_from_slice(&[0x81, 0x80]),
+                false => resp.extend_from_slice(&[0x81, 0x83]), // NXDOMAIN
+            }
+            let rdatas: Vec<Vec<u8>> = match (known, qtype) {
+                (true, DNS_TYPE_A) => vec![vec![10, 0, 0, 1], vec![10, 0, 0, 2]],
+                (_, _) => vec![],
+            };
+            resp.extend_from_slice(&[0, 1, 0, (rdatas.len() + 1) as u8, 0, 0, 0, 0]);
+            resp.extend_from_slice(question);
+            // CNAME answer is skipped.
+            resp.extend_from_slice(&[0xc0, 0x0c, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xc0, 0x0c]);
+            for rdata in rdatas.iter() {
+                resp.extend_from_slice(&[0xc0, 0x0c, 0, qtype as u8, 0, 1, 0, 0, 0, 60, 0]);
+                resp.push(rdata.len() as u8);
+                resp.extend_from_slice(rdata);
+            }
+            server.send_to(&resp, client).unwrap();
+        }
+    });
+
+    let resolver = SystemResolver::with_nameservers(vec![ns]);
+    assert_eq!(
+        resolver.lookup_ip4("node.io").unwrap(),
+        vec![
+            "10.0.0.1".parse::<net::Ipv4Addr>().unwrap(),
+            "10.0.0.2".parse().unwrap()
+        ]
+    );
+    assert!(resolver.lookup_ip6("node.io").unwrap().is_empty());
+    // non-existent names have no records.
+    assert!(resolver.lookup_ip4("unknown.io").unwrap().is_empty());
+    assert!(resolver.lookup_ip6("unknown.io").unwrap().is_empty());
+    handle.join().unwrap();
+
+    // localhost is not sent to the nameserver.
+    assert_eq!(
+        resolver.lookup_ip4("localhost").unwrap(),
+        vec![net::Ipv4Addr::LOCALHOST]
+    );
+    assert_eq!(
+        resolver.lookup_ip6("a.localhost.").unwrap(),
+        vec![net::Ipv6Addr::LOCALHOST]
+    );
+    let ma = Multiaddr::from_text("/dns4/localhost/tcp/1").unwrap();
+    assert_eq!(
+        to_texts(ma.resolve(&resolver).unwrap()),
+        vec!["/ip4/127.0.0.1/tcp/1"]
+    );
+}
+
+#[test]
+fn test_parse_resolv_conf() {
+    let text = concat!(
+        "# comment\nsearch lan\nnameserver 127.0.0.53\n",
+        "nameserver fe80::1%eth0\nnameserver fe80::2%3\nnameserver 1.1.1.1%3\n",
+        "nameserver 2001:db8::1\nnameserver bad\n",
+    );
+    let link_local = net::SocketAddrV6::new("fe80::2".parse().unwrap(), 53, 0, 3);
+    assert_eq!(
+        parse_resolv_conf(text),
+        vec![
+            "127.0.0.53:53".parse().unwrap(),
+            net::SocketAddr::from(link_local),
+            "[2001:db8::1]:53".parse().unwrap(),
+        ]
+    );
+}
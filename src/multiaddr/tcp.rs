@@ -30,7 +30,7 @@ impl Tcp {
     }
 
     pub(crate) fn to_text(&self) -> Result<String> {
-        Ok("/tcp".to_string() + &self.port.to_string())
+        Ok("/tcp/".to_string() + &self.port.to_string())
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -30,7 +30,7 @@ impl Udp {
     }
 
     pub(crate) fn to_text(&self) -> Result<String> {
-        Ok("/udp".to_string() + &self.port.to_string())
+        Ok("/udp/".to_string() + &self.port.to_string())
     }
 
     pub(crate) fn decode(data: &[u8]) -> Result<(Self, &[u8])> {
@@ -63,7 +63,7 @@ impl Unix {
         use unsigned_varint::decode::u128 as uv_decode;
 
         let val = {
-            let (n, data) = err_at!(DecodeError, uv_decode(data))?;
+            let (n, data) = uv_decode(data)?;
             let (path, data) = read_slice!(data, (n as usize), "unix")?;
             let path = err_at!(DecodeError, from_utf8(path))?.to_string();
             (Unix { path }, data)
@@ -0,0 +1,232 @@
+//! Module implement semantic validation for Multiaddr.
+//!
+//! Parsing only checks that each component is well formed on its own.
+//! Validation checks the address as a whole, like the order in which
+//! protocols are stacked, and the values that parsing stores as is.
+
+use std::{fmt, result};
+
+use crate::{
+    multiaddr::Multiaddr,
+    multicodec::{self, Multicodec},
+    Error, Result,
+};
+
+const IP_OR_DNS: &[u128] = &[
+    multicodec::IP4,
+    multicodec::IP6,
+    multicodec::DNS,
+    multicodec::DNS4,
+    multicodec::DNS6,
+];
+
+/// Rule broken by a multiaddr component, refer [Violation].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Rule {
+    /// Component can only start an address, or a relayed address
+    /// following `p2p-circuit`.
+    MustStart,
+    /// Component must follow one of the listed protocols.
+    MustFollow(Vec<String>),
+    /// Component can't follow another component of the same protocol.
+    Repeated,
+    /// `ip6zone` must be followed by an `ip6` component.
+    MissingIp6,
+    /// Name is not a valid hostname, with the reason.
+    BadHostname(String),
+    /// Peer-id is not a valid multihash, with the reason.
+    BadPeerId(String),
+    /// Port must be non-zero.
+    ZeroPort,
+    /// Path must be non-empty.
+    EmptyPath,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        match self {
+            Rule::MustStart => write!(f, "must start the address"),
+            Rule::MustFollow(protos) => write!(f, "must follow {}", protos.join("|")),
+            Rule::Repeated => write!(f, "repeated protocol"),
+            Rule::MissingIp6 => write!(f, "must be followed by ip6"),
+            Rule::BadHostname(msg) => write!(f, "bad hostname, {}", msg),
+            Rule::BadPeerId(msg) => write!(f, "bad peer-id, {}", msg),
+            Rule::ZeroPort => write!(f, "zero port"),
+            Rule::EmptyPath => write!(f, "empty path"),
+        }
+    }
+}
+
+/// Type describe a rule violated by a multiaddr component, refer
+/// [Multiaddr::violations].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Violation {
+    /// Position of the offending component in the address, from 0.
+    pub index: usize,
+    /// Protocol name of the offending component.
+    pub protocol: String,
+    /// Rule broken by the component.
+    pub rule: Rule,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(
+            f,
+            "component {} /{}: {}",
+            self.index, self.protocol, self.rule
+        )
+    }
+}
+
+impl Violation {
+    fn new(index: usize, code: u128, rule: Rule) -> Violation {
+        Violation {
+            index,
+            protocol: Multicodec::from(code).to_string(),
+            rule,
+        }
+    }
+}
+
+impl Multiaddr {
+    /// Validate this multiaddr, return [Error] describing the first
+    /// offending component. Refer [Self::violations] for the rules.
+    pub fn validate(&self) -> Result<()> {
+        match self.violations()?.into_iter().next() {
+            Some(violation) => err_at!(BadAddr, msg: "{}", violation),
+            None => Ok(()),
+        }
+    }
+
+    /// Return all rules violated by this multiaddr. Error is returned only
+    /// when the multiaddr can't be parsed.
+    ///
+    /// * `tcp`, `udp`, `dccp`, `sctp` must follow `ip4`, `ip6` or a `dns*`
+    ///   component. `quic`, `utp` and `udt` must follow `udp`. `ws`,
+    ///   `wss`, `http` and `https` must follow `tcp`. `p2p-webrtc-direct`
+    ///   must follow `http`.
+    /// * `ip4`, `ip6`, `dns*`, `unix`, `onion*` and `garlic*` must start
+    ///   the address, or the relayed address after `p2p-circuit`. `ip6`
+    ///   may follow `ip6zone`, which in turn must be followed by `ip6`.
+    /// * `p2p` and `p2p-circuit` can't be repeated back to back.
+    /// * `dns*` names must be valid hostnames, `p2p` must hold a valid
+    ///   peer-id multihash, `onion*` ports must be non-zero and `unix`
+    ///   paths must be non-empty.
+    pub fn violations(&self) -> Result<Vec<Violation>> {
+        let comps = self.clone().split()?;
+
+        let mut violations = vec![];
+        let mut prev: Option<u128> = None;
+        for (index, comp) in comps.iter().enumerate() {
+            let code = match comp.to_multicodec() {
+                Some(codec) => codec.to_code(),
+                None => continue,
+            };
+
+            if prev == Some(multicodec::IP6ZONE) && code != multicodec::IP6 {
+                violations.push(Violation::new(
+                    index - 1,
+                    multicodec::IP6ZONE,
+                    Rule::MissingIp6,
+                ))
+            }
+            if let Some(rule) = check_placement(code, prev) {
+                violations.push(Violation::new(index, code, rule))
+            }
+            if let Some(rule) = check_value(comp) {
+                violations.push(Violation::new(index, code, rule))
+            }
+            prev = Some(code);
+        }
+
+        if prev == Some(multicodec::IP6ZONE) {
+            let index = comps.len() - 1;
+            violations.push(Violation::new(index, multicodec::IP6ZONE, Rule::MissingIp6))
+        }
+
+        Ok(violations)
+    }
+}
+
+fn check_placement(code: u128, prev: Option<u128>) -> Option<Rule> {
+    // a relayed address starts afresh after p2p-circuit.
+    let at_start = matches!(prev, None | Some(multicodec::P2P_CIRCUIT));
+
+    let after = match code {
+        multicodec::TCP | multicodec::UDP | multicodec::DCCP | multicodec::SCTP => IP_OR_DNS,
+        multicodec::QUIC | multicodec::UTP | multicodec::UDT => &[multicodec::UDP],
+        multicodec::WS | multicodec::WSS | multicodec::HTTP | multicodec::HTTPS => {
+            &[multicodec::TCP]
+        }
+        multicodec::P2P_WEBRTC_DIRECT => &[multicodec::HTTP],
+        multicodec::IP6 if prev == Some(multicodec::IP6ZONE) => return None,
+        multicodec::P2P | multicodec::P2P_CIRCUIT if prev == Some(code) => {
+            return Some(Rule::Repeated)
+        }
+        multicodec::P2P | multicodec::P2P_CIRCUIT => return None,
+        _ if at_start => return None,
+        _ => return Some(Rule::MustStart),
+    };
+
+    match prev {
+        Some(prev) if after.contains(&prev) => None,
+        Some(prev) if prev == code => Some(Rule::Repeated),
+        _ => {
+            let protos = after.iter().map(|code| Multicodec::from(*code).to_string());
+            Some(Rule::MustFollow(protos.collect()))
+        }
+    }
+}
+
+fn check_value(comp: &Multiaddr) -> Option<Rule> {
+    let name = match comp {
+        Multiaddr::Dns(val, _) => val.as_str(),
+        Multiaddr::Dns4(val, _) => val.as_str(),
+        Multiaddr::Dns6(val, _) => val.as_str(),
+        Multiaddr::Dnsaddr(val, _) => val.as_str(),
+        Multiaddr::P2p(val, _) | Multiaddr::Ipfs(val, _) => {
+            return val.peer_id().err().map(|e| Rule::BadPeerId(e.to_string()));
+        }
+        Multiaddr::Onion(val, _) if val.to_port() == 0 => return Some(Rule::ZeroPort),
+        Multiaddr::Onion3(val, _) if val.to_port() == 0 => return Some(Rule::ZeroPort),
+        Multiaddr::Unix(val, _) if val.to_path().trim_matches('/').is_empty() => {
+            return Some(Rule::EmptyPath)
+        }
+        _ => return None,
+    };
+
+    match name {
+        Ok(name) => check_hostname(name).err().map(Rule::BadHostname),
+        Err(_) => Some(Rule::BadHostname("not utf8".to_string())),
+    }
+}
+
+// Hostname as per RFC-1123, with an optional trailing dot.
+fn check_hostname(name: &str) -> result::Result<(), String> {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    if name.is_empty() || name.len() > 253 {
+        return Err(format!("length {}", name.len()));
+    }
+
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(format!("label length {} in {:?}", label.len(), name));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(format!("label {:?} with hyphen at edge", label));
+        }
+        if let Some(ch) = label
+            .chars()
+            .find(|ch| !(ch.is_ascii_alphanumeric() || *ch == '-'))
+        {
+            return Err(format!("invalid char {:?} in {:?}", ch, name));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "validate_test.rs"]
+mod validate_test;
@@ -0,0 +1,131 @@
+use super::*;
+
+const PEER: &str = "QmNnooDu7bfjPFoTZYxMNLWUQJyrVwtbZg5gBMjTezGAJN";
+
+fn violations(text: &str) -> Vec<(usize, String, Rule)> {
+    let ma = Multiaddr::from_text(text).unwrap();
+    let iter = ma.violations().unwrap().into_iter();
+    iter.map(|v| (v.index, v.protocol, v.rule)).collect()
+}
+
+#[test]
+fn test_validate_ok() {
+    let texts = vec![
+        "/ip4/1.2.3.4/tcp/80".to_string(),
+        "/ip6/::1/udp/4001/quic".to_string(),
+        "/ip6zone/eth0/ip6/fe80::1/tcp/1/ws".to_string(),
+        "/dns4/example.com/tcp/443/wss".to_string(),
+        "/dns/a-b.example.com./tcp/80/http/p2p-webrtc-direct".to_string(),
+        "/dnsaddr/bootstrap.libp2p.io".to_string(),
+        "/unix/tmp/p2p.sock".to_string(),
+        format!("/p2p/{}", PEER),
+        format!("/ip4/1.2.3.4/tcp/1/p2p/{}/p2p-circuit/p2p/{}", PEER, PEER),
+        format!("/p2p/{}/p2p-circuit/ip4/5.6.7.8/tcp/2", PEER),
+        "/onion/aaimaq4ygg2iegci:80".to_string(),
+    ];
+
+    for text in texts.iter() {
+        let ma = Multiaddr::from_text(text).unwrap();
+        assert_eq!(ma.violations().unwrap(), vec![], "{}", text);
+        ma.validate().unwrap();
+    }
+}
+
+#[test]
+fn test_validate_ordering() {
+    let follow_ip = Rule::MustFollow(
+        vec!["ip4", "ip6", "dns", "dns4", "dns6"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect(),
+    );
+    let follow_tcp = Rule::MustFollow(vec!["tcp".to_string()]);
+
+    assert_eq!(
+        violations("/tcp/80/ip4/1.2.3.4"),
+        vec![
+            (0, "tcp".to_string(), follow_ip.clone()),
+            (1, "ip4".to_string(), Rule::MustStart)
+        ]
+    );
+    assert_eq!(
+        violations("/ws/ws/ws"),
+        vec![
+            (0, "ws".to_string(), follow_tcp.clone()),
+            (1, "ws".to_string(), Rule::Repeated),
+            (2, "ws".to_string(), Rule::Repeated),
+        ]
+    );
+    assert_eq!(
+        violations("/ip4/1.2.3.4/udp/1/ws"),
+        vec![(2, "ws".to_string(), follow_tcp)]
+    );
+    assert_eq!(
+        violations("/ip4/1.2.3.4/tcp/1/quic"),
+        vec![(
+            2,
+            "quic".to_string(),
+            Rule::MustFollow(vec!["udp".to_string()])
+        )]
+    );
+    assert_eq!(
+        violations("/dnsaddr/example.com/tcp/1"),
+        vec![(1, "tcp".to_string(), follow_ip)]
+    );
+    assert_eq!(
+        violations("/ip6zone/eth0/ip4/1.2.3.4"),
+        vec![
+            (0, "ip6zone".to_string(), Rule::MissingIp6),
+            (1, "ip4".to_string(), Rule::MustStart)
+        ]
+    );
+    assert_eq!(
+        violations("/ip6zone/eth0"),
+        vec![(0, "ip6zone".to_string(), Rule::MissingIp6)]
+    );
+    assert_eq!(
+        violations(&format!("/p2p/{}/p2p/{}", PEER, PEER)),
+        vec![(1, "p2p".to_string(), Rule::Repeated)]
+    );
+}
+
+#[test]
+fn test_validate_values() {
+    let rules = |text: &str| -> Vec<Rule> {
+        let iter = violations(text).into_iter();
+        iter.map(|(_, _, rule)| rule).collect()
+    };
+
+    for text in [
+        "/dns4/exa_mple.com/tcp/1",
+        "/dns6/-example.com",
+        "/dns/example..com",
+        "/dnsaddr/.",
+    ]
+    .iter()
+    {
+        match rules(text).as_slice() {
+            [Rule::BadHostname(_)] => (),
+            rules => panic!("{} {:?}", text, rules),
+        }
+    }
+    let long = format!("/dns/{}.com", "a".repeat(64));
+    assert!(matches!(rules(&long).as_slice(), [Rule::BadHostname(_)]));
+
+    match rules("/p2p/QmNotAPeerId").as_slice() {
+        [Rule::BadPeerId(_)] => (),
+        rules => panic!("{:?}", rules),
+    }
+    assert_eq!(rules("/unix/"), vec![Rule::EmptyPath]);
+
+    // zero port can only be carried in binary form.
+    let mut data = Multiaddr::from_text("/onion/aaimaq4ygg2iegci:80")
+        .unwrap()
+        .encode()
+        .unwrap();
+    let n = data.len();
+    data[n - 2..].copy_from_slice(&[0, 0]);
+    let ma = Multiaddr::decode(&data).unwrap().0;
+    let err = ma.validate().unwrap_err().to_string();
+    assert!(err.contains("component 0 /onion: zero port"), "{}", err);
+}
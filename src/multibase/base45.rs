@@ -0,0 +1,48 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{Error, Result};
+
+// Refer RFC-9285.
+const ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+pub(crate) fn encode(data: &[u8], out: &mut String) {
+    out.reserve(encoded_len(data.len()));
+    for chunk in data.chunks(2) {
+        let (mut n, width) = match chunk {
+            [a, b] => ((usize::from(*a) << 8) | usize::from(*b), 3),
+            _ => (usize::from(chunk[0]), 2),
+        };
+        for _ in 0..width {
+            out.push(ALPHABET[n % 45] as char);
+            n /= 45;
+        }
+    }
+}
+
+pub(crate) fn decode(text: &str, out: &mut Vec<u8>) -> Result<()> {
+    let text = text.as_bytes();
+    out.reserve(decoded_len_max(text.len()));
+    for chunk in text.chunks(3) {
+        let mut n = 0;
+        for ch in chunk.iter().rev() {
+            match ALPHABET.iter().position(|a| a == ch) {
+                Some(val) => n = n * 45 + val,
+                None => err_at!(DecodeError, msg: "base45 invalid char {:?}", *ch as char)?,
+            }
+        }
+        match chunk.len() {
+            3 if n <= 0xffff => out.extend_from_slice(&[(n >> 8) as u8, n as u8]),
+            2 if n <= 0xff => out.push(n as u8),
+            _ => err_at!(DecodeError, msg: "base45 invalid group {:?}", chunk)?,
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn encoded_len(n: usize) -> usize {
+    (n / 2) * 3 + (n % 2) * 2
+}
+
+pub(crate) fn decoded_len_max(n: usize) -> usize {
+    (n / 3) * 2 + 1
+}
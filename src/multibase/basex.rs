@@ -0,0 +1,77 @@
+use alloc::{string::String, vec::Vec};
+use core::mem;
+
+use crate::{Error, Result};
+
+// Encode `data` as a big-endian number in base `alphabet.len()`. Each
+// leading zero byte is encoded as a leading `alphabet[0]` character.
+// Digits are computed in place, within `out`.
+pub(crate) fn encode(alphabet: &[u8], data: &[u8], out: &mut String) -> Result<()> {
+    let base = alphabet.len() as u32;
+    let zeros = data.iter().take_while(|b| **b == 0).count();
+
+    let mut buf = mem::take(out).into_bytes();
+    buf.reserve(encoded_len(alphabet.len(), data.len()));
+    buf.extend((0..zeros).map(|_| 0));
+
+    // little-endian digits
+    let start = buf.len();
+    for byte in data[zeros..].iter() {
+        let mut carry = u32::from(*byte);
+        for digit in buf[start..].iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % base) as u8;
+            carry /= base;
+        }
+        while carry > 0 {
+            buf.push((carry % base) as u8);
+            carry /= base;
+        }
+    }
+    buf[start..].reverse();
+
+    let n = start - zeros;
+    buf[n..].iter_mut().for_each(|d| *d = alphabet[*d as usize]);
+    *out = err_at!(Fatal, String::from_utf8(buf))?;
+    Ok(())
+}
+
+pub(crate) fn decode(alphabet: &[u8], text: &str, out: &mut Vec<u8>) -> Result<()> {
+    let base = alphabet.len() as u32;
+    let zeros = text.bytes().take_while(|ch| *ch == alphabet[0]).count();
+
+    out.reserve(text.len());
+    out.extend((0..zeros).map(|_| 0));
+
+    // little-endian bytes
+    let start = out.len();
+    for (i, ch) in text.bytes().enumerate().skip(zeros) {
+        let mut carry = match alphabet.iter().position(|a| *a == ch) {
+            Some(val) => val as u32,
+            None => err_at!(DecodeError, msg: "base{} invalid char at {}", base, i)?,
+        };
+        for byte in out[start..].iter_mut() {
+            carry += u32::from(*byte) * base;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            out.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    out[start..].reverse();
+    Ok(())
+}
+
+// Upper bound, log(256) / log(base) digits for every byte. Leading
+// zero bytes take a single digit.
+pub(crate) fn encoded_len(base: usize, n: usize) -> usize {
+    let ratio = match base {
+        10 => 241,
+        36 => 155,
+        58 => 137,
+        _ => 800,
+    };
+    (n * ratio) / 100 + 1
+}
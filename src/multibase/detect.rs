@@ -0,0 +1,75 @@
+use alloc::vec::Vec;
+
+use crate::multibase::{Base, BASES};
+
+// Return the alphabet size for `base`, None for bases that are detected
+// by their shape instead of their alphabet.
+fn alphabet_len(base: Base) -> Option<usize> {
+    use Base::*;
+
+    match base {
+        Identity | Proquint | Base256Emoji => None,
+        Base2 => Some(2),
+        Base8 => Some(8),
+        Base10 => Some(10),
+        Base16Lower | Base16Upper => Some(16),
+        Base32HexLower | Base32HexUpper | Base32HexPadLower | Base32HexPadUpper => Some(32),
+        Base32Lower | Base32Upper | Base32PadLower | Base32PadUpper | Base32Z => Some(32),
+        Base36Lower | Base36Upper => Some(36),
+        Base45 => Some(45),
+        Base58Btc | Base58Flickr => Some(58),
+        Base64 | Base64Pad | Base64Url | Base64UrlPad => Some(64),
+    }
+}
+
+// Text that decodes under several bases is more likely to be encoded using
+// the base with the smallest alphabet that covers the characters used in
+// text. Confidence is the fraction of the alphabet used by the text, with
+// well known shapes, like CIDv0 and hex digests, scoring higher.
+pub(crate) fn detect_unprefixed(text: &str) -> Vec<(Base, f64)> {
+    let mut chars: Vec<char> = text.chars().filter(|ch| *ch != '=').collect();
+    chars.sort_unstable();
+    chars.dedup();
+    let distinct = chars.len() as f64;
+
+    let mut bases = vec![];
+    if text.is_empty() {
+        return bases;
+    }
+
+    for base in BASES.iter().skip(1) {
+        let confidence = match (base, alphabet_len(*base)) {
+            (Base::Proquint, _) => {
+                let text = text.strip_prefix("pro-").unwrap_or(text);
+                match base.decode(&format!("ro-{}", text)) {
+                    Ok(_) => 0.9,
+                    Err(_) => continue,
+                }
+            }
+            (_, alen) => match base.decode(text) {
+                Ok(data) => match (base, alen) {
+                    (Base::Base256Emoji, _) => 1.0,
+                    (Base::Base58Btc, _) if is_cid_v0(text, &data) => 1.0,
+                    (Base::Base16Lower | Base::Base16Upper, _) if is_digest(&data) => 1.0,
+                    (_, Some(alen)) => distinct / (alen as f64),
+                    (_, None) => continue,
+                },
+                Err(_) => continue,
+            },
+        };
+        bases.push((*base, confidence))
+    }
+
+    bases.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(core::cmp::Ordering::Equal));
+    bases
+}
+
+// CIDv0 is base58btc encoded sha2-256 multihash.
+fn is_cid_v0(text: &str, data: &[u8]) -> bool {
+    text.len() == 46 && text.starts_with("Qm") && data.len() == 34 && data[..2] == [0x12, 0x20]
+}
+
+// Common digest sizes, md5, sha1, sha2-256 and sha2-512.
+fn is_digest(data: &[u8]) -> bool {
+    matches!(data.len(), 16 | 20 | 32 | 64)
+}
@@ -0,0 +1,59 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{Error, Result};
+
+// Each byte is encoded as one emoji from this alphabet.
+#[rustfmt::skip]
+const ALPHABET: [char; 256] = [
+    '🚀', '🪐', '☄', '🛰', '🌌', '🌑', '🌒', '🌓',
+    '🌔', '🌕', '🌖', '🌗', '🌘', '🌍', '🌏', '🌎',
+    '🐉', '☀', '💻', '🖥', '💾', '💿', '😂', '❤',
+    '😍', '🤣', '😊', '🙏', '💕', '😭', '😘', '👍',
+    '😅', '👏', '😁', '🔥', '🥰', '💔', '💖', '💙',
+    '😢', '🤔', '😆', '🙄', '💪', '😉', '☺', '👌',
+    '🤗', '💜', '😔', '😎', '😇', '🌹', '🤦', '🎉',
+    '💞', '✌', '✨', '🤷', '😱', '😌', '🌸', '🙌',
+    '😋', '💗', '💚', '😏', '💛', '🙂', '💓', '🤩',
+    '😄', '😀', '🖤', '😃', '💯', '🙈', '👇', '🎶',
+    '😒', '🤭', '❣', '😜', '💋', '👀', '😪', '😑',
+    '💥', '🙋', '😞', '😩', '😡', '🤪', '👊', '🥳',
+    '😥', '🤤', '👉', '💃', '😳', '✋', '😚', '😝',
+    '😴', '🌟', '😬', '🙃', '🍀', '🌷', '😻', '😓',
+    '⭐', '✅', '🥺', '🌈', '😈', '🤘', '💦', '✔',
+    '😣', '🏃', '💐', '☹', '🎊', '💘', '😠', '☝',
+    '😕', '🌺', '🎂', '🌻', '😐', '🖕', '💝', '🙊',
+    '😹', '🗣', '💫', '💀', '👑', '🎵', '🤞', '😛',
+    '🔴', '😤', '🌼', '😫', '⚽', '🤙', '☕', '🏆',
+    '🤫', '👈', '😮', '🙆', '🍻', '🍃', '🐶', '💁',
+    '😲', '🌿', '🧡', '🎁', '⚡', '🌞', '🎈', '❌',
+    '✊', '👋', '😰', '🤨', '😶', '🤝', '🚶', '💰',
+    '🍓', '💢', '🤟', '🙁', '🚨', '💨', '🤬', '✈',
+    '🎀', '🍺', '🤓', '😙', '💟', '🌱', '😖', '👶',
+    '🥴', '▶', '➡', '❓', '💎', '💸', '⬇', '😨',
+    '🌚', '🦋', '😷', '🕺', '⚠', '🙅', '😟', '😵',
+    '👎', '🤲', '🤠', '🤧', '📌', '🔵', '💅', '🧐',
+    '🐾', '🍒', '😗', '🤑', '🌊', '🤯', '🐷', '☎',
+    '💧', '😯', '💆', '👆', '🎤', '🙇', '🍑', '❄',
+    '🌴', '💣', '🐸', '💌', '📍', '🥀', '🤢', '👅',
+    '💡', '💩', '👐', '📸', '👻', '🤐', '🤮', '🎼',
+    '🥵', '🚩', '🍎', '🍊', '👼', '💍', '📣', '🥂',
+];
+
+pub(crate) fn encode(data: &[u8], out: &mut String) {
+    out.extend(data.iter().map(|b| ALPHABET[*b as usize]))
+}
+
+pub(crate) fn decode(text: &str, out: &mut Vec<u8>) -> Result<()> {
+    out.reserve(text.len() / 4);
+    for (i, ch) in text.chars().enumerate() {
+        match ALPHABET.iter().position(|c| *c == ch) {
+            Some(b) => out.push(b as u8),
+            None => err_at!(DecodeError, msg: "base256emoji invalid {:?} at {}", ch, i)?,
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn encoded_len(data: &[u8]) -> usize {
+    data.iter().map(|b| ALPHABET[*b as usize].len_utf8()).sum()
+}
@@ -0,0 +1,508 @@
+//! Module implement Multibase. _Refer [multibase] spec for detail_.
+//!
+//! All base formats listed by the specification are natively implemented.
+//! Decoders are strict, case-sensitive bases reject characters of the
+//! other case and padded bases reject invalid padding. Use [BASES] and
+//! [Base] methods to lookup, encode and decode a base format.
+//!
+//! With `std` feature, [MultibaseWriter] and [MultibaseReader] can encode
+//! and decode a stream of bytes.
+//!
+//! [multibase]: https://github.com/multiformats/multibase
+
+use alloc::{string::String, vec::Vec};
+use core::mem;
+
+use crate::{Error, Result};
+
+mod base45;
+mod basex;
+mod detect;
+mod emoji;
+mod proquint;
+mod rfc4648;
+#[cfg(feature = "std")]
+mod stream;
+
+#[cfg(feature = "std")]
+pub use stream::{MultibaseReader, MultibaseWriter, BUFFER_LIMIT};
+
+macro_rules! bases {
+    ($(($var:ident, $name:expr, $code:expr, $descr:expr),)*) => {
+        /// Base formats defined by multibase specification.
+        #[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+        pub enum Base {
+            $($var,)*
+        }
+
+        /// Registry of base formats, in the order of multibase specification.
+        pub const BASES: [Base; 26] = [$(Base::$var,)*];
+
+        /// Multibase table of (name, prefix-char, description).
+        pub const TABLE: [(&str, char, &str); 26] = [$(($name, $code, $descr),)*];
+
+        impl Base {
+            /// Lookup base format for multibase prefix `ch`.
+            pub fn from_code(ch: char) -> Result<Base> {
+                match ch {
+                    $($code => Ok(Base::$var),)*
+                    _ => Err(Error::InvalidBase(ch)),
+                }
+            }
+
+            /// Lookup base format by its multibase name.
+            pub fn from_name(name: &str) -> Result<Base> {
+                match name {
+                    $($name => Ok(Base::$var),)*
+                    _ => err_at!(Invalid, msg: "unknown base name {:?}", name),
+                }
+            }
+
+            /// Return the multibase prefix character.
+            pub fn code(&self) -> char {
+                match self {
+                    $(Base::$var => $code,)*
+                }
+            }
+
+            /// Return the multibase name.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Base::$var => $name,)*
+                }
+            }
+        }
+    };
+}
+
+bases![
+    (
+        Identity,
+        "identity",
+        '\0',
+        "8-bit binary (encoder and decoder keeps data unmodified)"
+    ),
+    (Base2, "base2", '0', "binary (01010101)"),
+    (Base8, "base8", '7', "octal"),
+    (Base10, "base10", '9', "decimal"),
+    (Base16Lower, "base16", 'f', "hexadecimal"),
+    (Base16Upper, "base16upper", 'F', "hexadecimal"),
+    (
+        Base32HexLower,
+        "base32hex",
+        'v',
+        "rfc4648 case-insensitive - no padding - highest char"
+    ),
+    (
+        Base32HexUpper,
+        "base32hexupper",
+        'V',
+        "rfc4648 case-insensitive - no padding - highest char"
+    ),
+    (
+        Base32HexPadLower,
+        "base32hexpad",
+        't',
+        "rfc4648 case-insensitive - with padding"
+    ),
+    (
+        Base32HexPadUpper,
+        "base32hexpadupper",
+        'T',
+        "rfc4648 case-insensitive - with padding"
+    ),
+    (
+        Base32Lower,
+        "base32",
+        'b',
+        "rfc4648 case-insensitive - no padding"
+    ),
+    (
+        Base32Upper,
+        "base32upper",
+        'B',
+        "rfc4648 case-insensitive - no padding"
+    ),
+    (
+        Base32PadLower,
+        "base32pad",
+        'c',
+        "rfc4648 case-insensitive - with padding"
+    ),
+    (
+        Base32PadUpper,
+        "base32padupper",
+        'C',
+        "rfc4648 case-insensitive - with padding"
+    ),
+    (Base32Z, "base32z", 'h', "z-base-32 (used by Tahoe-LAFS)"),
+    (
+        Base36Lower,
+        "base36",
+        'k',
+        "base36 [0-9a-z] case-insensitive - no padding"
+    ),
+    (
+        Base36Upper,
+        "base36upper",
+        'K',
+        "base36 [0-9a-z] case-insensitive - no padding"
+    ),
+    (Base45, "base45", 'R', "rfc9285"),
+    (Base58Btc, "base58btc", 'z', "base58 bitcoin"),
+    (Base58Flickr, "base58flickr", 'Z', "base58 flicker"),
+    (Base64, "base64", 'm', "rfc4648 no padding"),
+    (
+        Base64Pad,
+        "base64pad",
+        'M',
+        "rfc4648 with padding - MIME encoding"
+    ),
+    (Base64Url, "base64url", 'u', "rfc4648 no padding"),
+    (Base64UrlPad, "base64urlpad", 'U', "rfc4648 with padding"),
+    (
+        Proquint,
+        "proquint",
+        'p',
+        "PRO-QUINT https://arxiv.org/html/0901.4016"
+    ),
+    (
+        Base256Emoji,
+        "base256emoji",
+        '🚀',
+        "base256 with custom alphabet using variable-sized-codepoints"
+    ),
+];
+
+const BASE10: &[u8; 10] = b"0123456789";
+const BASE36_LOWER: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE36_UPPER: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl Base {
+    /// Encode `data` in this base format, without the multibase prefix.
+    /// Identity base can only encode utf8 data.
+    pub fn encode(&self, data: &[u8]) -> Result<String> {
+        let mut text = String::with_capacity(self.encoded_len(data));
+        self.encode_into(data, &mut text)?;
+        Ok(text)
+    }
+
+    /// Same as [Self::encode], but append the encoded text to `out`.
+    pub fn encode_into(&self, data: &[u8], out: &mut String) -> Result<()> {
+        use Base::*;
+
+        if let Some(encoding) = self.as_encoding() {
+            encoding.encode_append(data, out);
+            return Ok(());
+        }
+
+        match self {
+            Identity => out.push_str(err_at!(Invalid, core::str::from_utf8(data))?),
+            Base10 => basex::encode(BASE10, data, out)?,
+            Base36Lower => basex::encode(BASE36_LOWER, data, out)?,
+            Base36Upper => basex::encode(BASE36_UPPER, data, out)?,
+            Base45 => base45::encode(data, out),
+            Base58Btc => base58_encode(bs58::Alphabet::BITCOIN, data, out)?,
+            Base58Flickr => base58_encode(bs58::Alphabet::FLICKR, data, out)?,
+            Proquint => proquint::encode(data, out),
+            Base256Emoji => emoji::encode(data, out),
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    /// Decode `text`, without the multibase prefix, from this base format.
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>> {
+        let mut data = Vec::with_capacity(self.decoded_len_max(text));
+        self.decode_into(text, &mut data)?;
+        Ok(data)
+    }
+
+    /// Same as [Self::decode], but append the decoded bytes to `out`. On
+    /// error `out` is left unmodified.
+    pub fn decode_into(&self, text: &str, out: &mut Vec<u8>) -> Result<()> {
+        let n = out.len();
+        let res = self.do_decode_into(text, out);
+        if res.is_err() {
+            out.truncate(n);
+        }
+        res
+    }
+
+    fn do_decode_into(&self, text: &str, out: &mut Vec<u8>) -> Result<()> {
+        use Base::*;
+
+        let inp = text.as_bytes();
+        let n = out.len();
+
+        if let Some(encoding) = self.as_encoding() {
+            out.resize(n + encoding.decode_len(inp.len())?, 0);
+            let m = encoding
+                .decode_mut(inp, &mut out[n..])
+                .map_err(|partial| partial.error)?;
+            out.truncate(n + m);
+            return Ok(());
+        }
+
+        match self {
+            Identity => out.extend_from_slice(inp),
+            Base10 => basex::decode(BASE10, text, out)?,
+            Base36Lower => basex::decode(BASE36_LOWER, text, out)?,
+            Base36Upper => basex::decode(BASE36_UPPER, text, out)?,
+            Base45 => base45::decode(text, out)?,
+            Base58Btc | Base58Flickr => {
+                let alphabet = match self {
+                    Base58Btc => bs58::Alphabet::BITCOIN,
+                    _ => bs58::Alphabet::FLICKR,
+                };
+                out.resize(n + inp.len(), 0);
+                let m = bs58::decode(inp)
+                    .with_alphabet(alphabet)
+                    .into(&mut out[n..])?;
+                out.truncate(n + m);
+            }
+            Proquint => proquint::decode(text, out)?,
+            Base256Emoji => emoji::decode(text, out)?,
+            _ => unreachable!(),
+        }
+
+        Ok(())
+    }
+
+    /// Return the length, in bytes, of `data` encoded in this base format,
+    /// without the multibase prefix. For base10, base36 and base58 the
+    /// returned value is an upper bound.
+    pub fn encoded_len(&self, data: &[u8]) -> usize {
+        use Base::*;
+
+        if let Some(encoding) = self.as_encoding() {
+            return encoding.encode_len(data.len());
+        }
+
+        match self {
+            Identity => data.len(),
+            Base10 => basex::encoded_len(10, data.len()),
+            Base36Lower | Base36Upper => basex::encoded_len(36, data.len()),
+            Base45 => base45::encoded_len(data.len()),
+            Base58Btc | Base58Flickr => basex::encoded_len(58, data.len()),
+            Proquint => proquint::encoded_len(data.len()),
+            Base256Emoji => emoji::encoded_len(data),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Return the maximum length of bytes decoded from `text`, without
+    /// the multibase prefix.
+    pub fn decoded_len_max(&self, text: &str) -> usize {
+        use Base::*;
+
+        let n = text.len();
+        match self.as_encoding() {
+            Some(encoding) => encoding.decode_len(n).unwrap_or(0),
+            None => match self {
+                Base45 => base45::decoded_len_max(n),
+                Proquint => proquint::decoded_len_max(n),
+                Base256Emoji => text.chars().count(),
+                _ => n,
+            },
+        }
+    }
+
+    fn as_encoding(&self) -> Option<data_encoding::Encoding> {
+        use Base::*;
+
+        let encoding = match self {
+            Base2 => rfc4648::BASE2,
+            Base8 => rfc4648::BASE8,
+            Base16Lower => rfc4648::BASE16_LOWER,
+            Base16Upper => rfc4648::BASE16_UPPER,
+            Base32HexLower => rfc4648::BASE32_HEX_LOWER,
+            Base32HexUpper => rfc4648::BASE32_HEX_UPPER,
+            Base32HexPadLower => rfc4648::BASE32_HEX_PAD_LOWER,
+            Base32HexPadUpper => rfc4648::BASE32_HEX_PAD_UPPER,
+            Base32Lower => rfc4648::BASE32_LOWER,
+            Base32Upper => rfc4648::BASE32_UPPER,
+            Base32PadLower => rfc4648::BASE32_PAD_LOWER,
+            Base32PadUpper => rfc4648::BASE32_PAD_UPPER,
+            Base32Z => rfc4648::BASE32_Z,
+            Base64 => rfc4648::BASE64,
+            Base64Pad => rfc4648::BASE64_PAD,
+            Base64Url => rfc4648::BASE64_URL,
+            Base64UrlPad => rfc4648::BASE64_URL_PAD,
+            _ => return None,
+        };
+
+        Some(encoding)
+    }
+}
+
+fn base58_encode(alphabet: &bs58::Alphabet, data: &[u8], out: &mut String) -> Result<()> {
+    let mut buf = mem::take(out).into_bytes();
+    let n = buf.len();
+    buf.resize(n + basex::encoded_len(58, data.len()), 0);
+    let res = bs58::encode(data)
+        .with_alphabet(alphabet)
+        .into(&mut buf[n..]);
+    buf.truncate(n + res.as_ref().map(|m| *m).unwrap_or(0));
+    *out = err_at!(Fatal, String::from_utf8(buf))?;
+    err_at!(Fatal, res)?;
+    Ok(())
+}
+
+/// Type to encode/decode bytes into/from multi-base formats.
+///
+/// Refer to [multibase] specification for supported base formats.
+///
+/// [multibase]: https://github.com/multiformats/multibase
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Multibase {
+    base: Base,
+    data: Option<Vec<u8>>,
+}
+
+impl Multibase {
+    /// Create a multibase encoder from one of the many base formats.
+    /// Subsequently encode() on this value will encode the supplied `data`.
+    pub fn with_base(base: Base, data: &[u8]) -> Result<Multibase> {
+        Ok(Multibase {
+            base,
+            data: Some(data.to_vec()),
+        })
+    }
+
+    /// Create a multibase encoder from character prefix defined in multibase
+    /// [specification]. Subsequently encode() on this value will encode the
+    /// supplied `data`.
+    ///
+    /// [specification]: https://github.com/multiformats/multibase/blob/master/multibase.csv
+    pub fn with_char(ch: char, data: &[u8]) -> Result<Multibase> {
+        let base = Base::from_code(ch)?;
+
+        Ok(Multibase {
+            base,
+            data: Some(data.to_vec()),
+        })
+    }
+
+    /// Base representation of binary-data, encoded stream of bytes shall
+    /// have the <base-prefix> followed by the actual base-representation
+    /// of the `input`.
+    pub fn to_text(&self) -> Result<String> {
+        let mut text = String::new();
+        if let Some(data) = &self.data {
+            text.reserve(Self::encoded_len(self.base, data));
+            Self::encode_into(self.base, data, &mut text)?;
+        }
+        Ok(text)
+    }
+
+    /// Encode `data` using `base`, with the <base-prefix>, and append the
+    /// text to `out`. Unlike [Self::with_base] and [Self::to_text], this
+    /// neither copies `data` nor allocates, provided `out` has capacity
+    /// for [Self::encoded_len] bytes.
+    pub fn encode_into(base: Base, data: &[u8], out: &mut String) -> Result<()> {
+        let n = out.len();
+        out.push(base.code());
+        let res = base.encode_into(data, out);
+        if res.is_err() {
+            out.truncate(n);
+        }
+        res
+    }
+
+    /// Return the length, in bytes, of multibase text for `data` encoded
+    /// using `base`, including the <base-prefix>. Refer [Base::encoded_len].
+    pub fn encoded_len(base: Base, data: &[u8]) -> usize {
+        base.code().len_utf8() + base.encoded_len(data)
+    }
+
+    /// Decode <base-prefix> followed by the base-representation, into
+    /// raw-data. Caller can use the returned value to get the base
+    /// format and the original raw-data. Refer [Self::to_base],
+    /// [Self::to_bytes].
+    pub fn from_text(text: &str) -> Result<Multibase> {
+        let (base, text) = split_prefix(text)?;
+        let data = base.decode(text)?;
+        let val = Multibase {
+            base,
+            data: Some(data),
+        };
+
+        Ok(val)
+    }
+
+    /// Same as [Self::from_text], but fail if `text` is not encoded using
+    /// `base`.
+    pub fn decode_as(base: Base, text: &str) -> Result<Multibase> {
+        match split_prefix(text)? {
+            (b, _) if b != base => {
+                err_at!(DecodeError, msg: "expected {} got {}", base.name(), b.name())
+            }
+            (_, text) => Ok(Multibase {
+                base,
+                data: Some(base.decode(text)?),
+            }),
+        }
+    }
+
+    /// Decode <base-prefix> followed by the base-representation, and
+    /// append the raw-data to `out`. Return the base format of `text`.
+    pub fn decode_into(text: &str, out: &mut Vec<u8>) -> Result<Base> {
+        let (base, text) = split_prefix(text)?;
+        base.decode_into(text, out)?;
+        Ok(base)
+    }
+
+    /// Return the maximum length of raw-data decoded from multibase `text`.
+    pub fn decoded_len_max(text: &str) -> Result<usize> {
+        let (base, text) = split_prefix(text)?;
+        Ok(base.decoded_len_max(text))
+    }
+
+    /// Re-encode multibase `text` using `base`.
+    pub fn convert(text: &str, base: Base) -> Result<String> {
+        let (from, text) = split_prefix(text)?;
+        let data = from.decode(text)?;
+
+        let mut out = String::with_capacity(Self::encoded_len(base, &data));
+        Self::encode_into(base, &data, &mut out)?;
+        Ok(out)
+    }
+
+    /// Guess the base format of `text` that is encoded without the
+    /// <base-prefix>, like legacy CIDv0 `Qm...` strings or hex digests.
+    /// Return the bases that can decode `text` along with a confidence
+    /// between 0.0 and 1.0, in descending order of confidence. Identity
+    /// base is never detected.
+    pub fn detect_unprefixed(text: &str) -> Vec<(Base, f64)> {
+        detect::detect_unprefixed(text)
+    }
+
+    /// Return the `Base` format type.
+    pub fn to_base(&self) -> Base {
+        self.base
+    }
+
+    /// Return the decoded original binary-data from base-format.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        self.data.clone()
+    }
+
+    /// Same as [Self::to_bytes], without cloning.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        self.data.as_deref()
+    }
+}
+
+fn split_prefix(text: &str) -> Result<(Base, &str)> {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(ch) => Ok((Base::from_code(ch)?, chars.as_str())),
+        None => err_at!(DecodeError, msg: "empty multibase text"),
+    }
+}
+
+#[cfg(test)]
+#[path = "multibase_test.rs"]
+mod multibase_test;
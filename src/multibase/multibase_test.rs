@@ -0,0 +1,394 @@
+use super::*;
+use reqwest::blocking::get;
+
+#[test]
+fn test_base_spec() {
+    let spec = {
+        let uri = "https://raw.githubusercontent.com/multiformats/multibase/master/multibase.csv";
+        get(uri).unwrap().text().unwrap()
+    };
+
+    let spec_lines: Vec<(String, char, String)> = {
+        let mut total_lines: Vec<String> = {
+            let iter = spec.lines().map(|s| s.to_string());
+            iter.collect()
+        };
+        total_lines.remove(0); // remove the column header.
+        total_lines
+            .into_iter()
+            .map(|s| {
+                let mut cols: Vec<&str> = s.split(",").map(|col| col.trim()).collect();
+                cols.pop();
+                match cols.as_slice() {
+                    [n, "0x00", d] => (n.to_string(), '\0', d.to_string()),
+                    [n, ch, d] => {
+                        let ch = ch.chars().next().unwrap();
+                        (n.to_string(), ch, d.to_string())
+                    }
+                    _ => panic!("{:?}", cols),
+                }
+            })
+            .collect()
+    };
+
+    let pkg_lines: Vec<(String, char, String)> = TABLE
+        .to_vec()
+        .into_iter()
+        .map(|(name, ch, descr)| (name.to_string(), ch, descr.to_string()))
+        .collect();
+
+    assert_eq!(
+        spec_lines.len(),
+        pkg_lines.len(),
+        "{} {}",
+        spec_lines.len(),
+        pkg_lines.len()
+    );
+
+    for (x, y) in spec_lines.into_iter().zip(pkg_lines) {
+        assert_eq!(x.0, y.0, "{:?}, {:?}", x, y);
+        assert_eq!(x.1, y.1, "{:?}, {:?}", x, y);
+        assert_eq!(x.2, y.2, "{:?}, {:?}", x, y);
+    }
+}
+
+#[test]
+fn test_base_formats() {
+    use std::str::from_utf8;
+
+    for row in TABLE.iter() {
+        let mb = Multibase::with_char(row.1, "hello world".as_bytes()).unwrap();
+        let out = mb.to_text().unwrap();
+        println!(".... BASE {:?} encoded {}", row.1, out);
+
+        let data = Multibase::from_text(&out).unwrap().to_bytes().unwrap();
+        let text = from_utf8(&data).unwrap();
+        assert_eq!(text, "hello world");
+    }
+}
+
+#[test]
+fn test_bs58_multibase() {
+    use crate::{multicodec, multihash::Multihash};
+
+    let mh = {
+        let data = "hello world".as_bytes();
+        Multihash::new(multicodec::SHA2_256.into(), data).unwrap()
+    };
+    let data = mh.encode().unwrap();
+
+    let mb = Multibase::with_char('z', &data).unwrap();
+    let out1 = mb.to_text().unwrap();
+
+    let out2 = bs58::encode(&data).into_string();
+    let mut out2 = out2.as_bytes().to_vec();
+    out2.insert(0, b' ');
+    let out2 = std::str::from_utf8(&out2).unwrap();
+
+    println!(".... BS58 encoded      {}", out1);
+    println!(".... MULTIBASE encoded {}", out2);
+
+    assert_eq!(&out1.as_bytes()[1..], &out2.as_bytes()[1..])
+}
+
+#[test]
+fn test_base_errors() {
+    use crate::Error;
+    use std::error::Error as _;
+
+    match Multibase::with_char('?', b"hello world") {
+        Err(Error::InvalidBase('?')) => (),
+        res => panic!("{:?}", res),
+    }
+    match Multibase::from_text("?abcd") {
+        Err(Error::InvalidBase('?')) => (),
+        res => panic!("{:?}", res),
+    }
+    match Multibase::from_text("z0OIl") {
+        Err(err @ Error::Base58(_)) => assert!(err.source().is_some()),
+        res => panic!("{:?}", res),
+    }
+}
+
+#[test]
+fn test_base_vectors() {
+    let testcases = [
+        (
+            "0",
+            "01111001011001010111001100100000011011010110000101101110011010010010000000100001",
+        ),
+        ("7", "362625631006654133464440102"),
+        ("9", "573277761329450583662625"),
+        ("f", "796573206d616e692021"),
+        ("F", "796573206D616E692021"),
+        ("v", "f5in683dc5n6i811"),
+        ("V", "F5IN683DC5N6I811"),
+        ("t", "f5in683dc5n6i811"),
+        ("T", "F5IN683DC5N6I811"),
+        ("b", "pfsxgidnmfxgsibb"),
+        ("B", "PFSXGIDNMFXGSIBB"),
+        ("c", "pfsxgidnmfxgsibb"),
+        ("C", "PFSXGIDNMFXGSIBB"),
+        ("h", "xf1zgedpcfzg1ebb"),
+        ("k", "2lcpzo5yikidynfl"),
+        ("K", "2LCPZO5YIKIDYNFL"),
+        ("z", "7paNL19xttacUY"),
+        ("Z", "7Pznk19XTTzBtx"),
+        ("m", "eWVzIG1hbmkgIQ"),
+        ("M", "eWVzIG1hbmkgIQ=="),
+        ("u", "eWVzIG1hbmkgIQ"),
+        ("U", "eWVzIG1hbmkgIQ=="),
+        ("🚀", "🏃✋🌈😅🌷🤤😻🌟😅👏"),
+    ];
+
+    let data = b"yes mani !";
+    for (prefix, text) in testcases.iter() {
+        let text = prefix.to_string() + text;
+        let mb = Multibase::from_text(&text).unwrap();
+        assert_eq!(mb.to_bytes().unwrap(), data.to_vec(), "{}", text);
+
+        let ch = prefix.chars().next().unwrap();
+        let mb = Multibase::with_char(ch, data).unwrap();
+        assert_eq!(mb.to_text().unwrap(), text);
+    }
+
+    // leading zeros
+    let data = b"\x00yes mani !";
+    for text in ["90573277761329450583662625", "k02lcpzo5yikidynfl"].iter() {
+        let mb = Multibase::from_text(text).unwrap();
+        assert_eq!(mb.to_bytes().unwrap(), data.to_vec(), "{}", text);
+        assert_eq!(&mb.to_text().unwrap(), text);
+    }
+
+    // refer RFC-9285
+    let testcases = [
+        ("AB", "BB8"),
+        ("Hello!!", "%69 VD92EX0"),
+        ("base-45", "UJCLQE7W581"),
+    ];
+    for (data, text) in testcases.iter() {
+        let mb = Multibase::with_base(Base::Base45, data.as_bytes()).unwrap();
+        assert_eq!(mb.to_text().unwrap(), format!("R{}", text));
+        let mb = Multibase::from_text(&format!("R{}", text)).unwrap();
+        assert_eq!(mb.to_bytes().unwrap(), data.as_bytes().to_vec());
+    }
+
+    let mb = Multibase::with_base(Base::Proquint, &[127, 0, 0, 1]).unwrap();
+    assert_eq!(mb.to_text().unwrap(), "pro-lusab-babad");
+    let mb = Multibase::from_text("pro-lusab-babad").unwrap();
+    assert_eq!(mb.to_bytes().unwrap(), vec![127, 0, 0, 1]);
+}
+
+#[test]
+fn test_base_registry() {
+    assert_eq!(BASES.len(), TABLE.len());
+    for (base, (name, ch, _)) in BASES.iter().zip(TABLE.iter()) {
+        assert_eq!(base.name(), *name);
+        assert_eq!(base.code(), *ch);
+        assert_eq!(Base::from_code(*ch).unwrap(), *base);
+        assert_eq!(Base::from_name(name).unwrap(), *base);
+    }
+
+    let data: Vec<u8> = (0..=255).chain(0..3).collect();
+    for base in BASES.iter().skip(1) {
+        for n in 0..data.len() {
+            let text = base.encode(&data[..n]).unwrap();
+            assert_eq!(
+                base.decode(&text).unwrap(),
+                data[..n].to_vec(),
+                "{:?}",
+                base
+            );
+        }
+    }
+}
+
+#[test]
+fn test_base_strict() {
+    let testcases = [
+        "bPfsxgidnmfxgsibb",
+        "BpFSXGIDNMFXGSIBB",
+        "cpfsxgidnmfxgsibb===",
+        "cpfsxgidnmfxgsibb=====",
+        "f796573206D616E692021",
+        "k2lcpzo5yikidyNfl",
+        "MeWVzIG1hbmkgIQ",
+        "MeWVzIG1hbmkgIQ=",
+        "meWVzIG1hbmkgIQ==",
+        "meWVzIG1hbmkgIR",
+        "R%69 VD92EX",
+        "RGGW",
+        "pro-lusab-babax",
+        "plusab-babad",
+        "🚀🏃x",
+        "",
+    ];
+    for text in testcases.iter() {
+        assert!(Multibase::from_text(text).is_err(), "{}", text);
+    }
+}
+
+#[test]
+fn test_base_into() {
+    let data: Vec<u8> = (0..=255).chain(0..3).collect();
+
+    let mut text = String::from("prefix:");
+    let mut out = vec![0xAA];
+    for base in BASES.iter().skip(1) {
+        for n in [0, 1, 2, 3, 7, 32, data.len()].iter() {
+            let data = &data[..*n];
+            let m = Multibase::encoded_len(*base, data);
+
+            text.truncate(7);
+            text.reserve(m);
+            let cap = text.capacity();
+            Multibase::encode_into(*base, data, &mut text).unwrap();
+            assert_eq!(text.capacity(), cap, "{:?}", base);
+            assert!(text.len() - 7 <= m, "{:?} {} {}", base, text.len() - 7, m);
+            match base {
+                Base::Base10 | Base::Base36Lower | Base::Base36Upper => (),
+                Base::Base58Btc | Base::Base58Flickr => (),
+                _ => assert_eq!(text.len() - 7, m, "{:?}", base),
+            }
+
+            let mb = Multibase::with_base(*base, data).unwrap();
+            assert_eq!(&text[7..], mb.to_text().unwrap());
+
+            assert!(Multibase::decoded_len_max(&text[7..]).unwrap() >= data.len());
+            out.truncate(1);
+            let b = Multibase::decode_into(&text[7..], &mut out).unwrap();
+            assert_eq!(b, *base);
+            assert_eq!(&out[1..], data, "{:?}", base);
+
+            let mb = Multibase::decode_as(*base, &text[7..]).unwrap();
+            assert_eq!(mb.as_bytes(), Some(data));
+        }
+    }
+
+    // on error, output buffers are left unmodified.
+    let mut out = vec![1, 2, 3];
+    assert!(Multibase::decode_into("mAAAA!", &mut out).is_err());
+    assert!(Multibase::decode_into("z1112O", &mut out).is_err());
+    assert_eq!(out, vec![1, 2, 3]);
+
+    let mut text = String::from("abc");
+    assert!(Multibase::encode_into(Base::Identity, &[0xff], &mut text).is_err());
+    assert_eq!(text, "abc");
+
+    match Multibase::decode_as(Base::Base32Lower, "meWVzIG1hbmkgIQ") {
+        Err(crate::Error::DecodeError(_, _)) => (),
+        res => panic!("{:?}", res),
+    }
+}
+
+#[test]
+fn test_base_stream() {
+    use std::io::{Read, Write};
+
+    let data: Vec<u8> = (0..3000).map(|i| (i % 251) as u8).collect();
+
+    for base in BASES.iter().skip(1) {
+        for chunk in [1, 2, 7, 4096].iter() {
+            let mut w = MultibaseWriter::new(*base, vec![]).unwrap();
+            for part in data.chunks(*chunk) {
+                w.write_all(part).unwrap();
+            }
+            let text = String::from_utf8(w.finish().unwrap()).unwrap();
+            let mb = Multibase::with_base(*base, &data).unwrap();
+            assert_eq!(text, mb.to_text().unwrap(), "{:?} {}", base, chunk);
+
+            let mut r = MultibaseReader::new(text.as_bytes()).unwrap();
+            assert_eq!(r.to_base(), *base);
+            let mut out = vec![];
+            let mut buf = vec![0; *chunk];
+            loop {
+                match r.read(&mut buf).unwrap() {
+                    0 => break,
+                    n => out.extend_from_slice(&buf[..n]),
+                }
+            }
+            assert_eq!(out, data, "{:?} {}", base, chunk);
+        }
+    }
+
+    // identity passes bytes through.
+    let mut w = MultibaseWriter::new(Base::Identity, vec![]).unwrap();
+    w.write_all("hello world".as_bytes()).unwrap();
+    let text = w.finish().unwrap();
+    assert_eq!(text, b"\0hello world".to_vec());
+
+    let mut out = vec![];
+    let mut r = MultibaseReader::new(text.as_slice()).unwrap();
+    r.read_to_end(&mut out).unwrap();
+    assert_eq!(out, b"hello world".to_vec());
+
+    // non-streamable bases are bounded.
+    let mut w = MultibaseWriter::new(Base::Base58Btc, vec![]).unwrap();
+    let big = vec![0xab; BUFFER_LIMIT + 1];
+    let err = w.write_all(&big).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    let text = format!("z{}", "2".repeat(BUFFER_LIMIT + 1));
+    let mut r = MultibaseReader::new(text.as_bytes()).unwrap();
+    let err = r.read_to_end(&mut vec![]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    // invalid text
+    let mut r = MultibaseReader::new("meWVzIG1hbmkgI!".as_bytes()).unwrap();
+    let err = r.read_to_end(&mut vec![]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    match MultibaseReader::new("?abcd".as_bytes()) {
+        Err(crate::Error::InvalidBase('?')) => (),
+        Err(err) => panic!("{}", err),
+        Ok(_) => panic!("expected error"),
+    }
+}
+
+#[test]
+fn test_base_convert() {
+    let text = Multibase::convert("MeWVzIG1hbmkgIQ==", Base::Base58Btc).unwrap();
+    assert_eq!(text, "z7paNL19xttacUY");
+    let text = Multibase::convert(&text, Base::Base256Emoji).unwrap();
+    assert_eq!(text, "🚀🏃✋🌈😅🌷🤤😻🌟😅👏");
+    let text = Multibase::convert(&text, Base::Base16Lower).unwrap();
+    assert_eq!(text, "f796573206d616e692021");
+
+    assert!(Multibase::convert("796573206d616e692021", Base::Base58Btc).is_err());
+    assert!(Multibase::convert("f796573206d616e69202", Base::Base58Btc).is_err());
+}
+
+#[test]
+fn test_base_detect() {
+    // CIDv0
+    let bases = Multibase::detect_unprefixed("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+    assert_eq!(bases[0], (Base::Base58Btc, 1.0));
+    assert!(bases[1..].iter().all(|(_, c)| *c < 1.0), "{:?}", bases);
+
+    // sha2-256 hex digest
+    let text = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+    let bases = Multibase::detect_unprefixed(text);
+    assert_eq!(bases[0], (Base::Base16Lower, 1.0));
+    assert!(bases.iter().all(|(b, _)| *b != Base::Base16Upper));
+
+    let bases = Multibase::detect_unprefixed("2021");
+    assert_eq!(bases[0].0, Base::Base10, "{:?}", bases);
+    assert!(bases.iter().all(|(b, _)| *b != Base::Base8));
+
+    let bases = Multibase::detect_unprefixed("PFSXGIDNMFXGSIBB");
+    assert_eq!(bases[0].0, Base::Base32Upper, "{:?}", bases);
+    assert!(bases.iter().all(|(b, _)| *b != Base::Base32Lower));
+
+    let bases = Multibase::detect_unprefixed("eWVzIG1hbmkgIQ==");
+    assert_eq!(bases.len(), 2, "{:?}", bases);
+    assert!(bases.iter().any(|(b, _)| *b == Base::Base64Pad));
+
+    let bases = Multibase::detect_unprefixed("lusab-babad");
+    assert_eq!(bases, vec![(Base::Proquint, 0.9)]);
+
+    let bases = Multibase::detect_unprefixed("🏃✋🌈");
+    assert_eq!(bases, vec![(Base::Base256Emoji, 1.0)]);
+
+    assert!(Multibase::detect_unprefixed("").is_empty());
+    assert!(Multibase::detect_unprefixed("not valid!").is_empty());
+}
@@ -0,0 +1,94 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{Error, Result};
+
+const CONSONANTS: &[u8; 16] = b"bdfghjklmnprstvz";
+const VOWELS: &[u8; 4] = b"aiou";
+
+// Encoded text starts with "ro-", following the multibase prefix `p`,
+// refer multibase's proquint rfc. Each 16-bit word is encoded as
+// consonant-vowel-consonant-vowel-consonant, and a trailing odd byte as
+// consonant-vowel-consonant, words are separated by '-'.
+pub(crate) fn encode(data: &[u8], text: &mut String) {
+    text.reserve(encoded_len(data.len()));
+    text.push_str("ro-");
+    for (i, chunk) in data.chunks(2).enumerate() {
+        if i > 0 {
+            text.push('-');
+        }
+        match chunk {
+            [a, b] => {
+                let n = (usize::from(*a) << 8) | usize::from(*b);
+                text.push(CONSONANTS[n >> 12] as char);
+                text.push(VOWELS[(n >> 10) & 0x3] as char);
+                text.push(CONSONANTS[(n >> 6) & 0xf] as char);
+                text.push(VOWELS[(n >> 4) & 0x3] as char);
+                text.push(CONSONANTS[n & 0xf] as char);
+            }
+            _ => {
+                let n = usize::from(chunk[0]);
+                text.push(CONSONANTS[n >> 4] as char);
+                text.push(VOWELS[(n >> 2) & 0x3] as char);
+                text.push(CONSONANTS[n & 0x3] as char);
+            }
+        }
+    }
+}
+
+pub(crate) fn decode(text: &str, data: &mut Vec<u8>) -> Result<()> {
+    let text = match text.strip_prefix("ro-") {
+        Some(text) => text,
+        None => err_at!(DecodeError, msg: "proquint must start with `pro-`")?,
+    };
+
+    if text.is_empty() {
+        return Ok(());
+    }
+
+    data.reserve(decoded_len_max(text.len()));
+    let mut words = text.split('-').peekable();
+    while let Some(word) = words.next() {
+        let last = words.peek().is_none();
+        match word.as_bytes() {
+            [c1, v1, c2, v2, c3] => {
+                let n = (consonant(*c1)? << 12)
+                    | (vowel(*v1)? << 10)
+                    | (consonant(*c2)? << 6)
+                    | (vowel(*v2)? << 4)
+                    | consonant(*c3)?;
+                data.extend_from_slice(&[(n >> 8) as u8, n as u8]);
+            }
+            [c1, v1, c2] if last && consonant(*c2)? < 4 => {
+                let n = (consonant(*c1)? << 4) | (vowel(*v1)? << 2) | consonant(*c2)?;
+                data.push(n as u8);
+            }
+            _ => err_at!(DecodeError, msg: "proquint invalid word {:?}", word)?,
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn encoded_len(n: usize) -> usize {
+    match n {
+        0 => 3,
+        n => 3 + (n / 2) * 6 + (n % 2) * 4 - 1,
+    }
+}
+
+pub(crate) fn decoded_len_max(n: usize) -> usize {
+    n / 3
+}
+
+fn consonant(ch: u8) -> Result<usize> {
+    match CONSONANTS.iter().position(|c| *c == ch) {
+        Some(val) => Ok(val),
+        None => err_at!(DecodeError, msg: "proquint invalid consonant {:?}", ch as char),
+    }
+}
+
+fn vowel(ch: u8) -> Result<usize> {
+    match VOWELS.iter().position(|c| *c == ch) {
+        Some(val) => Ok(val),
+        None => err_at!(DecodeError, msg: "proquint invalid vowel {:?}", ch as char),
+    }
+}
@@ -0,0 +1,62 @@
+use data_encoding::Encoding;
+
+// Encodings are strict, they reject characters outside their alphabet,
+// including the other case, invalid padding and non-zero trailing bits.
+
+pub(crate) const BASE2: Encoding = new_encoding! {
+    symbols: "01",
+};
+pub(crate) const BASE8: Encoding = new_encoding! {
+    symbols: "01234567",
+};
+pub(crate) const BASE16_LOWER: Encoding = new_encoding! {
+    symbols: "0123456789abcdef",
+};
+pub(crate) const BASE16_UPPER: Encoding = new_encoding! {
+    symbols: "0123456789ABCDEF",
+};
+pub(crate) const BASE32_HEX_LOWER: Encoding = new_encoding! {
+    symbols: "0123456789abcdefghijklmnopqrstuv",
+};
+pub(crate) const BASE32_HEX_UPPER: Encoding = new_encoding! {
+    symbols: "0123456789ABCDEFGHIJKLMNOPQRSTUV",
+};
+pub(crate) const BASE32_HEX_PAD_LOWER: Encoding = new_encoding! {
+    symbols: "0123456789abcdefghijklmnopqrstuv",
+    padding: '=',
+};
+pub(crate) const BASE32_HEX_PAD_UPPER: Encoding = new_encoding! {
+    symbols: "0123456789ABCDEFGHIJKLMNOPQRSTUV",
+    padding: '=',
+};
+pub(crate) const BASE32_LOWER: Encoding = new_encoding! {
+    symbols: "abcdefghijklmnopqrstuvwxyz234567",
+};
+pub(crate) const BASE32_UPPER: Encoding = new_encoding! {
+    symbols: "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+};
+pub(crate) const BASE32_PAD_LOWER: Encoding = new_encoding! {
+    symbols: "abcdefghijklmnopqrstuvwxyz234567",
+    padding: '=',
+};
+pub(crate) const BASE32_PAD_UPPER: Encoding = new_encoding! {
+    symbols: "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+    padding: '=',
+};
+pub(crate) const BASE32_Z: Encoding = new_encoding! {
+    symbols: "ybndrfg8ejkmcpqxot1uwisza345h769",
+};
+pub(crate) const BASE64: Encoding = new_encoding! {
+    symbols: "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+};
+pub(crate) const BASE64_PAD: Encoding = new_encoding! {
+    symbols: "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+    padding: '=',
+};
+pub(crate) const BASE64_URL: Encoding = new_encoding! {
+    symbols: "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+};
+pub(crate) const BASE64_URL_PAD: Encoding = new_encoding! {
+    symbols: "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+    padding: '=',
+};
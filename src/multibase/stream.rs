@@ -0,0 +1,260 @@
+use std::io::{self, Read, Write};
+
+use crate::{multibase::Base, Error, Result};
+
+/// Maximum number of bytes buffered by [MultibaseWriter] and
+/// [MultibaseReader] for bases that cannot be streamed, that is, base10,
+/// base36, base58 and proquint. Beyond this limit, write and read fail
+/// with [io::ErrorKind::InvalidInput] and [io::ErrorKind::InvalidData]
+/// respectively.
+pub const BUFFER_LIMIT: usize = 1024 * 1024;
+
+// Return the number of (data, text) bytes that can be encoded and decoded
+// independent of subsequent input. Return None for bases that treat the
+// entire input as a single number or need the complete input. Text
+// length for base256emoji varies with the code-point.
+fn block_len(base: Base) -> Option<(usize, usize)> {
+    use Base::*;
+
+    match base {
+        Identity => Some((1, 1)),
+        Base2 => Some((1, 8)),
+        Base8 => Some((3, 8)),
+        Base16Lower | Base16Upper => Some((1, 2)),
+        Base32HexLower | Base32HexUpper | Base32HexPadLower | Base32HexPadUpper => Some((5, 8)),
+        Base32Lower | Base32Upper | Base32PadLower | Base32PadUpper | Base32Z => Some((5, 8)),
+        Base45 => Some((2, 3)),
+        Base64 | Base64Pad | Base64Url | Base64UrlPad => Some((3, 4)),
+        Base256Emoji => Some((1, 0)),
+        Base10 | Base36Lower | Base36Upper | Base58Btc | Base58Flickr | Proquint => None,
+    }
+}
+
+/// Type implement [io::Write] to encode a stream of bytes into multibase
+/// text, writing the text into the underlying writer.
+///
+/// The base-prefix is written on construction and input is encoded in
+/// blocks as it is written. Caller must call [MultibaseWriter::finish] to
+/// encode the trailing partial block, if any. Bases that cannot be
+/// streamed are buffered, up to [BUFFER_LIMIT] bytes, and encoded on
+/// finish.
+pub struct MultibaseWriter<W: Write> {
+    base: Base,
+    inner: W,
+    data: Vec<u8>,
+    text: String,
+}
+
+impl<W: Write> MultibaseWriter<W> {
+    /// Create a new writer encoding in `base` format into `inner`.
+    pub fn new(base: Base, mut inner: W) -> Result<MultibaseWriter<W>> {
+        let mut prefix = [0; 4];
+        let prefix = base.code().encode_utf8(&mut prefix);
+        err_at!(IOError, inner.write_all(prefix.as_bytes()))?;
+
+        let val = MultibaseWriter {
+            base,
+            inner,
+            data: Vec::default(),
+            text: String::default(),
+        };
+        Ok(val)
+    }
+
+    /// Return the base format used by this writer.
+    pub fn to_base(&self) -> Base {
+        self.base
+    }
+
+    /// Encode remaining input, flush and return the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        let data = std::mem::take(&mut self.data);
+        self.encode(&data)?;
+        err_at!(IOError, self.inner.flush())?;
+        Ok(self.inner)
+    }
+
+    fn encode(&mut self, data: &[u8]) -> Result<()> {
+        self.text.clear();
+        match self.base {
+            Base::Identity => err_at!(IOError, self.inner.write_all(data))?,
+            base => {
+                base.encode_into(data, &mut self.text)?;
+                err_at!(IOError, self.inner.write_all(self.text.as_bytes()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for MultibaseWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = match block_len(self.base) {
+            Some((n, _)) if self.data.is_empty() => {
+                let m = (buf.len() / n) * n;
+                self.encode(&buf[..m]).map_err(to_io_error)?;
+                self.data.extend_from_slice(&buf[m..]);
+                return Ok(buf.len());
+            }
+            Some((n, _)) => n,
+            None if (self.data.len() + buf.len()) > BUFFER_LIMIT => {
+                let msg = format!("{} cannot buffer beyond {}", self.base.name(), BUFFER_LIMIT);
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+            }
+            None => {
+                self.data.extend_from_slice(buf);
+                return Ok(buf.len());
+            }
+        };
+
+        // complete the partial block from previous write.
+        let k = std::cmp::min(n - self.data.len(), buf.len());
+        self.data.extend_from_slice(&buf[..k]);
+        if self.data.len() == n {
+            let data = std::mem::take(&mut self.data);
+            self.encode(&data).map_err(to_io_error)?;
+            self.write_all(&buf[k..])?;
+        }
+        Ok(buf.len())
+    }
+
+    /// Flush the underlying writer, partial block, if any, is encoded
+    /// only on [MultibaseWriter::finish].
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Type implement [io::Read] to decode multibase text, read from the
+/// underlying reader, into a stream of bytes.
+///
+/// The base-prefix is read on construction and text is decoded in blocks
+/// as it is read. Bases that cannot be streamed are buffered, up to
+/// [BUFFER_LIMIT] bytes, and decoded once the underlying reader reaches
+/// end-of-file.
+pub struct MultibaseReader<R: Read> {
+    base: Base,
+    inner: R,
+    text: Vec<u8>,
+    data: Vec<u8>,
+    off: usize,
+    eof: bool,
+}
+
+impl<R: Read> MultibaseReader<R> {
+    /// Create a new reader, detect the base format from the base-prefix
+    /// read from `inner`.
+    pub fn new(mut inner: R) -> Result<MultibaseReader<R>> {
+        let mut prefix = [0; 4];
+        err_at!(IOError, inner.read_exact(&mut prefix[..1]))?;
+        // base-prefix is a single utf8 encoded char.
+        let n = match prefix[0] {
+            b if b < 0x80 => 1,
+            b if b >= 0xf0 => 4,
+            b if b >= 0xe0 => 3,
+            _ => 2,
+        };
+        err_at!(IOError, inner.read_exact(&mut prefix[1..n]))?;
+        let base = match err_at!(DecodeError, std::str::from_utf8(&prefix[..n]))?
+            .chars()
+            .next()
+        {
+            Some(ch) => Base::from_code(ch)?,
+            None => err_at!(DecodeError, msg: "empty multibase prefix")?,
+        };
+
+        let val = MultibaseReader {
+            base,
+            inner,
+            text: Vec::default(),
+            data: Vec::default(),
+            off: 0,
+            eof: false,
+        };
+        Ok(val)
+    }
+
+    /// Return the base format detected from base-prefix.
+    pub fn to_base(&self) -> Base {
+        self.base
+    }
+
+    /// Return the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    // Return the number of bytes, from the front of `self.text`, that can
+    // be decoded.
+    fn decodable_len(&self) -> io::Result<usize> {
+        let n = self.text.len();
+        let m = match block_len(self.base) {
+            _ if self.eof => n,
+            Some((_, 0)) => match std::str::from_utf8(&self.text) {
+                Ok(_) => n,
+                Err(err) if err.error_len().is_none() => err.valid_up_to(),
+                Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+            },
+            Some((_, m)) => (n / m) * m,
+            None if n > BUFFER_LIMIT => {
+                let msg = format!("{} cannot buffer beyond {}", self.base.name(), BUFFER_LIMIT);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+            }
+            None => 0,
+        };
+        Ok(m)
+    }
+
+    fn decode(&mut self) -> io::Result<()> {
+        let m = self.decodable_len()?;
+        if m == 0 && !self.eof {
+            return Ok(());
+        }
+
+        self.data.clear();
+        self.off = 0;
+        match self.base {
+            Base::Identity => self.data.extend_from_slice(&self.text[..m]),
+            base => match std::str::from_utf8(&self.text[..m]) {
+                Ok(text) => base
+                    .decode_into(text, &mut self.data)
+                    .map_err(to_io_error)?,
+                Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+            },
+        }
+        self.text.drain(..m);
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for MultibaseReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut scratch = [0; 4096];
+        loop {
+            if self.off < self.data.len() {
+                let n = std::cmp::min(buf.len(), self.data.len() - self.off);
+                buf[..n].copy_from_slice(&self.data[self.off..self.off + n]);
+                self.off += n;
+                return Ok(n);
+            } else if self.eof {
+                return Ok(0);
+            }
+
+            match self.inner.read(&mut scratch) {
+                Ok(0) => self.eof = true,
+                Ok(n) => self.text.extend_from_slice(&scratch[..n]),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+            self.decode()?;
+        }
+    }
+}
+
+fn to_io_error(err: Error) -> io::Error {
+    let kind = match &err {
+        Error::IOError(_, _) => io::ErrorKind::Other,
+        _ => io::ErrorKind::InvalidData,
+    };
+    io::Error::new(kind, err.to_string())
+}
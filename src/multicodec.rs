@@ -7,9 +7,11 @@
 //! [multicodec]: https://github.com/multiformats/multicodec
 //! [TABLE]: https://github.com/multiformats/multicodec/blob/master/table.csv
 
+#[cfg(feature = "std")]
 use lazy_static::lazy_static;
 
-use std::{fmt, result};
+use alloc::{string::String, vec::Vec};
+use core::{fmt, result};
 
 use crate::{Error, Result};
 
@@ -17,7 +19,7 @@ use crate::{Error, Result};
 ///
 /// [multicodec]: https://github.com/multiformats/multicodec
 /// [unsigned-varint]: https://github.com/multiformats/unsigned-varint
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Multicodec {
     code: u128,
 }
@@ -58,7 +60,7 @@ impl Multicodec {
     ///
     /// Return [Error] if `buf's` content can't be recognised.
     pub fn decode(buf: &[u8]) -> Result<(Multicodec, &[u8])> {
-        let (code, rem) = err_at!(Invalid, unsigned_varint::decode::u128(buf))?;
+        let (code, rem) = unsigned_varint::decode::u128(buf)?;
         Ok((Multicodec { code }, rem))
     }
 
@@ -109,14 +111,47 @@ macro_rules! code_points {
             }
         }
 
+        impl Multicodec {
+            /// Create a Multicodec from its name in the default [TABLE].
+            pub fn from_name(name: &str) -> Result<Multicodec> {
+                match name {
+                    $( $name => Ok($code.into()), )*
+                    _ => err_at!(BadInput, msg: "unknown codec name {:?}", name),
+                }
+            }
+
+            /// Return the name of this codec in the default [TABLE], None
+            /// if the code is not in the table.
+            pub fn to_name(&self) -> Option<&'static str> {
+                match self.code {
+                    $( $code => Some($name), )*
+                    _ => None,
+                }
+            }
+        }
+
+        /// Return a list of code-points tagged as "multihash".
+        pub fn multihash_codes() -> Vec<u128> {
+            let mut codes = Vec::default();
+            $(
+                if $tag == "multihash" {
+                    codes.push($code)
+                }
+            )*
+            codes
+        }
+
+        #[cfg(feature = "std")]
         lazy_static! {
             /// Default codec table. Refer [table] for details.
             ///
             /// Constructed using lazy_static!() macro, use this as
-            /// `Vec<Codepoint>`.
+            /// `Vec<Codepoint>`. Available only with `std` feature.
             ///
             /// [table]: https://github.com/multiformats/multicodec/blob/master/table.csv
             pub static ref TABLE: Vec<Codepoint> = {
+                use alloc::string::ToString;
+
                 vec![
                     $(Codepoint {
                         code: $code,
@@ -125,23 +160,6 @@ macro_rules! code_points {
                     },)*
                 ]
             };
-
-            // Pre-sorted table of multihash code values, only codes tagged as
-            // "multihash" will be gathered in this table.
-            static ref TABLE_MULTIHASH: Vec<Codepoint> = {
-                let mut codes = Vec::default();
-                $(
-                    match $tag {
-                        "multihash" => codes.push(Codepoint {
-                            code: $code,
-                            name: $name.to_string(),
-                            tag: $tag.to_string()
-                        }),
-                        _ => ()
-                    };
-                )*
-                codes
-            };
         }
     );
 }
@@ -207,6 +225,8 @@ code_points![
     (MULTIADDR, 0x32, "multiaddr", "multiformat"),
     /// _multiformat_, multibase specification from [multiformats][http://multiformats.io]
     (MULTIBASE, 0x33, "multibase", "multiformat"),
+    /// _multiformat_, varsig specification from [multiformats][http://multiformats.io]
+    (VARSIG, 0x34, "varsig", "multiformat"),
     /// _multiaddr_, Domain name system
     (DNS, 0x35, "dns", "multiaddr"),
     /// _multiaddr_
@@ -381,8 +401,19 @@ code_points![
     (JSON, 0x0200, "json", "serialization"),
     /// _serialization_
     (MESSAGEPACK, 0x0201, "messagepack", "serialization"),
+    /// _serialization_, Content Addressable aRchive (CAR)
+    (CAR, 0x0202, "car", "serialization"),
     /// _libp2p_
     (LIBP2P_PEER_RECORD, 0x0301, "libp2p-peer-record", "libp2p"),
+    /// _serialization_, CARv2 IndexSorted index format
+    (CAR_INDEX_SORTED, 0x0400, "car-index-sorted", "serialization"),
+    /// _serialization_, CARv2 MultihashIndexSorted index format
+    (
+        CAR_MULTIHASH_INDEX_SORTED,
+        0x0401,
+        "car-multihash-index-sorted",
+        "serialization"
+    ),
     /// _multihash_
     (
         SHA2_256_TRUNC254_PADDED,
@@ -1105,15 +1136,6 @@ code_points![
     (HOLOCHAIN_SIG_V1, 0xa37124, "holochain-sig-v1", "holochain"),
 ];
 
-/// Return a list of code-points tagged as "multihash".
-pub fn multihash_codes() -> Vec<u128> {
-    TABLE_MULTIHASH
-        .clone()
-        .into_iter()
-        .map(|cp| cp.code)
-        .collect()
-}
-
 #[cfg(test)]
 #[path = "multicodec_test.rs"]
 mod multicodec_test;
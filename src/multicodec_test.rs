@@ -27,7 +27,7 @@ fn test_multicodec_spec() {
                 if row[2] == "0x00" {
                     row[2] = "0".to_string();
                 }
-                let code: u32 = parse_int::parse(&row[2]).expect(&format!("{}", row[2]));
+                let code: u32 = parse_int::parse(&row[2]).unwrap_or_else(|_| panic!("{}", row[2]));
                 vec![row.remove(0), format!("0x{:x}", code), row.remove(0)] // re-order colums
             })
             .collect();
@@ -43,9 +43,9 @@ fn test_multicodec_spec() {
         total_lines
     };
 
-    let pkg_lines: Vec<Vec<String>> = (&TABLE)
-        .to_vec()
-        .into_iter()
+    let pkg_lines: Vec<Vec<String>> = TABLE
+        .iter()
+        .cloned()
         .map(|cp| vec![cp.name, format!("0x{:x}", cp.code), cp.tag])
         .collect();
 
@@ -58,7 +58,7 @@ fn test_multicodec_spec() {
         pkg_lines.len()
     );
 
-    for (x, y) in spec_lines.into_iter().zip(pkg_lines.into_iter()) {
+    for (x, y) in spec_lines.into_iter().zip(pkg_lines) {
         println!("{:?} {:?}", x, y);
         assert_eq!(x[0], y[0], "{:?}, {:?}", x, y);
         assert_eq!(x[1], y[1], "{:?}, {:?}", x, y);
@@ -75,6 +75,22 @@ fn test_codec() {
 
         let (res_code, res_buf) = Multicodec::decode(&buf).unwrap();
         assert_eq!(res_code, code, "{:?}", code);
-        assert_eq!(res_buf, vec![].as_slice(), "{:?}", code);
+        assert_eq!(res_buf, Vec::<u8>::new().as_slice(), "{:?}", code);
+    }
+}
+
+#[test]
+fn test_codec_errors() {
+    use std::error::Error as _;
+
+    match Multicodec::decode(&[0xff; 20]) {
+        Err(Error::VarintOverflow) => (),
+        res => panic!("{:?}", res),
+    }
+    match Multicodec::decode(&[0x80]) {
+        Err(err @ Error::Varint(unsigned_varint::decode::Error::Insufficient)) => {
+            assert!(err.source().is_some())
+        }
+        res => panic!("{:?}", res),
     }
 }
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use crate::{multicodec, Error, Result};
 
 #[derive(Clone)]
@@ -17,11 +17,14 @@ mod sha1;
 mod sha2;
 mod sha3;
 
-use std::{fmt, io, result};
+use alloc::vec::Vec;
+use core::{fmt, result};
+#[cfg(feature = "std")]
+use std::io;
 
 use crate::multihash::{
     blake2b::Blake2b, blake2s::Blake2s, blake3::Blake3, identity::Identity, md4::Md4, md5::Md5,
-    ripemd::RipeMd, sha1::Sha1, sha2::Sha2, sha3::Sha3
+    ripemd::RipeMd, sha1::Sha1, sha2::Sha2, sha3::Sha3,
 };
 
 use crate::{
@@ -37,6 +40,7 @@ pub struct Multihash {
     inner: Inner,
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Eq, PartialEq)]
 enum Inner {
     Binary(Vec<u8>),
@@ -54,37 +58,33 @@ enum Inner {
 
 impl fmt::Display for Multihash {
     fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
-        use multibase::Base::Base16Lower;
-        use std::iter::FromIterator;
+        use crate::multibase::Base::Base16Lower;
         use Inner::*;
 
         fn get_parts(inner: &Inner) -> Option<(Multicodec, Vec<u8>)> {
             let (codec, digest) = match inner {
-                Identity(c, h) => (c.clone(), h.as_digest().ok()?.to_vec()),
-                Sha1(c, h) => (c.clone(), h.as_digest().ok()?.to_vec()),
-                Sha2(c, h) => (c.clone(), h.as_digest().ok()?.to_vec()),
-                Sha3(c, h) => (c.clone(), h.as_digest().ok()?.to_vec()),
-                Blake2b(c, h) => (c.clone(), h.as_digest().ok()?.to_vec()),
-                Blake2s(c, h) => (c.clone(), h.as_digest().ok()?.to_vec()),
-                Blake3(c, h) => (c.clone(), h.as_digest().ok()?.to_vec()),
-                Md4(c, h) => (c.clone(), h.as_digest().ok()?.to_vec()),
-                Md5(c, h) => (c.clone(), h.as_digest().ok()?.to_vec()),
-                RipeMd(c, h) => (c.clone(), h.as_digest().ok()?.to_vec()),
-                Binary(data) => get_parts(&Multihash::decode(&data).ok()?.0.inner)?,
+                Identity(c, h) => (*c, h.as_digest().ok()?.to_vec()),
+                Sha1(c, h) => (*c, h.as_digest().ok()?.to_vec()),
+                Sha2(c, h) => (*c, h.as_digest().ok()?.to_vec()),
+                Sha3(c, h) => (*c, h.as_digest().ok()?.to_vec()),
+                Blake2b(c, h) => (*c, h.as_digest().ok()?.to_vec()),
+                Blake2s(c, h) => (*c, h.as_digest().ok()?.to_vec()),
+                Blake3(c, h) => (*c, h.as_digest().ok()?.to_vec()),
+                Md4(c, h) => (*c, h.as_digest().ok()?.to_vec()),
+                Md5(c, h) => (*c, h.as_digest().ok()?.to_vec()),
+                RipeMd(c, h) => (*c, h.as_digest().ok()?.to_vec()),
+                Binary(data) => get_parts(&Multihash::decode(data).ok()?.0.inner)?,
             };
 
             Some((codec, digest))
-        };
+        }
 
         // human readable repr
         // refer: https://github.com/multiformats/cid/blob/master/README.md#human-readable-cids
 
         match get_parts(&self.inner) {
             Some((codec, digest)) => {
-                let text = multibase::encode(Base16Lower, &digest);
-                let chars = text.chars();
-                // first char is base-prefix
-                let text = String::from_iter(chars.skip(1));
+                let text = Base16Lower.encode(&digest).map_err(|_| fmt::Error)?;
                 write!(f, "{}-{}-{}", codec, digest.len() * 8, text)
             }
             None => write!(f, "xxx-xxx-xxx..."),
@@ -92,6 +92,12 @@ impl fmt::Display for Multihash {
     }
 }
 
+impl fmt::Debug for Multihash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "Multihash<{}>", self)
+    }
+}
+
 impl From<Inner> for Multihash {
     fn from(inner: Inner) -> Multihash {
         Multihash { inner }
@@ -152,7 +158,7 @@ impl Multihash {
             // multicodec::X11 => unimplemented!(),
             // multicodec::BMT => unimplemented!(),
             // multicodec::SHA2_256_TRUNC254_PADDED => unimplemented!(),
-            codec => err_at!(NotImplemented, msg: "codec {}", codec)?,
+            code => Err(Error::UnknownCodec(code))?,
         };
 
         let mut mh: Multihash = inner.into();
@@ -161,9 +167,18 @@ impl Multihash {
         Ok(mh)
     }
 
-    /// New multihash from digest and multihash-type.
+    /// New multihash from digest and multihash-type. Digest can be
+    /// truncated but not longer than the output of the hash algorithm.
     pub fn from_digest(codec: Multicodec, digest: &[u8]) -> Result<Multihash> {
         let code = codec.to_code();
+        match digest_size(code) {
+            Some(expected) if digest.len() > expected => Err(Error::DigestLengthMismatch {
+                expected,
+                got: digest.len(),
+            })?,
+            _ => (),
+        }
+
         let inner = match code {
             multicodec::IDENTITY => {
                 let hasher = Identity::decode(code, digest)?;
@@ -205,7 +220,7 @@ impl Multihash {
                 let hasher = RipeMd::decode(code, digest)?;
                 Inner::RipeMd(codec, hasher)
             }
-            codec => err_at!(NotImplemented, msg: "codec {}", codec)?,
+            code => Err(Error::UnknownCodec(code))?,
         };
 
         Ok(inner.into())
@@ -220,9 +235,8 @@ impl Multihash {
     /// Lazy parse. Typically called after creating this instance using
     /// [Self::decode_lazy] constructor.
     pub fn parse(&mut self) -> Result<()> {
-        match &self.inner {
-            Inner::Binary(data) => *self = Self::decode(data)?.0,
-            _ => (),
+        if let Inner::Binary(data) = &self.inner {
+            *self = Self::decode(data)?.0
         }
         Ok(())
     }
@@ -249,13 +263,10 @@ impl Multihash {
 
         let (codec, digest, rem) = {
             let (codec, rem) = Multicodec::decode(buf)?;
-            let (n, rem) = err_at!(BadInput, decode::usize(rem))?;
-            if n <= rem.len() {
-                Ok((codec, &rem[..n], &rem[n..]))
-            } else {
-                err_at!(BadInput, msg: "hash-len {}", n)
-            }
-        }?;
+            let (n, rem) = decode::usize(rem)?;
+            let (digest, rem) = read_slice!(rem, n, "multihash")?;
+            (codec, digest, rem)
+        };
 
         let mh = Self::from_digest(codec, digest)?;
         Ok((mh, rem))
@@ -280,10 +291,7 @@ impl Multihash {
 
     // Similar to encode() but avoid allocation by using supplied buffer
     // `buf`.
-    fn encode_with<W>(&self, buf: &mut W) -> Result<usize>
-    where
-        W: io::Write,
-    {
+    fn encode_with(&self, buf: &mut Vec<u8>) -> Result<usize> {
         use unsigned_varint::encode;
 
         let digest = match &self.inner {
@@ -301,20 +309,16 @@ impl Multihash {
         };
         let n = {
             let out = self.to_codec()?.encode()?;
-            err_at!(IOError, buf.write(&out))?;
+            buf.extend_from_slice(&out);
             out.len()
         };
         let m = {
-            #[cfg(not(target_arch = "wasm32"))]
-            let mut scratch: [u8; 10] = Default::default();
-            #[cfg(target_arch = "wasm32")]
-            let mut scratch: [u8; 5] = Default::default();
-
+            let mut scratch = encode::usize_buffer();
             let slice = encode::usize(digest.len(), &mut scratch);
-            err_at!(IOError, buf.write(slice))?;
+            buf.extend_from_slice(slice);
             slice.len()
         };
-        err_at!(IOError, buf.write(digest))?;
+        buf.extend_from_slice(digest);
         Ok(n + m + digest.len())
     }
 
@@ -392,16 +396,16 @@ impl Multihash {
     /// Return the multihash codec.
     pub fn to_codec(&self) -> Result<Multicodec> {
         match &self.inner {
-            Inner::Identity(codec, _) => Ok(codec.clone()),
-            Inner::Sha1(codec, _) => Ok(codec.clone()),
-            Inner::Sha2(codec, _) => Ok(codec.clone()),
-            Inner::Sha3(codec, _) => Ok(codec.clone()),
-            Inner::Blake3(codec, _) => Ok(codec.clone()),
-            Inner::Blake2b(codec, _) => Ok(codec.clone()),
-            Inner::Blake2s(codec, _) => Ok(codec.clone()),
-            Inner::Md4(codec, _) => Ok(codec.clone()),
-            Inner::Md5(codec, _) => Ok(codec.clone()),
-            Inner::RipeMd(codec, _) => Ok(codec.clone()),
+            Inner::Identity(codec, _) => Ok(*codec),
+            Inner::Sha1(codec, _) => Ok(*codec),
+            Inner::Sha2(codec, _) => Ok(*codec),
+            Inner::Sha3(codec, _) => Ok(*codec),
+            Inner::Blake3(codec, _) => Ok(*codec),
+            Inner::Blake2b(codec, _) => Ok(*codec),
+            Inner::Blake2s(codec, _) => Ok(*codec),
+            Inner::Md4(codec, _) => Ok(*codec),
+            Inner::Md5(codec, _) => Ok(*codec),
+            Inner::RipeMd(codec, _) => Ok(*codec),
             Inner::Binary(data) => Self::decode(data)?.0.to_codec(),
         }
     }
@@ -429,21 +433,45 @@ impl Multihash {
     /// is not generated or decoded.
     pub fn unwrap(self) -> Result<(Multicodec, Vec<u8>)> {
         match &self.inner {
-            Inner::Identity(c, h) => Ok((c.clone(), h.as_digest()?.to_vec())),
-            Inner::Sha1(c, h) => Ok((c.clone(), h.as_digest()?.to_vec())),
-            Inner::Sha2(c, h) => Ok((c.clone(), h.as_digest()?.to_vec())),
-            Inner::Sha3(c, h) => Ok((c.clone(), h.as_digest()?.to_vec())),
-            Inner::Blake3(c, h) => Ok((c.clone(), h.as_digest()?.to_vec())),
-            Inner::Blake2b(c, h) => Ok((c.clone(), h.as_digest()?.to_vec())),
-            Inner::Blake2s(c, h) => Ok((c.clone(), h.as_digest()?.to_vec())),
-            Inner::Md4(c, h) => Ok((c.clone(), h.as_digest()?.to_vec())),
-            Inner::Md5(c, h) => Ok((c.clone(), h.as_digest()?.to_vec())),
-            Inner::RipeMd(c, h) => Ok((c.clone(), h.as_digest()?.to_vec())),
+            Inner::Identity(c, h) => Ok((*c, h.as_digest()?.to_vec())),
+            Inner::Sha1(c, h) => Ok((*c, h.as_digest()?.to_vec())),
+            Inner::Sha2(c, h) => Ok((*c, h.as_digest()?.to_vec())),
+            Inner::Sha3(c, h) => Ok((*c, h.as_digest()?.to_vec())),
+            Inner::Blake3(c, h) => Ok((*c, h.as_digest()?.to_vec())),
+            Inner::Blake2b(c, h) => Ok((*c, h.as_digest()?.to_vec())),
+            Inner::Blake2s(c, h) => Ok((*c, h.as_digest()?.to_vec())),
+            Inner::Md4(c, h) => Ok((*c, h.as_digest()?.to_vec())),
+            Inner::Md5(c, h) => Ok((*c, h.as_digest()?.to_vec())),
+            Inner::RipeMd(c, h) => Ok((*c, h.as_digest()?.to_vec())),
             Inner::Binary(data) => Self::decode(data)?.0.unwrap(),
         }
     }
 }
 
+// Output length, in bytes, of the hash algorithm identified by `code`.
+// Return None for algorithms with variable length output.
+fn digest_size(code: u128) -> Option<usize> {
+    let n = match code {
+        multicodec::SHA1 => 20,
+        multicodec::SHA2_256 | multicodec::DBL_SHA2_256 => 32,
+        multicodec::SHA2_512 => 64,
+        multicodec::SHA3_224 | multicodec::KECCAK_224 => 28,
+        multicodec::SHA3_256 | multicodec::KECCAK_256 => 32,
+        multicodec::SHA3_384 | multicodec::KECCAK_384 => 48,
+        multicodec::SHA3_512 | multicodec::KECCAK_512 => 64,
+        multicodec::BLAKE2B_8..=multicodec::BLAKE2B_512 => (code - 0xb200) as usize,
+        multicodec::BLAKE2S_8..=multicodec::BLAKE2S_256 => (code - 0xb240) as usize,
+        multicodec::MD4 | multicodec::MD5 => 16,
+        multicodec::RIPEMD_128 => 16,
+        multicodec::RIPEMD_160 => 20,
+        multicodec::RIPEMD_256 => 32,
+        multicodec::RIPEMD_320 => 40,
+        _ => return None,
+    };
+    Some(n)
+}
+
+#[cfg(feature = "std")]
 impl io::Write for Multihash {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.write(buf)
@@ -1,5 +1,5 @@
 use super::*;
-use multibase::Base;
+use crate::multibase::Base;
 
 #[test]
 fn test_sha1() {
@@ -69,3 +69,58 @@ fn test_multihash_pretty() {
         "sha2-256-256-b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9".to_string(),
     );
 }
+
+#[test]
+fn test_shake() {
+    use crate::multibase;
+
+    let mh = Multihash::new(multicodec::SHAKE_128.into(), &[]).unwrap();
+    let data = mh.to_digest().unwrap();
+    let mb = multibase::Multibase::with_base(Base::Base16Lower, &data).unwrap();
+    assert_eq!(
+        mb.to_text().unwrap(),
+        "f7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"
+    );
+
+    let mh = Multihash::new(multicodec::SHAKE_256.into(), &[]).unwrap();
+    let data = mh.encode().unwrap();
+    assert_eq!(&data[..3], &[0x19, 0x40, 0x46]);
+    assert_eq!(data.len(), 66);
+    assert_eq!(Multihash::decode(&data).unwrap().0, mh);
+}
+
+#[test]
+fn test_multihash_errors() {
+    let data = Multihash::new(multicodec::SHA2_256.into(), b"hello world")
+        .unwrap()
+        .encode()
+        .unwrap();
+
+    match Multihash::decode(&data[..20]) {
+        Err(Error::InsufficientBytes { protocol, needed }) => {
+            assert_eq!(protocol, "multihash");
+            assert_eq!(needed, 32);
+        }
+        res => panic!("{:?}", res),
+    }
+
+    // truncated digests are fine, longer ones are not.
+    Multihash::from_digest(multicodec::SHA2_256.into(), &[1; 20]).unwrap();
+    match Multihash::from_digest(multicodec::SHA2_256.into(), &[1; 33]) {
+        Err(Error::DigestLengthMismatch { expected, got }) => assert_eq!((expected, got), (32, 33)),
+        res => panic!("{:?}", res),
+    }
+    match Multihash::from_digest(multicodec::BLAKE2B_256.into(), &[1; 33]) {
+        Err(Error::DigestLengthMismatch { expected, got }) => assert_eq!((expected, got), (32, 33)),
+        res => panic!("{:?}", res),
+    }
+
+    match Multihash::new(multicodec::SM3_256.into(), b"hello world") {
+        Err(Error::UnknownCodec(multicodec::SM3_256)) => (),
+        res => panic!("{:?}", res),
+    }
+    match Multihash::decode(&[0x12]) {
+        Err(Error::Varint(_)) => (),
+        res => panic!("{:?}", res),
+    }
+}
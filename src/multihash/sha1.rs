@@ -1,5 +1,7 @@
 use digest::Digest;
 
+use alloc::vec::Vec;
+
 use crate::{Error, Result};
 
 #[derive(Clone)]
@@ -1,9 +1,13 @@
 use digest::Digest;
 
-use std::io::Read;
+use alloc::vec::Vec;
 
 use crate::{multicodec, Error, Result};
 
+// Default output length, in bytes, for variable length shake digests.
+const SHAKE_128_LEN: usize = 32;
+const SHAKE_256_LEN: usize = 64;
+
 #[derive(Clone)]
 pub(crate) enum Sha3 {
     Sha3_224 {
@@ -234,7 +238,7 @@ impl Sha3 {
     }
 
     pub(crate) fn finish(&mut self) -> Result<()> {
-        use digest::ExtendableOutputReset;
+        use digest::{ExtendableOutputReset, XofReader};
 
         match self {
             Sha3::Sha3_224 {
@@ -265,18 +269,16 @@ impl Sha3 {
                 hasher,
                 digest: digest @ None,
             } => {
-                let mut buf = Vec::default();
-                let mut xof = hasher.finalize_xof_reset();
-                err_at!(IOError, xof.read_to_end(&mut buf))?;
+                let mut buf = vec![0; SHAKE_128_LEN];
+                hasher.finalize_xof_reset().read(&mut buf);
                 *digest = Some(buf);
             }
             Sha3::Shake256 {
                 hasher,
                 digest: digest @ None,
             } => {
-                let mut buf = Vec::default();
-                let mut xof = hasher.finalize_xof_reset();
-                err_at!(IOError, xof.read_to_end(&mut buf))?;
+                let mut buf = vec![0; SHAKE_256_LEN];
+                hasher.finalize_xof_reset().read(&mut buf);
                 *digest = Some(buf)
             }
             Sha3::Keccak224 {
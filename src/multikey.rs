@@ -0,0 +1,153 @@
+//! Module implement Multikey. _Refer [multikey] spec for detail_.
+//!
+//! A multikey is raw key bytes prefixed with the unsigned-varint of its
+//! `key` tagged multicodec, `<codec><key-bytes>`. Its text form is the
+//! base58btc multibase of the binary form, for example `z6Mk...` for
+//! Ed25519 public keys, as used by W3C Multikey and `did:key`.
+//!
+//! Public keys on elliptic curves are held in their compressed form.
+//!
+//! [multikey]: https://www.w3.org/TR/cid-1.0/#Multikey
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, result, str::FromStr};
+
+use crate::{
+    multibase::{Base, Multibase},
+    multicodec::{self, Multicodec},
+    Error, Result,
+};
+
+/// Type implement multicodec prefixed public and private keys.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct Multikey {
+    codec: Multicodec,
+    data: Vec<u8>,
+}
+
+// Private keys are redacted to their codec name, use to_text() to
+// export them.
+impl fmt::Display for Multikey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        if self.is_private() {
+            return write!(f, "{}", self.codec);
+        }
+        match self.to_text(Base::Base58Btc) {
+            Ok(text) => write!(f, "{}", text),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl fmt::Debug for Multikey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        match self.is_private() {
+            true => write!(f, "Multikey<{}>", self.codec),
+            false => write!(f, "Multikey<{}>", self),
+        }
+    }
+}
+
+impl FromStr for Multikey {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Multikey> {
+        Multikey::from_text(text)
+    }
+}
+
+impl Multikey {
+    /// Create a multikey from raw key bytes `data`, `codec` shall be one of
+    /// the `key` tagged codes listed by [key_len].
+    pub fn new(codec: Multicodec, data: &[u8]) -> Result<Multikey> {
+        let code = codec.to_code();
+        match key_len(code) {
+            Some(n) if n == data.len() => Ok(Multikey {
+                codec,
+                data: data.to_vec(),
+            }),
+            Some(n) => {
+                err_at!(BadInput, msg: "{} key length {}, expected {}", codec, data.len(), n)
+            }
+            None => Err(Error::UnknownCodec(code)),
+        }
+    }
+
+    /// Decode multikey from its binary form and return the remaining
+    /// unparsed slice.
+    pub fn decode(buf: &[u8]) -> Result<(Multikey, &[u8])> {
+        let (codec, rem) = Multicodec::decode(buf)?;
+        let n = match key_len(codec.to_code()) {
+            Some(n) => n,
+            None => Err(Error::UnknownCodec(codec.to_code()))?,
+        };
+        let (data, rem) = read_slice!(rem, n, "multikey")?;
+        Ok((Multikey::new(codec, data)?, rem))
+    }
+
+    /// Encode multikey into its binary form, `<codec><key-bytes>`.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut data = self.codec.encode()?;
+        data.extend_from_slice(&self.data);
+        Ok(data)
+    }
+
+    /// Parse multikey from multibase encoded text.
+    pub fn from_text(text: &str) -> Result<Multikey> {
+        let data = match Multibase::from_text(text)?.to_bytes() {
+            Some(data) => data,
+            None => err_at!(DecodeError, msg: "empty multikey")?,
+        };
+        match Multikey::decode(&data)? {
+            (key, []) => Ok(key),
+            (_, rem) => err_at!(DecodeError, msg: "multikey trailing bytes {}", rem.len()),
+        }
+    }
+
+    /// Return the text form, multibase encoded using `base`. Multikey
+    /// spec uses [Base::Base58Btc], same as the [fmt::Display] output for
+    /// public keys.
+    pub fn to_text(&self, base: Base) -> Result<String> {
+        Multibase::with_base(base, &self.encode()?)?.to_text()
+    }
+
+    /// Return the key's codec.
+    pub fn to_codec(&self) -> Multicodec {
+        self.codec
+    }
+
+    /// Return the raw key bytes.
+    pub fn as_key_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Return whether this is a private key.
+    pub fn is_private(&self) -> bool {
+        self.codec.to_code() == multicodec::ED25519_PRIV
+    }
+}
+
+/// Return the length of raw key bytes for `key` tagged multicodec `code`,
+/// None if `code` is not a supported key.
+pub fn key_len(code: u128) -> Option<usize> {
+    let n = match code {
+        multicodec::ED25519_PUB => 32,
+        multicodec::ED25519_PRIV => 32,
+        multicodec::X25519_PUB => 32,
+        multicodec::ED448_PUB => 57,
+        multicodec::X448_PUB => 56,
+        multicodec::SECP256K1_PUB => 33,
+        multicodec::P256_PUB => 33,
+        multicodec::P384_PUB => 49,
+        multicodec::P521_PUB => 67,
+        multicodec::BLS12_381_G1_PUB => 48,
+        multicodec::BLS12_381_G2_PUB => 96,
+        multicodec::BLS12_381_G1G2_PUB => 144,
+        _ => return None,
+    };
+    Some(n)
+}
+
+#[cfg(test)]
+#[path = "multikey_test.rs"]
+mod multikey_test;
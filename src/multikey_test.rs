@@ -0,0 +1,90 @@
+use super::*;
+
+// Refer did:key test vectors, (codec, key-length, multikey).
+const KEYS: [(u128, usize, &str); 6] = [
+    (
+        multicodec::ED25519_PUB,
+        32,
+        "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+    ),
+    (
+        multicodec::SECP256K1_PUB,
+        33,
+        "zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme",
+    ),
+    (
+        multicodec::P256_PUB,
+        33,
+        "zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169",
+    ),
+    (
+        multicodec::P384_PUB,
+        49,
+        "z82Lm1MpAkeJcix9K8TMiLd5NMAhnwkjjCBeWHXyu3U4oT2MVJJKXkcVBgjGhnLBn2Kaau9",
+    ),
+    (
+        multicodec::BLS12_381_G2_PUB,
+        96,
+        "zUC7EK3ZakmukHhuncwkbySmomv3FmrkmS36E4Ks5rsb6VQSRpoCrx6Hb8e2Nk6UvJFSdyw9NK1scFXJp21gNNYFjVWNgaqyGnkyhtagagCpQb5B7tagJu3HDbjQ8h5ypoHjwBb",
+    ),
+    (
+        multicodec::X25519_PUB,
+        32,
+        "z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F",
+    ),
+];
+
+#[test]
+fn test_multikey() {
+    for (code, n, text) in KEYS.iter() {
+        let key: Multikey = text.parse().unwrap();
+        assert_eq!(key.to_codec().to_code(), *code);
+        assert_eq!(key.as_key_bytes().len(), *n);
+        assert!(!key.is_private());
+        assert_eq!(&key.to_string(), text);
+        assert_eq!(format!("{:?}", key), format!("Multikey<{}>", text));
+
+        let data = key.encode().unwrap();
+        assert_eq!(Multikey::decode(&data).unwrap(), (key.clone(), &[][..]));
+
+        let other = Multikey::new(key.to_codec(), key.as_key_bytes()).unwrap();
+        assert_eq!(other, key);
+
+        let b32 = key.to_text(Base::Base32Lower).unwrap();
+        assert_eq!(Multikey::from_text(&b32).unwrap(), key);
+    }
+
+    let key = Multikey::new(multicodec::ED25519_PRIV.into(), &[7; 32]).unwrap();
+    assert!(key.is_private());
+    assert_eq!(format!("{:?}", key), "Multikey<ed25519-priv>");
+    assert_eq!(key.to_string(), "ed25519-priv");
+    let text = key.to_text(Base::Base58Btc).unwrap();
+    assert_eq!(Multikey::from_text(&text).unwrap(), key);
+}
+
+#[test]
+fn test_multikey_errors() {
+    match Multikey::new(multicodec::ED25519_PUB.into(), &[0; 31]) {
+        Err(Error::BadInput(_, _)) => (),
+        res => panic!("{:?}", res),
+    }
+    match Multikey::new(multicodec::SHA2_256.into(), &[0; 32]) {
+        Err(Error::UnknownCodec(multicodec::SHA2_256)) => (),
+        res => panic!("{:?}", res),
+    }
+
+    let key: Multikey = KEYS[0].2.parse().unwrap();
+    let data = key.encode().unwrap();
+    match Multikey::decode(&data[..20]) {
+        Err(Error::InsufficientBytes { needed: 32, .. }) => (),
+        res => panic!("{:?}", res),
+    }
+
+    let mut data = data;
+    data.push(0);
+    let text = Multibase::with_base(Base::Base58Btc, &data)
+        .unwrap()
+        .to_text()
+        .unwrap();
+    assert!(Multikey::from_text(&text).is_err());
+}
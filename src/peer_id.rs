@@ -0,0 +1,233 @@
+//! Module implement libp2p PeerId. _Refer [peer-id] spec for detail_.
+//!
+//! A peer-id is a multihash of the peer's public key, serialized as
+//! libp2p's `PublicKey` protobuf message. Keys whose serialized form is
+//! at most 42 bytes are inlined using the `identity` multihash, others
+//! are hashed using `sha2-256`.
+//!
+//! Peer-ids have two text representations, the legacy one encoding the
+//! multihash in raw base58btc (`Qm...`, `12D3KooW...`) and the one
+//! encoding it as a CIDv1 with `libp2p-key` codec, in any multibase
+//! (`bafz...`).
+//!
+//! [peer-id]: https://github.com/libp2p/specs/blob/master/peer-ids/peer-ids.md
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, result, str::FromStr};
+
+use crate::{
+    multibase::{Base, Multibase},
+    multicodec::{self, Multicodec},
+    multihash::Multihash,
+    protobuf, Error, Result,
+};
+
+/// Serialized public keys up to this length are inlined in the peer-id
+/// using the `identity` multihash.
+pub const MAX_INLINE_KEY_LENGTH: usize = 42;
+
+/// Type of public key, as enumerated by libp2p's `KeyType` protobuf.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum KeyType {
+    Rsa = 0,
+    Ed25519 = 1,
+    Secp256k1 = 2,
+    Ecdsa = 3,
+}
+
+impl KeyType {
+    fn from_code(code: u64) -> Result<KeyType> {
+        let val = match code {
+            0 => KeyType::Rsa,
+            1 => KeyType::Ed25519,
+            2 => KeyType::Secp256k1,
+            3 => KeyType::Ecdsa,
+            code => err_at!(BadInput, msg: "key type {}", code)?,
+        };
+        Ok(val)
+    }
+}
+
+/// Type implement libp2p peer identity.
+///
+/// Held as the encoded multihash of the peer's public key, ordering and
+/// hashing are done on the encoded bytes.
+#[derive(Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct PeerId {
+    mh: Vec<u8>,
+}
+
+impl fmt::Display for PeerId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "{}", self.to_base58())
+    }
+}
+
+impl fmt::Debug for PeerId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "PeerId<{}>", self.to_base58())
+    }
+}
+
+impl FromStr for PeerId {
+    type Err = Error;
+
+    /// Parse the legacy base58btc form or the multibase encoded CIDv1 form.
+    fn from_str(text: &str) -> Result<PeerId> {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some('Q'), Some('m')) | (Some('1'), Some(_)) => {
+                let bytes = err_at!(BadInput, bs58::decode(text.as_bytes()).into_vec())?;
+                PeerId::from_bytes(&bytes)
+            }
+            _ => {
+                let bytes = match Multibase::from_text(text)?.to_bytes() {
+                    Some(bytes) => bytes,
+                    None => err_at!(BadInput, msg: "peer-id {}", text)?,
+                };
+                PeerId::from_cid_bytes(&bytes)
+            }
+        }
+    }
+}
+
+impl PeerId {
+    /// Create a peer-id from the public key `data` of type `key_type`.
+    /// `data` is the key in the format specified for its type, raw bytes
+    /// for Ed25519, compressed point for Secp256k1 and DER encoded
+    /// SubjectPublicKeyInfo for RSA and ECDSA.
+    pub fn from_public_key(key_type: KeyType, data: &[u8]) -> Result<PeerId> {
+        match (key_type, data.len()) {
+            (KeyType::Ed25519, 32) | (KeyType::Secp256k1, 33) => (),
+            (KeyType::Ed25519, n) | (KeyType::Secp256k1, n) => {
+                err_at!(BadInput, msg: "{:?} key length {}", key_type, n)?
+            }
+            (_, _) => (),
+        }
+
+        PeerId::from_protobuf(&encode_public_key(key_type, data))
+    }
+
+    /// Create a peer-id from public key serialized as libp2p's
+    /// `PublicKey` protobuf message.
+    pub fn from_protobuf(data: &[u8]) -> Result<PeerId> {
+        // validate the message, before hashing it.
+        decode_public_key(data)?;
+
+        let codec = match data.len() {
+            n if n <= MAX_INLINE_KEY_LENGTH => multicodec::IDENTITY,
+            _ => multicodec::SHA2_256,
+        };
+        let mh = Multihash::new(codec.into(), data)?;
+        Ok(PeerId { mh: mh.encode()? })
+    }
+
+    /// Create a peer-id from its multihash. Only `identity`, for keys up
+    /// to [MAX_INLINE_KEY_LENGTH], and `sha2-256` multihashes are valid.
+    pub fn from_multihash(mh: Multihash) -> Result<PeerId> {
+        let (codec, digest) = mh.clone().unwrap()?;
+        match codec.to_code() {
+            multicodec::IDENTITY if digest.len() <= MAX_INLINE_KEY_LENGTH => (),
+            multicodec::SHA2_256 => (),
+            _ => err_at!(BadInput, msg: "peer-id multihash {}", mh)?,
+        }
+
+        Ok(PeerId { mh: mh.encode()? })
+    }
+
+    /// Create a peer-id from its binary form, which is the encoded
+    /// multihash.
+    pub fn from_bytes(data: &[u8]) -> Result<PeerId> {
+        match Multihash::decode(data)? {
+            (mh, []) => PeerId::from_multihash(mh),
+            (_, rem) => err_at!(BadInput, msg: "peer-id trailing bytes {}", rem.len()),
+        }
+    }
+
+    /// Create a peer-id from its binary CID form,
+    /// `<cidv1><libp2p-key><multihash>`.
+    pub fn from_cid_bytes(data: &[u8]) -> Result<PeerId> {
+        let (version, data) = Multicodec::decode(data)?;
+        match version.to_code() {
+            multicodec::CID_V1 => (),
+            _ => err_at!(BadInput, msg: "peer-id CID version {}", version.to_code())?,
+        }
+
+        let (codec, data) = Multicodec::decode(data)?;
+        match codec.to_code() {
+            multicodec::LIBP2P_KEY => PeerId::from_bytes(data),
+            _ => err_at!(BadInput, msg: "peer-id codec {}", codec),
+        }
+    }
+
+    /// Return the binary form of peer-id, which is the encoded multihash.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.mh.clone()
+    }
+
+    /// Return the binary CID form of peer-id,
+    /// `<cidv1><libp2p-key><multihash>`.
+    pub fn to_cid_bytes(&self) -> Result<Vec<u8>> {
+        let mut data = Multicodec::from_code(multicodec::CID_V1)?.encode()?;
+        data.extend_from_slice(&Multicodec::from_code(multicodec::LIBP2P_KEY)?.encode()?);
+        data.extend_from_slice(&self.mh);
+        Ok(data)
+    }
+
+    /// Return the legacy text form of peer-id, base58btc encoded multihash
+    /// without multibase prefix. Same as the [fmt::Display] output.
+    pub fn to_base58(&self) -> String {
+        bs58::encode(&self.mh).into_string()
+    }
+
+    /// Return the CID text form of peer-id, multibase encoded using `base`.
+    /// Spec recommends [Base::Base32Lower].
+    pub fn to_cid_text(&self, base: Base) -> Result<String> {
+        Multibase::with_base(base, &self.to_cid_bytes()?)?.to_text()
+    }
+
+    /// Return the multihash of the public key.
+    pub fn to_multihash(&self) -> Result<Multihash> {
+        Ok(Multihash::decode(&self.mh)?.0)
+    }
+
+    /// Return the public key, if it is inlined in the peer-id. Returned
+    /// as key-type and key-data.
+    pub fn to_public_key(&self) -> Result<Option<(KeyType, Vec<u8>)>> {
+        let (codec, digest) = self.to_multihash()?.unwrap()?;
+        match codec.to_code() {
+            multicodec::IDENTITY => Ok(Some(decode_public_key(&digest)?)),
+            _ => Ok(None),
+        }
+    }
+}
+
+// Deterministic encoding for `PublicKey { Type = 1, Data = 2 }` message.
+fn encode_public_key(key_type: KeyType, data: &[u8]) -> Vec<u8> {
+    let mut buf = vec![];
+    protobuf::encode_varint_field(&mut buf, 1, key_type as u64);
+    protobuf::encode_bytes_field(&mut buf, 2, data);
+    buf
+}
+
+pub(crate) fn decode_public_key(mut buf: &[u8]) -> Result<(KeyType, Vec<u8>)> {
+    let (mut key_type, mut data) = (None, None);
+    while !buf.is_empty() {
+        let (field, val, rem) = protobuf::decode_field(buf)?;
+        match field {
+            1 => key_type = Some(KeyType::from_code(val.to_varint(field)?)?),
+            2 => data = Some(val.to_bytes(field)?.to_vec()),
+            field => err_at!(DecodeError, msg: "public-key field {}", field)?,
+        }
+        buf = rem;
+    }
+
+    match (key_type, data) {
+        (Some(key_type), Some(data)) => Ok((key_type, data)),
+        (_, _) => err_at!(DecodeError, msg: "public-key missing fields"),
+    }
+}
+
+#[cfg(test)]
+#[path = "peer_id_test.rs"]
+mod peer_id_test;
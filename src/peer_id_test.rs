@@ -0,0 +1,113 @@
+use std::collections::BTreeSet;
+
+use super::*;
+
+// Refer peer-ids spec, both forms identify the same peer.
+const LEGACY: &str = "QmYyQSo1c1Ym7orWxLYvCrM2EmxFTANf8wXmmE7DWjhx5N";
+const CIDV1: &str = "bafzbeie5745rpv2m6tjyuugywy4d5ewrqgqqhfnf445he3omzpjbx5xqxe";
+
+#[test]
+fn test_peer_id_text() {
+    let a: PeerId = LEGACY.parse().unwrap();
+    let b: PeerId = CIDV1.parse().unwrap();
+    assert_eq!(a, b);
+
+    assert_eq!(a.to_string(), LEGACY);
+    assert_eq!(format!("{:?}", a), format!("PeerId<{}>", LEGACY));
+    assert_eq!(b.to_cid_text(Base::Base32Lower).unwrap(), CIDV1);
+    assert_eq!(
+        a.to_multihash().unwrap().to_codec().unwrap().to_code(),
+        multicodec::SHA2_256
+    );
+    assert_eq!(a.to_public_key().unwrap(), None);
+
+    let c: PeerId = a.to_cid_text(Base::Base36Lower).unwrap().parse().unwrap();
+    assert_eq!(a, c);
+    assert_eq!(PeerId::from_bytes(&a.to_bytes()).unwrap(), a);
+    assert_eq!(
+        PeerId::from_cid_bytes(&a.to_cid_bytes().unwrap()).unwrap(),
+        a
+    );
+}
+
+#[test]
+fn test_peer_id_bad_text() {
+    // CIDv1 with dag-pb codec instead of libp2p-key.
+    let data = {
+        let mut data = Multicodec::from_code(multicodec::CID_V1)
+            .unwrap()
+            .encode()
+            .unwrap();
+        data.extend_from_slice(
+            &Multicodec::from_code(multicodec::DAG_PB)
+                .unwrap()
+                .encode()
+                .unwrap(),
+        );
+        let pid: PeerId = LEGACY.parse().unwrap();
+        data.extend_from_slice(&pid.to_bytes());
+        data
+    };
+    let text = Multibase::with_base(Base::Base32Lower, &data)
+        .unwrap()
+        .to_text()
+        .unwrap();
+    assert!(text.parse::<PeerId>().is_err());
+
+    // multihash other than identity and sha2-256.
+    let mh = Multihash::new(multicodec::SHA3_256.into(), b"hello world").unwrap();
+    assert!(PeerId::from_multihash(mh.clone()).is_err());
+    let text = bs58::encode(mh.encode().unwrap()).into_string();
+    assert!(text.parse::<PeerId>().is_err());
+
+    assert!("QmYyQSo1c1Ym7orWxLYvCrM2Emx".parse::<PeerId>().is_err());
+    assert!("".parse::<PeerId>().is_err());
+}
+
+#[test]
+fn test_peer_id_public_key() {
+    let ed25519 = [7_u8; 32];
+    let pid = PeerId::from_public_key(KeyType::Ed25519, &ed25519).unwrap();
+    assert!(pid.to_string().starts_with("12D3KooW"), "{}", pid);
+    assert!(pid
+        .to_cid_text(Base::Base32Lower)
+        .unwrap()
+        .starts_with("bafzaa"));
+    assert_eq!(
+        pid.to_public_key().unwrap(),
+        Some((KeyType::Ed25519, ed25519.to_vec()))
+    );
+    assert_eq!(pid.to_string().parse::<PeerId>().unwrap(), pid);
+
+    let mut proto = vec![0x08, 0x01, 0x12, 0x20];
+    proto.extend_from_slice(&ed25519);
+    assert_eq!(PeerId::from_protobuf(&proto).unwrap(), pid);
+
+    // serialized secp256k1 keys are within the inline limit.
+    let secp256k1 = [2_u8; 33];
+    let pid = PeerId::from_public_key(KeyType::Secp256k1, &secp256k1).unwrap();
+    assert!(pid.to_string().starts_with("16Uiu2"), "{}", pid);
+
+    // RSA and ECDSA keys, in DER form, are hashed.
+    let rsa = vec![0x30_u8; 270];
+    let pid = PeerId::from_public_key(KeyType::Rsa, &rsa).unwrap();
+    assert!(pid.to_string().starts_with("Qm"), "{}", pid);
+    assert_eq!(pid.to_public_key().unwrap(), None);
+    let ecdsa = vec![0x30_u8; 91];
+    let pid = PeerId::from_public_key(KeyType::Ecdsa, &ecdsa).unwrap();
+    assert!(pid.to_string().starts_with("Qm"), "{}", pid);
+
+    assert!(PeerId::from_public_key(KeyType::Ed25519, &[7_u8; 31]).is_err());
+    assert!(PeerId::from_protobuf(&[0x08, 0x01]).is_err());
+    assert!(PeerId::from_protobuf(&[0x08, 0x09, 0x12, 0x00]).is_err());
+}
+
+#[test]
+fn test_peer_id_ord() {
+    let a = PeerId::from_public_key(KeyType::Ed25519, &[1_u8; 32]).unwrap();
+    let b = PeerId::from_public_key(KeyType::Ed25519, &[2_u8; 32]).unwrap();
+    assert!(a < b);
+
+    let set: BTreeSet<PeerId> = vec![b.clone(), a.clone(), b.clone()].into_iter().collect();
+    assert_eq!(set.into_iter().collect::<Vec<PeerId>>(), vec![a, b]);
+}
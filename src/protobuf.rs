@@ -0,0 +1,115 @@
+//! Minimal protobuf wire format, enough for dag-pb, UnixFS and IPNS
+//! messages. Varints shall be minimally encoded.
+
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::{Error, Result};
+
+/// Decoded field value, by wire type.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub(crate) enum Value<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+impl<'a> Value<'a> {
+    pub(crate) fn to_varint(self, field: u64) -> Result<u64> {
+        match self {
+            Value::Varint(val) => Ok(val),
+            _ => err_at!(DecodeError, msg: "protobuf field {} expected varint", field),
+        }
+    }
+
+    pub(crate) fn to_bytes(self, field: u64) -> Result<&'a [u8]> {
+        match self {
+            Value::Bytes(val) => Ok(val),
+            _ => err_at!(DecodeError, msg: "protobuf field {} expected bytes", field),
+        }
+    }
+
+    pub(crate) fn to_fixed32(self, field: u64) -> Result<u32> {
+        match self {
+            Value::Fixed32(val) => Ok(val),
+            _ => err_at!(DecodeError, msg: "protobuf field {} expected fixed32", field),
+        }
+    }
+}
+
+/// Decode the next field from `buf`, return the field number, its value
+/// and the remaining slice.
+pub(crate) fn decode_field(buf: &[u8]) -> Result<(u64, Value<'_>, &[u8])> {
+    let (tag, rem) = unsigned_varint::decode::u64(buf)?;
+    let field = tag >> 3;
+    if field == 0 {
+        err_at!(DecodeError, msg: "protobuf field number ZERO")?
+    }
+
+    let (val, rem) = match tag & 0x7 {
+        0 => {
+            let (val, rem) = unsigned_varint::decode::u64(rem)?;
+            (Value::Varint(val), rem)
+        }
+        1 => {
+            let (b, rem) = read_slice!(rem, 8, "protobuf")?;
+            (
+                Value::Fixed64(u64::from_le_bytes(b.try_into().unwrap())),
+                rem,
+            )
+        }
+        2 => {
+            let (n, rem) = unsigned_varint::decode::usize(rem)?;
+            let (b, rem) = read_slice!(rem, n, "protobuf")?;
+            (Value::Bytes(b), rem)
+        }
+        5 => {
+            let (b, rem) = read_slice!(rem, 4, "protobuf")?;
+            (
+                Value::Fixed32(u32::from_le_bytes(b.try_into().unwrap())),
+                rem,
+            )
+        }
+        wire => err_at!(DecodeError, msg: "protobuf wire type {}", wire)?,
+    };
+
+    Ok((field, val, rem))
+}
+
+/// Decode packed, or a single unpacked, repeated varint values.
+pub(crate) fn decode_varints(field: u64, val: Value, out: &mut Vec<u64>) -> Result<()> {
+    match val {
+        Value::Varint(val) => out.push(val),
+        Value::Bytes(mut buf) => {
+            while !buf.is_empty() {
+                let (val, rem) = unsigned_varint::decode::u64(buf)?;
+                out.push(val);
+                buf = rem;
+            }
+        }
+        _ => err_at!(DecodeError, msg: "protobuf field {} expected varint", field)?,
+    }
+    Ok(())
+}
+
+pub(crate) fn encode_varint(buf: &mut Vec<u8>, val: u64) {
+    let mut scratch = unsigned_varint::encode::u64_buffer();
+    buf.extend_from_slice(unsigned_varint::encode::u64(val, &mut scratch));
+}
+
+pub(crate) fn encode_varint_field(buf: &mut Vec<u8>, field: u64, val: u64) {
+    encode_varint(buf, field << 3);
+    encode_varint(buf, val);
+}
+
+pub(crate) fn encode_bytes_field(buf: &mut Vec<u8>, field: u64, val: &[u8]) {
+    encode_varint(buf, (field << 3) | 2);
+    encode_varint(buf, val.len() as u64);
+    buf.extend_from_slice(val);
+}
+
+pub(crate) fn encode_fixed32_field(buf: &mut Vec<u8>, field: u64, val: u32) {
+    encode_varint(buf, (field << 3) | 5);
+    buf.extend_from_slice(&val.to_le_bytes());
+}
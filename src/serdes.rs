@@ -0,0 +1,332 @@
+//! Module implement [serde] support, enabled with the `serde` feature.
+//!
+//! Human readable formats, like JSON, use the canonical text form of the
+//! values:
+//!
+//! * [Cid], as raw base58btc for CIDv0 and base32 multibase for CIDv1.
+//! * [Multiaddr], as multiaddr string, `/ip4/1.2.3.4/tcp/80`, only with
+//!   `std` feature.
+//! * [Multibase], as multibase string, `zStV1...`.
+//! * [Multicodec], as codec name, `sha2-256`, or its hex code when it
+//!   is not in the default table.
+//! * [Multihash], as base58btc multibase string of its binary form.
+//! * [Multikey], as base58btc multibase string of its binary form.
+//!   Private keys are not serialized, in any format.
+//! * [PeerId], as legacy base58btc text form.
+//!
+//! Binary formats, like bincode and CBOR, use the compact binary form
+//! returned by `encode()`. For [Multibase] that is the utf8 encoded base
+//! prefix followed by the raw data, and for [PeerId] the encoded multihash.
+
+use serde::{
+    de::{self, Deserialize, Deserializer, SeqAccess, Visitor},
+    ser::{self, Serialize, Serializer},
+};
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, marker::PhantomData, result};
+
+#[cfg(feature = "std")]
+use crate::multiaddr::Multiaddr;
+use crate::{
+    cid::Cid, multibase::Multibase, multicodec::Multicodec, multihash::Multihash,
+    multikey::Multikey, peer_id::PeerId, Error, Result,
+};
+
+// Conversion between a value and its text/binary representation.
+trait Repr: Sized {
+    const EXPECTING: &'static str;
+
+    fn to_repr_text(&self) -> Result<String>;
+
+    fn from_repr_text(text: &str) -> Result<Self>;
+
+    fn to_repr_bytes(&self) -> Result<Vec<u8>>;
+
+    fn from_repr_bytes(data: &[u8]) -> Result<Self>;
+}
+
+impl Repr for Cid {
+    const EXPECTING: &'static str = "cid text or bytes";
+
+    fn to_repr_text(&self) -> Result<String> {
+        Ok(self.to_string())
+    }
+
+    fn from_repr_text(text: &str) -> Result<Self> {
+        Cid::from_text(text)
+    }
+
+    fn to_repr_bytes(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn from_repr_bytes(data: &[u8]) -> Result<Self> {
+        match Cid::decode(data)? {
+            (cid, []) => Ok(cid),
+            (_, rem) => err_at!(DecodeError, msg: "CID trailing bytes {}", rem.len()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Repr for Multiaddr {
+    const EXPECTING: &'static str = "multiaddr text or bytes";
+
+    fn to_repr_text(&self) -> Result<String> {
+        self.to_text()
+    }
+
+    fn from_repr_text(text: &str) -> Result<Self> {
+        Multiaddr::from_text(text)
+    }
+
+    fn to_repr_bytes(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn from_repr_bytes(data: &[u8]) -> Result<Self> {
+        match Multiaddr::decode(data)? {
+            (ma, []) => Ok(ma),
+            (_, rem) => err_at!(DecodeError, msg: "multiaddr trailing bytes {}", rem.len()),
+        }
+    }
+}
+
+impl Repr for Multibase {
+    const EXPECTING: &'static str = "multibase text or bytes";
+
+    fn to_repr_text(&self) -> Result<String> {
+        self.to_text()
+    }
+
+    fn from_repr_text(text: &str) -> Result<Self> {
+        Multibase::from_text(text)
+    }
+
+    fn to_repr_bytes(&self) -> Result<Vec<u8>> {
+        let mut buf = [0_u8; 4];
+        let mut data = self
+            .to_base()
+            .code()
+            .encode_utf8(&mut buf)
+            .as_bytes()
+            .to_vec();
+        data.extend_from_slice(&self.to_bytes().unwrap_or_default());
+        Ok(data)
+    }
+
+    fn from_repr_bytes(data: &[u8]) -> Result<Self> {
+        // base prefix is a single utf8 encoded char.
+        let n = match data.first() {
+            Some(b) if *b < 0x80 => 1,
+            Some(b) if *b >= 0xf0 => 4,
+            Some(b) if *b >= 0xe0 => 3,
+            Some(_) => 2,
+            None => err_at!(DecodeError, msg: "empty multibase")?,
+        };
+        let (prefix, data) = read_slice!(data, n, "multibase-prefix")?;
+        match err_at!(DecodeError, core::str::from_utf8(prefix))?
+            .chars()
+            .next()
+        {
+            Some(ch) => Multibase::with_char(ch, data),
+            None => err_at!(DecodeError, msg: "empty multibase"),
+        }
+    }
+}
+
+impl Repr for Multicodec {
+    const EXPECTING: &'static str = "multicodec name or bytes";
+
+    fn to_repr_text(&self) -> Result<String> {
+        match self.to_name() {
+            Some(name) => Ok(name.to_string()),
+            None => Ok(format!("0x{:x}", self.to_code())),
+        }
+    }
+
+    fn from_repr_text(text: &str) -> Result<Self> {
+        match text.strip_prefix("0x") {
+            Some(hex) => {
+                let code = err_at!(BadInput, u128::from_str_radix(hex, 16), "codec {}", text)?;
+                Multicodec::from_code(code)
+            }
+            None => Multicodec::from_name(text),
+        }
+    }
+
+    fn to_repr_bytes(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn from_repr_bytes(data: &[u8]) -> Result<Self> {
+        match Multicodec::decode(data)? {
+            (codec, []) => Ok(codec),
+            (_, rem) => err_at!(DecodeError, msg: "multicodec trailing bytes {}", rem.len()),
+        }
+    }
+}
+
+impl Repr for Multihash {
+    const EXPECTING: &'static str = "multihash text or bytes";
+
+    fn to_repr_text(&self) -> Result<String> {
+        Multibase::with_base(crate::multibase::Base::Base58Btc, &self.encode()?)?.to_text()
+    }
+
+    fn from_repr_text(text: &str) -> Result<Self> {
+        match Multibase::from_text(text)?.to_bytes() {
+            Some(data) => Self::from_repr_bytes(&data),
+            None => err_at!(DecodeError, msg: "empty multihash"),
+        }
+    }
+
+    fn to_repr_bytes(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn from_repr_bytes(data: &[u8]) -> Result<Self> {
+        match Multihash::decode(data)? {
+            (mh, []) => Ok(mh),
+            (_, rem) => err_at!(DecodeError, msg: "multihash trailing bytes {}", rem.len()),
+        }
+    }
+}
+
+impl Repr for Multikey {
+    const EXPECTING: &'static str = "multikey text or bytes";
+
+    fn to_repr_text(&self) -> Result<String> {
+        check_public_key(self)?;
+        self.to_text(crate::multibase::Base::Base58Btc)
+    }
+
+    fn from_repr_text(text: &str) -> Result<Self> {
+        Multikey::from_text(text)
+    }
+
+    fn to_repr_bytes(&self) -> Result<Vec<u8>> {
+        check_public_key(self)?;
+        self.encode()
+    }
+
+    fn from_repr_bytes(data: &[u8]) -> Result<Self> {
+        match Multikey::decode(data)? {
+            (key, []) => Ok(key),
+            (_, rem) => err_at!(DecodeError, msg: "multikey trailing bytes {}", rem.len()),
+        }
+    }
+}
+
+impl Repr for PeerId {
+    const EXPECTING: &'static str = "peer-id text or bytes";
+
+    fn to_repr_text(&self) -> Result<String> {
+        Ok(self.to_base58())
+    }
+
+    fn from_repr_text(text: &str) -> Result<Self> {
+        text.parse()
+    }
+
+    fn to_repr_bytes(&self) -> Result<Vec<u8>> {
+        Ok(self.to_bytes())
+    }
+
+    fn from_repr_bytes(data: &[u8]) -> Result<Self> {
+        PeerId::from_bytes(data)
+    }
+}
+
+fn check_public_key(key: &Multikey) -> Result<()> {
+    match key.is_private() {
+        true => err_at!(Invalid, msg: "refusing to serialize {} key", key.to_codec()),
+        false => Ok(()),
+    }
+}
+
+struct ReprVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for ReprVisitor<T>
+where
+    T: Repr,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "{}", T::EXPECTING)
+    }
+
+    fn visit_str<E>(self, text: &str) -> result::Result<T, E>
+    where
+        E: de::Error,
+    {
+        T::from_repr_text(text).map_err(E::custom)
+    }
+
+    fn visit_bytes<E>(self, data: &[u8]) -> result::Result<T, E>
+    where
+        E: de::Error,
+    {
+        T::from_repr_bytes(data).map_err(E::custom)
+    }
+
+    // formats without native bytes support serialize them as sequence.
+    fn visit_seq<A>(self, mut seq: A) -> result::Result<T, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut data: Vec<u8> = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            data.push(byte)
+        }
+        T::from_repr_bytes(&data).map_err(de::Error::custom)
+    }
+}
+
+macro_rules! impl_serde {
+    ($($type:ty),*) => (
+        $(
+            impl Serialize for $type {
+                fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    if serializer.is_human_readable() {
+                        let text = self.to_repr_text().map_err(ser::Error::custom)?;
+                        serializer.serialize_str(&text)
+                    } else {
+                        let data = self.to_repr_bytes().map_err(ser::Error::custom)?;
+                        serializer.serialize_bytes(&data)
+                    }
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $type {
+                fn deserialize<D>(deserializer: D) -> result::Result<$type, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let visitor = ReprVisitor::<$type>(PhantomData);
+                    if deserializer.is_human_readable() {
+                        deserializer.deserialize_str(visitor)
+                    } else {
+                        deserializer.deserialize_bytes(visitor)
+                    }
+                }
+            }
+        )*
+    );
+}
+
+impl_serde![Cid, Multibase, Multicodec, Multihash, Multikey, PeerId];
+#[cfg(feature = "std")]
+impl_serde![Multiaddr];
+
+#[cfg(test)]
+#[path = "serdes_test.rs"]
+mod serdes_test;
@@ -0,0 +1,120 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::*;
+use crate::multicodec;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Config {
+    root: Cid,
+    legacy: Cid,
+    listen: Vec<Multiaddr>,
+    peer: PeerId,
+    codec: Multicodec,
+    hash: Multihash,
+    key: Multibase,
+    pubkey: Multikey,
+}
+
+fn config() -> Config {
+    let listen = vec![
+        Multiaddr::from_text("/ip4/0.0.0.0/tcp/4001").unwrap(),
+        Multiaddr::from_text(
+            "/dnsaddr/bootstrap.libp2p.io/p2p/QmNnooDu7bfjPFoTZYxMNLWUQJyrVwtbZg5gBMjTezGAJN",
+        )
+        .unwrap(),
+    ];
+    Config {
+        root: "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+            .parse()
+            .unwrap(),
+        legacy: "QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4"
+            .parse()
+            .unwrap(),
+        listen,
+        peer: "QmYyQSo1c1Ym7orWxLYvCrM2EmxFTANf8wXmmE7DWjhx5N"
+            .parse()
+            .unwrap(),
+        codec: multicodec::SHA2_256.into(),
+        hash: Multihash::new(multicodec::SHA2_256.into(), b"hello world").unwrap(),
+        key: Multibase::with_char('z', b"hello world").unwrap(),
+        pubkey: "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"
+            .parse()
+            .unwrap(),
+    }
+}
+
+fn roundtrip_cbor<T>(val: &T) -> T
+where
+    T: Serialize + DeserializeOwned,
+{
+    let mut data = vec![];
+    ciborium::ser::into_writer(val, &mut data).unwrap();
+    ciborium::de::from_reader(data.as_slice()).unwrap()
+}
+
+#[test]
+fn test_serde_json() {
+    let val = config();
+    let text = serde_json::to_string(&val).unwrap();
+    assert_eq!(
+        text,
+        concat!(
+            r#"{"root":"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi","#,
+            r#""legacy":"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4","#,
+            r#""listen":["/ip4/0.0.0.0/tcp/4001","#,
+            r#""/dnsaddr/bootstrap.libp2p.io/p2p/QmNnooDu7bfjPFoTZYxMNLWUQJyrVwtbZg5gBMjTezGAJN"],"#,
+            r#""peer":"QmYyQSo1c1Ym7orWxLYvCrM2EmxFTANf8wXmmE7DWjhx5N","#,
+            r#""codec":"sha2-256","#,
+            r#""hash":"zQmaozNR7DZHQK1ZcU9p7QdrshMvXqWK6gpu5rmrkPdT3L4","#,
+            r#""key":"zStV1DL6CwTryKyV","#,
+            r#""pubkey":"z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"}"#,
+        )
+    );
+    assert_eq!(serde_json::from_str::<Config>(&text).unwrap(), val);
+
+    let codec: Multicodec = 0x7fff_ffff_u128.into();
+    let text = serde_json::to_string(&codec).unwrap();
+    assert_eq!(text, r#""0x7fffffff""#);
+    assert_eq!(serde_json::from_str::<Multicodec>(&text).unwrap(), codec);
+
+    assert!(serde_json::from_str::<Multicodec>(r#""no-such-codec""#).is_err());
+    assert!(serde_json::from_str::<Multiaddr>(r#""/ip4/1.2.3""#).is_err());
+    let key = Multikey::new(multicodec::ED25519_PRIV.into(), &[7; 32]).unwrap();
+    assert!(serde_json::to_string(&key).is_err());
+    assert!(bincode::serialize(&key).is_err());
+    assert!(serde_json::from_str::<Cid>(r#""bafybeigdyrzt5sfp7udm7hu76uh7y26nf3""#).is_err());
+}
+
+#[test]
+fn test_serde_binary() {
+    let val = config();
+
+    let data = bincode::serialize(&val).unwrap();
+    assert_eq!(bincode::deserialize::<Config>(&data).unwrap(), val);
+
+    assert_eq!(roundtrip_cbor(&val), val);
+
+    // compact binary form is the encoded value.
+    let ma = val.listen[0].clone();
+    let mut data = vec![];
+    ciborium::ser::into_writer(&ma, &mut data).unwrap();
+    let value: ciborium::value::Value = ciborium::de::from_reader(data.as_slice()).unwrap();
+    assert_eq!(value, ciborium::value::Value::Bytes(ma.encode().unwrap()));
+
+    let data = bincode::serialize(&val.root).unwrap();
+    assert_eq!(&data[8..], val.root.encode().unwrap().as_slice());
+    let data = bincode::serialize(&val.legacy).unwrap();
+    assert_eq!(&data[8..10], &[0x12, 0x20]);
+
+    let data = bincode::serialize(&val.codec).unwrap();
+    assert_eq!(&data[8..], &[0x12]);
+    let data = bincode::serialize(&val.key).unwrap();
+    assert_eq!(&data[8..], b"zhello world");
+    let data = bincode::serialize(&val.pubkey).unwrap();
+    assert_eq!(&data[8..10], &[0xed, 0x01]);
+
+    assert!(
+        bincode::deserialize::<Multihash>(&bincode::serialize(&vec![0x12_u8, 0x20]).unwrap())
+            .is_err()
+    );
+}
@@ -0,0 +1,278 @@
+//! Module implement Swarm references. _Refer [swarm-cid] for detail_.
+//!
+//! A Swarm reference is the 32-byte address of the root chunk, or 64 bytes
+//! for encrypted content, address followed by the decryption key. As text
+//! it is hex encoded, without `0x` prefix.
+//!
+//! Plain references can be carried as CIDv1 with `swarm-manifest` or
+//! `swarm-feed` codec and `keccak-256` multihash holding the reference,
+//! and as EIP-1577 contenthash, `<swarm-ns><cid>`. Encrypted references do
+//! not fit in a `keccak-256` digest and have no CID form.
+//!
+//! [BzzUrl] parses `bzz://<reference-or-ens-name>/path` URLs.
+//!
+//! [swarm-cid]: https://github.com/ethersphere/swarm-cid-js
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt, result, str::FromStr};
+
+use crate::{
+    cid::{Cid, Version},
+    content_path::{check_domain, check_segment, percent_decode, percent_encode},
+    multibase::Base,
+    multicodec::{self, Multicodec},
+    multihash::Multihash,
+    Error, Result,
+};
+
+/// Length of a plain reference.
+pub const REFERENCE_LEN: usize = 32;
+
+/// Length of an encrypted reference, address followed by key.
+pub const ENCRYPTED_REFERENCE_LEN: usize = 64;
+
+/// Type of content addressed by a reference in CID form.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum ReferenceType {
+    Manifest,
+    Feed,
+}
+
+impl ReferenceType {
+    /// Create reference type from CID codec.
+    pub fn from_codec(codec: Multicodec) -> Result<ReferenceType> {
+        let val = match codec.to_code() {
+            multicodec::SWARM_MANIFEST => ReferenceType::Manifest,
+            multicodec::SWARM_FEED => ReferenceType::Feed,
+            code => err_at!(BadInput, msg: "swarm codec 0x{:x}", code)?,
+        };
+        Ok(val)
+    }
+
+    /// Return the CID codec for this reference type.
+    pub fn to_codec(&self) -> Multicodec {
+        match self {
+            ReferenceType::Manifest => multicodec::SWARM_MANIFEST.into(),
+            ReferenceType::Feed => multicodec::SWARM_FEED.into(),
+        }
+    }
+}
+
+/// Type implement Swarm reference, refer to [module][self] documentation.
+#[derive(Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Reference {
+    data: Vec<u8>,
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        for b in self.data.iter() {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        write!(f, "Reference<{}>", self)
+    }
+}
+
+impl FromStr for Reference {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Reference> {
+        Reference::from_text(text)
+    }
+}
+
+impl Reference {
+    /// Create reference from 32 bytes, or 64 bytes for encrypted content.
+    pub fn from_bytes(data: &[u8]) -> Result<Reference> {
+        match data.len() {
+            REFERENCE_LEN | ENCRYPTED_REFERENCE_LEN => Ok(Reference {
+                data: data.to_vec(),
+            }),
+            n => err_at!(BadInput, msg: "swarm reference length {}", n),
+        }
+    }
+
+    /// Parse reference from hex text, or from its CID form.
+    pub fn from_text(text: &str) -> Result<Reference> {
+        match text.len() {
+            64 | 128 if text.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                let data = Base::Base16Lower.decode(&text.to_ascii_lowercase())?;
+                Reference::from_bytes(&data)
+            }
+            _ => Ok(Reference::from_cid(&Cid::from_text(text)?)?.0),
+        }
+    }
+
+    /// Create reference from its CID form, along with its type. CID shall
+    /// be version 1 with `keccak-256` multihash.
+    pub fn from_cid(cid: &Cid) -> Result<(Reference, ReferenceType)> {
+        if cid.to_version() != Version::V1 {
+            err_at!(BadInput, msg: "swarm CID version {:?}", cid.to_version())?
+        }
+        let typ = ReferenceType::from_codec(cid.to_codec())?;
+        let (codec, digest) = cid.to_multihash()?.unwrap()?;
+        match codec.to_code() {
+            multicodec::KECCAK_256 => Ok((Reference::from_bytes(&digest)?, typ)),
+            code => err_at!(BadInput, msg: "swarm CID multihash 0x{:x}", code),
+        }
+    }
+
+    /// Return the CID form of this reference, for plain references only.
+    pub fn to_cid(&self, typ: ReferenceType) -> Result<Cid> {
+        if self.is_encrypted() {
+            err_at!(BadInput, msg: "encrypted swarm reference has no CID")?
+        }
+        let mh = Multihash::from_digest(multicodec::KECCAK_256.into(), &self.data)?;
+        Cid::new_v1(typ.to_codec(), mh)
+    }
+
+    /// Decode reference from EIP-1577 contenthash, `<swarm-ns><cid>`.
+    pub fn from_contenthash(buf: &[u8]) -> Result<(Reference, ReferenceType)> {
+        let (codec, rem) = Multicodec::decode(buf)?;
+        match codec.to_code() {
+            multicodec::SWARM_NS => (),
+            code => err_at!(BadInput, msg: "contenthash namespace 0x{:x}", code)?,
+        }
+        match Cid::decode(rem)? {
+            (cid, []) => Reference::from_cid(&cid),
+            (_, rem) => err_at!(BadInput, msg: "contenthash trailing bytes {}", rem.len()),
+        }
+    }
+
+    /// Encode reference as EIP-1577 contenthash, `<swarm-ns><cid>`.
+    pub fn to_contenthash(&self, typ: ReferenceType) -> Result<Vec<u8>> {
+        let mut buf = Multicodec::from_code(multicodec::SWARM_NS)?.encode()?;
+        buf.extend_from_slice(&self.to_cid(typ)?.encode()?);
+        Ok(buf)
+    }
+
+    /// Return the reference bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Return the address of the root chunk, first 32 bytes.
+    pub fn as_address(&self) -> &[u8] {
+        &self.data[..REFERENCE_LEN]
+    }
+
+    /// Return the decryption key, for encrypted references.
+    pub fn as_key(&self) -> Option<&[u8]> {
+        match self.is_encrypted() {
+            true => Some(&self.data[REFERENCE_LEN..]),
+            false => None,
+        }
+    }
+
+    /// Return whether this reference addresses encrypted content.
+    pub fn is_encrypted(&self) -> bool {
+        self.data.len() == ENCRYPTED_REFERENCE_LEN
+    }
+}
+
+/// Host part of a `bzz://` URL.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Host {
+    Reference(Reference),
+    Ens(String),
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        match self {
+            Host::Reference(reference) => write!(f, "{}", reference),
+            Host::Ens(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Type implement `bzz://` URL, a reference or ENS name followed by path
+/// segments. Segments are percent-encoded, query or fragment is ignored.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct BzzUrl {
+    host: Host,
+    segments: Vec<String>,
+}
+
+impl fmt::Display for BzzUrl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        let mut text = format!("bzz://{}", self.host);
+        for segment in self.segments.iter() {
+            text.push('/');
+            percent_encode(segment, &mut text);
+        }
+        write!(f, "{}", text)
+    }
+}
+
+impl FromStr for BzzUrl {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<BzzUrl> {
+        BzzUrl::from_text(text)
+    }
+}
+
+impl BzzUrl {
+    /// Create a URL for `host` and path `segments`.
+    pub fn new(host: Host, segments: Vec<String>) -> Result<BzzUrl> {
+        if let Host::Ens(name) = &host {
+            check_domain(name)?;
+        }
+        for segment in segments.iter() {
+            check_segment(segment)?;
+        }
+        Ok(BzzUrl { host, segments })
+    }
+
+    /// Parse `bzz://<host>/path` URL, host being a hex reference, a
+    /// reference in CID form or an ENS name. Trailing slash is ignored.
+    pub fn from_text(text: &str) -> Result<BzzUrl> {
+        let rest = match text.strip_prefix("bzz://") {
+            Some(rest) => rest.split(['?', '#']).next().unwrap_or(""),
+            None => err_at!(BadInput, msg: "bzz URL {:?}", text)?,
+        };
+        let rest = rest.strip_suffix('/').unwrap_or(rest);
+
+        let mut parts = rest.split('/');
+        let host = match parts.next().unwrap_or("") {
+            "" => err_at!(BadInput, msg: "bzz URL missing host {:?}", text)?,
+            s if s.contains('.') => Host::Ens(String::from(s)),
+            s => Host::Reference(s.parse()?),
+        };
+        let segments = parts.map(percent_decode).collect::<Result<Vec<String>>>()?;
+
+        BzzUrl::new(host, segments)
+    }
+
+    /// Return the URL text, same as the [fmt::Display] output.
+    pub fn to_text(&self) -> String {
+        format!("{}", self)
+    }
+
+    /// Return the host, reference or ENS name.
+    pub fn as_host(&self) -> &Host {
+        &self.host
+    }
+
+    /// Return the path segments following the host.
+    pub fn as_segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Return the path, segments joined with `/`, as looked up in the
+    /// manifest.
+    pub fn to_path(&self) -> String {
+        self.segments.join("/")
+    }
+}
+
+#[cfg(test)]
+#[path = "swarm_test.rs"]
+mod swarm_test;
@@ -0,0 +1,121 @@
+use super::*;
+
+// Published examples, swarm-cid-js README and EIP-1577.
+const REFERENCE: &str = "ca6357a08e317d15ec560fef34e4c45f8f19f01c372aa70f1da72bfa7f1a4338";
+const MANIFEST_CID: &str = "bah5acgzazjrvpieogf6rl3cwb7xtjzgel6hrt4a4g4vkody5u4v7u7y2im4a";
+const FEED_CID: &str = "bah5qcgzazjrvpieogf6rl3cwb7xtjzgel6hrt4a4g4vkody5u4v7u7y2im4a";
+const EIP1577_REFERENCE: &str = "d1de9994b4d039f6548d191eb26786769f580809256b4685ef316805265ea162";
+const EIP1577_CONTENTHASH: &str =
+    "e40101fa011b20d1de9994b4d039f6548d191eb26786769f580809256b4685ef316805265ea162";
+
+#[test]
+fn test_swarm_reference() {
+    let reference: Reference = REFERENCE.parse().unwrap();
+    assert_eq!(reference.to_string(), REFERENCE);
+    assert_eq!(
+        reference.as_bytes(),
+        &Base::Base16Lower.decode(REFERENCE).unwrap()[..]
+    );
+    assert_eq!(reference.as_address(), reference.as_bytes());
+    assert_eq!(reference.as_key(), None);
+    assert!(!reference.is_encrypted());
+    let upper = REFERENCE.to_ascii_uppercase();
+    assert_eq!(Reference::from_text(&upper).unwrap(), reference);
+
+    let testcases = vec![
+        (ReferenceType::Manifest, MANIFEST_CID),
+        (ReferenceType::Feed, FEED_CID),
+    ];
+    for (typ, text) in testcases.into_iter() {
+        let cid = reference.to_cid(typ).unwrap();
+        assert_eq!(cid.to_string(), text);
+        assert_eq!(Reference::from_cid(&cid).unwrap(), (reference.clone(), typ));
+        assert_eq!(Reference::from_text(text).unwrap(), reference);
+    }
+
+    let text = format!("{}{}", REFERENCE, EIP1577_REFERENCE);
+    let encrypted: Reference = text.parse().unwrap();
+    assert!(encrypted.is_encrypted());
+    assert_eq!(encrypted.to_string(), text);
+    assert_eq!(encrypted.as_address(), reference.as_bytes());
+    assert_eq!(
+        encrypted.as_key(),
+        Some(&Base::Base16Lower.decode(EIP1577_REFERENCE).unwrap()[..])
+    );
+    assert!(encrypted.to_cid(ReferenceType::Manifest).is_err());
+
+    assert!(Reference::from_bytes(&[0; 31]).is_err());
+    assert!(Reference::from_bytes(&[0; 33]).is_err());
+    assert!(Reference::from_text(&REFERENCE[1..]).is_err());
+    assert!(Reference::from_text(&format!("0x{}", &REFERENCE[2..])).is_err());
+
+    // CIDs that are not swarm references.
+    let cid: Cid = "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+        .parse()
+        .unwrap();
+    assert!(Reference::from_cid(&cid).is_err());
+    let mh = cid.to_multihash().unwrap();
+    let cid = Cid::new_v1(multicodec::SWARM_MANIFEST.into(), mh.clone()).unwrap();
+    assert!(Reference::from_cid(&cid).is_err());
+    let cid = Cid::new_v0(mh).unwrap();
+    assert!(Reference::from_cid(&cid).is_err());
+}
+
+#[test]
+fn test_swarm_contenthash() {
+    let buf = Base::Base16Lower.decode(EIP1577_CONTENTHASH).unwrap();
+    let (reference, typ) = Reference::from_contenthash(&buf).unwrap();
+    assert_eq!(reference.to_string(), EIP1577_REFERENCE);
+    assert_eq!(typ, ReferenceType::Manifest);
+    assert_eq!(reference.to_contenthash(typ).unwrap(), buf);
+
+    let url: BzzUrl = format!("bzz://{}", EIP1577_REFERENCE).parse().unwrap();
+    assert_eq!(url.as_host(), &Host::Reference(reference));
+
+    let mut bad = buf.clone();
+    bad[0] = 0xe3;
+    assert!(Reference::from_contenthash(&bad).is_err());
+    let mut bad = buf;
+    bad.push(0);
+    assert!(Reference::from_contenthash(&bad).is_err());
+}
+
+#[test]
+fn test_swarm_bzz_url() {
+    let testcases = vec![
+        format!("bzz://{}", REFERENCE),
+        format!("bzz://{}/index.html", REFERENCE),
+        format!("bzz://{}/a%20b/c", MANIFEST_CID),
+        "bzz://swarm.eth/docs/index.html".to_string(),
+    ];
+    for text in testcases.into_iter() {
+        let url: BzzUrl = text.parse().unwrap();
+        let want = text.replace(MANIFEST_CID, REFERENCE);
+        assert_eq!(url.to_string(), want);
+        assert_eq!(url.to_text(), want);
+    }
+
+    let url = BzzUrl::from_text(&format!("bzz://{}/a%20b/c.txt/?x=1#y", REFERENCE)).unwrap();
+    assert_eq!(url.as_host(), &Host::Reference(REFERENCE.parse().unwrap()));
+    assert_eq!(url.as_segments(), &["a b".to_string(), "c.txt".to_string()]);
+    assert_eq!(url.to_path(), "a b/c.txt");
+
+    let url = BzzUrl::from_text("bzz://swarm.eth").unwrap();
+    assert_eq!(url.as_host(), &Host::Ens("swarm.eth".to_string()));
+    assert_eq!(url.to_path(), "");
+
+    let testcases = vec![
+        "bzz://".to_string(),
+        format!("bzz:/{}", REFERENCE),
+        format!("http://{}", REFERENCE),
+        "bzz://abcd/x".to_string(),
+        "bzz://swarm..eth".to_string(),
+        format!("bzz://{}//x", REFERENCE),
+        format!("bzz://{}/%2e%2e", REFERENCE),
+        format!("bzz://{}/%zz", REFERENCE),
+        format!("bzz://{}/%+f", REFERENCE),
+    ];
+    for text in testcases.into_iter() {
+        assert!(BzzUrl::from_text(&text).is_err(), "{}", text);
+    }
+}
@@ -0,0 +1,335 @@
+//! Module implement UnixFS importer, compute CIDs for file content the way
+//! `ipfs add` does.
+//!
+//! Content is split into chunks, chunks become leaves of a DAG laid out
+//! as balanced or trickle tree, each internal node is a dag-pb node
+//! carrying UnixFS `File` data with `blocksizes` of its children. Link
+//! names are empty and link `Tsize` is the cumulative size of the
+//! sub-DAG. Defaults match `ipfs add` without options.
+
+use std::io::{self, Read};
+
+use crate::{
+    block::Block,
+    cid::{Cid, Version},
+    dag_pb::{PbLink, PbNode},
+    multicodec,
+    multihash::Multihash,
+    unixfs::{rabin::Rabin, Data, DataType},
+    Error, Result,
+};
+
+/// Default chunk size, 256KiB.
+pub const DEFAULT_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Default maximum number of links per node.
+pub const DEFAULT_MAX_LINKS: usize = 174;
+
+// Number of sub-DAGs added at each depth in trickle layout.
+const DEPTH_REPEAT: usize = 4;
+
+/// Splitting of content into chunks.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Chunker {
+    /// Fixed size chunks, `--chunker=size-<n>`.
+    Size(usize),
+    /// Content defined chunks, `--chunker=rabin-<min>-<avg>-<max>`.
+    Rabin { min: usize, avg: usize, max: usize },
+}
+
+impl Default for Chunker {
+    fn default() -> Chunker {
+        Chunker::Size(DEFAULT_CHUNK_SIZE)
+    }
+}
+
+impl Chunker {
+    /// Rabin chunker for `avg` chunk size, `--chunker=rabin-<avg>`. Same
+    /// as go-ipfs minimum is `avg/3` and maximum is `avg + avg/2`.
+    pub fn rabin(avg: usize) -> Chunker {
+        Chunker::Rabin {
+            min: avg / 3,
+            avg,
+            max: avg + (avg / 2),
+        }
+    }
+}
+
+/// DAG layout for chunked content.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum Layout {
+    #[default]
+    Balanced,
+    Trickle,
+}
+
+/// Type implement UnixFS importer, refer to [module][self] documentation.
+#[derive(Clone, Debug)]
+pub struct Importer {
+    chunker: Chunker,
+    layout: Layout,
+    version: Version,
+    raw_leaves: bool,
+    max_links: usize,
+}
+
+impl Default for Importer {
+    fn default() -> Importer {
+        Importer {
+            chunker: Chunker::default(),
+            layout: Layout::default(),
+            version: Version::V0,
+            raw_leaves: false,
+            max_links: DEFAULT_MAX_LINKS,
+        }
+    }
+}
+
+impl Importer {
+    /// Create an importer with `ipfs add` defaults, CIDv0 with fixed size
+    /// chunks of 256KiB in balanced layout.
+    pub fn new() -> Importer {
+        Importer::default()
+    }
+
+    /// Set the chunker.
+    pub fn set_chunker(&mut self, chunker: Chunker) -> &mut Self {
+        self.chunker = chunker;
+        self
+    }
+
+    /// Set the DAG layout.
+    pub fn set_layout(&mut self, layout: Layout) -> &mut Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set the CID version, `--cid-version`. Like `ipfs add`, CIDv1 turns
+    /// on raw leaves, call [Importer::set_raw_leaves] after this to
+    /// override.
+    pub fn set_cid_version(&mut self, version: Version) -> &mut Self {
+        self.version = version;
+        self.raw_leaves = version == Version::V1;
+        self
+    }
+
+    /// Encode leaves as `raw` blocks instead of dag-pb nodes,
+    /// `--raw-leaves`. Raw leaves are always CIDv1.
+    pub fn set_raw_leaves(&mut self, raw_leaves: bool) -> &mut Self {
+        self.raw_leaves = raw_leaves;
+        self
+    }
+
+    /// Set the maximum number of links per node, shall be more than one.
+    pub fn set_max_links(&mut self, max_links: usize) -> &mut Self {
+        self.max_links = max_links;
+        self
+    }
+
+    /// Import content from `r` and return the root CID. Every block of
+    /// the DAG, including the root, is passed to `emit`, children before
+    /// their parents.
+    pub fn import<R, F>(&self, r: R, emit: F) -> Result<Cid>
+    where
+        R: Read,
+        F: FnMut(Block) -> Result<()>,
+    {
+        if self.max_links < 2 {
+            err_at!(BadInput, msg: "importer max_links {}", self.max_links)?
+        }
+        let chunks = match self.chunker {
+            Chunker::Size(0) => err_at!(BadInput, msg: "importer chunk size 0")?,
+            Chunker::Size(size) => Chunks::Size(r, size),
+            Chunker::Rabin { min, avg, max } => {
+                Chunks::Rabin(io::BufReader::new(r), Box::new(Rabin::new(min, avg, max)?))
+            }
+        };
+
+        let mut builder = Builder {
+            imp: self,
+            chunks,
+            next: None,
+            emit,
+        };
+        builder.prefetch()?;
+
+        let root = match self.layout {
+            Layout::Balanced => builder.balanced()?,
+            Layout::Trickle => builder.trickle(FileNode::default(), None)?,
+        };
+        Ok(root.cid)
+    }
+}
+
+enum Chunks<R> {
+    Size(R, usize),
+    Rabin(io::BufReader<R>, Box<Rabin>),
+}
+
+impl<R: Read> Chunks<R> {
+    fn next_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        match self {
+            Chunks::Size(r, size) => {
+                let mut chunk = vec![];
+                let n = err_at!(IOError, r.take(*size as u64).read_to_end(&mut chunk))?;
+                match n {
+                    0 => Ok(None),
+                    _ => Ok(Some(chunk)),
+                }
+            }
+            Chunks::Rabin(r, rabin) => rabin.next_chunk(r),
+        }
+    }
+}
+
+// Imported sub-DAG, as seen by its parent.
+struct Child {
+    cid: Cid,
+    tsize: u64,
+    filesize: u64,
+}
+
+#[derive(Default)]
+struct FileNode {
+    links: Vec<PbLink>,
+    blocksizes: Vec<u64>,
+}
+
+impl FileNode {
+    fn add_child(&mut self, child: Child) {
+        self.links
+            .push(PbLink::new(child.cid, Some(""), Some(child.tsize)));
+        self.blocksizes.push(child.filesize);
+    }
+}
+
+struct Builder<'a, R, F> {
+    imp: &'a Importer,
+    chunks: Chunks<R>,
+    next: Option<Vec<u8>>,
+    emit: F,
+}
+
+impl<'a, R, F> Builder<'a, R, F>
+where
+    R: Read,
+    F: FnMut(Block) -> Result<()>,
+{
+    fn prefetch(&mut self) -> Result<()> {
+        self.next = self.chunks.next_chunk()?;
+        Ok(())
+    }
+
+    fn is_done(&self) -> bool {
+        self.next.is_none()
+    }
+
+    fn balanced(&mut self) -> Result<Child> {
+        if self.is_done() {
+            return self.leaf(vec![], DataType::File);
+        }
+
+        // first root is a single leaf, there after each filled root
+        // becomes the first child of a root one level deeper.
+        let mut root = self.next_leaf(DataType::File)?;
+        let mut depth = 1;
+        while !self.is_done() {
+            let mut node = FileNode::default();
+            node.add_child(root);
+            root = self.fill_balanced(node, depth)?;
+            depth += 1;
+        }
+        Ok(root)
+    }
+
+    fn fill_balanced(&mut self, mut node: FileNode, depth: usize) -> Result<Child> {
+        while node.links.len() < self.imp.max_links && !self.is_done() {
+            let child = match depth {
+                1 => self.next_leaf(DataType::File)?,
+                _ => self.fill_balanced(FileNode::default(), depth - 1)?,
+            };
+            node.add_child(child);
+        }
+        self.commit(node)
+    }
+
+    // Fill a layer of leaves, then add `DEPTH_REPEAT` sub-DAGs for each
+    // depth below `max_depth`, unlimited for the root.
+    fn trickle(&mut self, mut node: FileNode, max_depth: Option<usize>) -> Result<Child> {
+        while node.links.len() < self.imp.max_links && !self.is_done() {
+            let child = self.next_leaf(DataType::Raw)?;
+            node.add_child(child);
+        }
+
+        let mut depth = 1;
+        while max_depth.map(|max| depth < max).unwrap_or(true) && !self.is_done() {
+            for _ in 0..DEPTH_REPEAT {
+                if self.is_done() {
+                    break;
+                }
+                let child = self.trickle(FileNode::default(), Some(depth))?;
+                node.add_child(child);
+            }
+            depth += 1;
+        }
+        self.commit(node)
+    }
+
+    fn next_leaf(&mut self, data_type: DataType) -> Result<Child> {
+        let chunk = self.next.take().unwrap_or_default();
+        self.prefetch()?;
+        self.leaf(chunk, data_type)
+    }
+
+    fn leaf(&mut self, chunk: Vec<u8>, data_type: DataType) -> Result<Child> {
+        let filesize = chunk.len() as u64;
+        if self.imp.raw_leaves {
+            let block = Block::encode(multicodec::RAW.into(), multicodec::SHA2_256.into(), &chunk)?;
+            return self.put(block, filesize);
+        }
+
+        let mut data = Data::new(data_type);
+        data.filesize = Some(filesize);
+        if !chunk.is_empty() {
+            data.data = Some(chunk);
+        }
+        let node = PbNode::new(vec![], Some(data.encode()));
+        self.put_node(node, filesize)
+    }
+
+    fn commit(&mut self, node: FileNode) -> Result<Child> {
+        let mut data = Data::new(DataType::File);
+        data.filesize = Some(node.blocksizes.iter().sum());
+        data.blocksizes = node.blocksizes;
+        let filesize = data.filesize.unwrap_or(0);
+
+        let node = PbNode::new(node.links, Some(data.encode()));
+        self.put_node(node, filesize)
+    }
+
+    fn put_node(&mut self, node: PbNode, filesize: u64) -> Result<Child> {
+        let buf = node.encode()?;
+        let mh = Multihash::new(multicodec::SHA2_256.into(), &buf)?;
+        let cid = match self.imp.version {
+            Version::V0 => Cid::new_v0(mh)?,
+            Version::V1 => Cid::new_v1(multicodec::DAG_PB.into(), mh)?,
+        };
+
+        let tsize: u64 = node.links.iter().filter_map(|l| l.tsize).sum();
+        let child = self.put(Block::new_unchecked(cid, buf)?, filesize)?;
+        Ok(Child {
+            tsize: child.tsize + tsize,
+            ..child
+        })
+    }
+
+    fn put(&mut self, block: Block, filesize: u64) -> Result<Child> {
+        let child = Child {
+            cid: block.as_cid().clone(),
+            tsize: block.len() as u64,
+            filesize,
+        };
+        (self.emit)(block)?;
+        Ok(child)
+    }
+}
@@ -0,0 +1,334 @@
+//! Module implement UnixFS data format. _Refer [unixfs] spec for detail_.
+//!
+//! UnixFS describes files and directories using [dag-pb][crate::dag_pb]
+//! nodes, the node's data field carries a protobuf encoded [Data] message.
+//! Large files are chunked into a DAG whose leaves are either `raw` blocks
+//! or dag-pb nodes, large directories are sharded into a HAMT.
+//!
+//! [read_file] and [list_directory] walk such DAGs using a caller supplied
+//! block fetcher, blocks are verified against their CID. With `std`
+//! feature, [importer] builds such DAGs from file content.
+//!
+//! [unixfs]: https://github.com/ipfs/specs/blob/main/UNIXFS.md
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    block::Block,
+    cid::Cid,
+    dag_pb::{PbLink, PbNode},
+    multicodec,
+    protobuf::{self, decode_field},
+    Error, Result,
+};
+
+#[cfg(feature = "std")]
+pub mod importer;
+#[cfg(feature = "std")]
+mod rabin;
+
+/// Type of UnixFS node.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum DataType {
+    Raw,
+    Directory,
+    File,
+    Metadata,
+    Symlink,
+    HamtShard,
+}
+
+impl DataType {
+    fn from_code(code: u64) -> Result<DataType> {
+        let val = match code {
+            0 => DataType::Raw,
+            1 => DataType::Directory,
+            2 => DataType::File,
+            3 => DataType::Metadata,
+            4 => DataType::Symlink,
+            5 => DataType::HamtShard,
+            code => err_at!(DecodeError, msg: "unixfs data type {}", code)?,
+        };
+        Ok(val)
+    }
+
+    fn to_code(self) -> u64 {
+        match self {
+            DataType::Raw => 0,
+            DataType::Directory => 1,
+            DataType::File => 2,
+            DataType::Metadata => 3,
+            DataType::Symlink => 4,
+            DataType::HamtShard => 5,
+        }
+    }
+}
+
+/// Modification time, seconds relative to unix epoch.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct UnixTime {
+    pub seconds: i64,
+    pub nanos: Option<u32>,
+}
+
+/// Type implement UnixFS `Data` message.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Data {
+    pub data_type: DataType,
+    pub data: Option<Vec<u8>>,
+    pub filesize: Option<u64>,
+    /// Size of file data under each link, in the order of links.
+    pub blocksizes: Vec<u64>,
+    /// Hash function used by HAMT shards.
+    pub hash_type: Option<u64>,
+    /// Width of HAMT shards.
+    pub fanout: Option<u64>,
+    pub mode: Option<u32>,
+    pub mtime: Option<UnixTime>,
+}
+
+impl Data {
+    /// Create a new message of `data_type` with all other fields unset.
+    pub fn new(data_type: DataType) -> Data {
+        Data {
+            data_type,
+            data: None,
+            filesize: None,
+            blocksizes: Vec::default(),
+            hash_type: None,
+            fanout: None,
+            mode: None,
+            mtime: None,
+        }
+    }
+
+    /// Decode the message from data field of dag-pb `node`.
+    pub fn from_node(node: &PbNode) -> Result<Data> {
+        match &node.data {
+            Some(data) => Data::decode(data),
+            None => err_at!(DecodeError, msg: "dag-pb node missing unixfs data"),
+        }
+    }
+
+    /// Decode the message from its binary form. Unknown fields are
+    /// ignored and blocksizes are accepted in packed form as well.
+    pub fn decode(buf: &[u8]) -> Result<Data> {
+        let mut data_type = None;
+        let mut val = Data::new(DataType::Raw);
+
+        let mut rem = buf;
+        while !rem.is_empty() {
+            let (field, v, r) = decode_field(rem)?;
+            match field {
+                1 => data_type = Some(DataType::from_code(v.to_varint(field)?)?),
+                2 => val.data = Some(v.to_bytes(field)?.to_vec()),
+                3 => val.filesize = Some(v.to_varint(field)?),
+                4 => protobuf::decode_varints(field, v, &mut val.blocksizes)?,
+                5 => val.hash_type = Some(v.to_varint(field)?),
+                6 => val.fanout = Some(v.to_varint(field)?),
+                7 => val.mode = Some(v.to_varint(field)? as u32),
+                8 => val.mtime = Some(decode_time(v.to_bytes(field)?)?),
+                _ => (),
+            }
+            rem = r;
+        }
+
+        match data_type {
+            Some(data_type) => Ok(Data { data_type, ..val }),
+            None => err_at!(DecodeError, msg: "unixfs data missing type"),
+        }
+    }
+
+    /// Encode the message into its binary form.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        protobuf::encode_varint_field(&mut buf, 1, self.data_type.to_code());
+        if let Some(data) = &self.data {
+            protobuf::encode_bytes_field(&mut buf, 2, data);
+        }
+        if let Some(filesize) = self.filesize {
+            protobuf::encode_varint_field(&mut buf, 3, filesize);
+        }
+        for size in self.blocksizes.iter() {
+            protobuf::encode_varint_field(&mut buf, 4, *size);
+        }
+        if let Some(hash_type) = self.hash_type {
+            protobuf::encode_varint_field(&mut buf, 5, hash_type);
+        }
+        if let Some(fanout) = self.fanout {
+            protobuf::encode_varint_field(&mut buf, 6, fanout);
+        }
+        if let Some(mode) = self.mode {
+            protobuf::encode_varint_field(&mut buf, 7, mode as u64);
+        }
+        if let Some(mtime) = &self.mtime {
+            let mut tbuf = vec![];
+            protobuf::encode_varint_field(&mut tbuf, 1, mtime.seconds as u64);
+            if let Some(nanos) = mtime.nanos {
+                protobuf::encode_fixed32_field(&mut tbuf, 2, nanos);
+            }
+            protobuf::encode_bytes_field(&mut buf, 8, &tbuf);
+        }
+        buf
+    }
+
+    /// Validate the message. For files, filesize shall add up to the size
+    /// of inline data and blocksizes. HAMT shards shall use murmur3
+    /// hashing with a power-of-two fanout.
+    pub fn validate(&self) -> Result<()> {
+        match self.data_type {
+            DataType::File | DataType::Raw => {
+                let n = self.data.as_ref().map(|d| d.len() as u64).unwrap_or(0);
+                let total = self.blocksizes.iter().try_fold(n, |a, b| a.checked_add(*b));
+                match (self.filesize, total) {
+                    (Some(filesize), Some(total)) if filesize == total => (),
+                    (None, _) => (),
+                    (Some(filesize), _) => {
+                        err_at!(BadInput, msg: "unixfs filesize {} mismatch", filesize)?
+                    }
+                }
+            }
+            DataType::HamtShard => {
+                // murmur3-x64-64, listed as murmur3-128 in the table.
+                match self.hash_type {
+                    Some(code) if (code as u128) == multicodec::MURMUR3_128 => (),
+                    hash_type => err_at!(BadInput, msg: "unixfs hamt hash {:?}", hash_type)?,
+                }
+                match self.fanout {
+                    Some(n) if (2..=1024).contains(&n) && n.is_power_of_two() => (),
+                    fanout => err_at!(BadInput, msg: "unixfs hamt fanout {:?}", fanout)?,
+                }
+            }
+            DataType::Directory | DataType::Metadata | DataType::Symlink => (),
+        }
+
+        if !self.blocksizes.is_empty() && !matches!(self.data_type, DataType::File) {
+            err_at!(BadInput, msg: "unixfs blocksizes for {:?}", self.data_type)?
+        }
+        Ok(())
+    }
+}
+
+fn decode_time(buf: &[u8]) -> Result<UnixTime> {
+    let (mut seconds, mut nanos) = (None, None);
+
+    let mut rem = buf;
+    while !rem.is_empty() {
+        let (field, v, r) = decode_field(rem)?;
+        match field {
+            1 => seconds = Some(v.to_varint(field)? as i64),
+            2 => nanos = Some(v.to_fixed32(field)?),
+            _ => (),
+        }
+        rem = r;
+    }
+
+    match seconds {
+        Some(seconds) => Ok(UnixTime { seconds, nanos }),
+        None => err_at!(DecodeError, msg: "unixfs mtime missing seconds"),
+    }
+}
+
+/// Read the content of UnixFS file rooted at `root`. Blocks are fetched
+/// using `fetch` and verified against their CID, blocks with identity
+/// CIDs are not fetched.
+pub fn read_file<F>(root: &Cid, mut fetch: F) -> Result<Vec<u8>>
+where
+    F: FnMut(&Cid) -> Result<Vec<u8>>,
+{
+    let mut out = vec![];
+
+    // depth first, in link order, without recursion.
+    let mut stack = vec![root.clone()];
+    while let Some(cid) = stack.pop() {
+        let block = load_block(cid, &mut fetch)?;
+        match block.to_codec().to_code() {
+            multicodec::RAW => out.extend_from_slice(block.as_data()),
+            multicodec::DAG_PB => {
+                let node = PbNode::decode(block.as_data())?;
+                let data = Data::from_node(&node)?;
+                match data.data_type {
+                    DataType::File | DataType::Raw => (),
+                    data_type => err_at!(BadInput, msg: "unixfs {:?} is not a file", data_type)?,
+                }
+                if let Some(data) = &data.data {
+                    out.extend_from_slice(data);
+                }
+                stack.extend(node.links.into_iter().rev().map(|link| link.hash));
+            }
+            code => Err(Error::UnknownCodec(code))?,
+        }
+    }
+
+    Ok(out)
+}
+
+/// List entries of UnixFS directory rooted at `root`, HAMT sharded
+/// directories are walked and entry names are stripped of their shard
+/// prefix. Entries are sorted by name. Blocks are fetched using `fetch`
+/// and verified against their CID.
+pub fn list_directory<F>(root: &Cid, mut fetch: F) -> Result<Vec<PbLink>>
+where
+    F: FnMut(&Cid) -> Result<Vec<u8>>,
+{
+    let mut entries = vec![];
+
+    let mut stack = vec![root.clone()];
+    while let Some(cid) = stack.pop() {
+        let block = load_block(cid, &mut fetch)?;
+        match block.to_codec().to_code() {
+            multicodec::DAG_PB => (),
+            code => Err(Error::UnknownCodec(code))?,
+        }
+        let node = PbNode::decode(block.as_data())?;
+        let data = Data::from_node(&node)?;
+        match data.data_type {
+            DataType::Directory => entries.extend(node.links),
+            DataType::HamtShard => {
+                data.validate()?;
+                let width = hamt_prefix_len(data.fanout.unwrap_or(256));
+                let mut shards = vec![];
+                for link in node.links.into_iter() {
+                    let name = match &link.name {
+                        Some(name) if name.len() >= width && name.is_char_boundary(width) => name,
+                        _ => err_at!(DecodeError, msg: "unixfs hamt link name {:?}", link.name)?,
+                    };
+                    match &name[width..] {
+                        "" => shards.push(link.hash),
+                        entry => entries.push(PbLink {
+                            name: Some(String::from(entry)),
+                            ..link
+                        }),
+                    }
+                }
+                stack.extend(shards.into_iter().rev());
+            }
+            data_type => err_at!(BadInput, msg: "unixfs {:?} is not a directory", data_type)?,
+        }
+    }
+
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+// Link names in HAMT shards are prefixed with the upper-case hex index of
+// the bucket, padded to the width of `fanout - 1`.
+fn hamt_prefix_len(fanout: u64) -> usize {
+    format!("{:X}", fanout.saturating_sub(1)).len()
+}
+
+fn load_block<F>(cid: Cid, fetch: &mut F) -> Result<Block>
+where
+    F: FnMut(&Cid) -> Result<Vec<u8>>,
+{
+    let (codec, digest) = cid.to_multihash()?.unwrap()?;
+    let data = match codec.to_code() {
+        multicodec::IDENTITY => digest,
+        _ => fetch(&cid)?,
+    };
+    Block::new_checked(cid, data)
+}
+
+#[cfg(test)]
+#[path = "unixfs_test.rs"]
+mod unixfs_test;
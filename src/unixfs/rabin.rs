@@ -0,0 +1,140 @@
+//! Rabin fingerprint chunker, content defined chunking as used by `ipfs add
+//! --chunker=rabin`.
+//!
+//! This follows the restic chunker, forked by go-ipfs, byte for byte:
+//! first `min - 64` bytes of each chunk are skipped without fingerprinting,
+//! there after a chunk is cut when the fingerprint over a 64 byte window
+//! has its lower `log2(avg)` bits unset, or when the chunk reaches `max`.
+
+use std::io::{BufRead, Read};
+
+use crate::{Error, Result};
+
+/// Irreducible polynomial used by go-ipfs for rabin chunking.
+const IPFS_RABIN_POLY: u64 = 17437180132763653;
+
+const WINDOW_SIZE: usize = 64;
+
+pub(crate) struct Rabin {
+    min: usize,
+    max: usize,
+    splitmask: u64,
+    pol_shift: u32,
+    out_table: [u64; 256],
+    mod_table: [u64; 256],
+    // per chunk state
+    window: [u8; WINDOW_SIZE],
+    wpos: usize,
+    digest: u64,
+}
+
+impl Rabin {
+    pub(crate) fn new(min: usize, avg: usize, max: usize) -> Result<Rabin> {
+        if min < WINDOW_SIZE || avg == 0 || max < min {
+            err_at!(BadInput, msg: "rabin chunker min:{} avg:{} max:{}", min, avg, max)?
+        }
+
+        let pol = IPFS_RABIN_POLY;
+        let k = deg(pol);
+
+        let mut out_table = [0; 256];
+        for (b, item) in out_table.iter_mut().enumerate() {
+            let mut h = append_byte(0, b as u8, pol);
+            for _ in 0..(WINDOW_SIZE - 1) {
+                h = append_byte(h, 0, pol);
+            }
+            *item = h;
+        }
+        let mut mod_table = [0; 256];
+        for (b, item) in mod_table.iter_mut().enumerate() {
+            *item = poly_mod((b as u64) << k, pol) | ((b as u64) << k);
+        }
+
+        let bits = 63 - (avg as u64).leading_zeros();
+        let val = Rabin {
+            min,
+            max,
+            splitmask: (1 << bits) - 1,
+            pol_shift: k - 8,
+            out_table,
+            mod_table,
+            window: [0; WINDOW_SIZE],
+            wpos: 0,
+            digest: 0,
+        };
+        Ok(val)
+    }
+
+    /// Read the next chunk from `r`, None when `r` is exhausted.
+    pub(crate) fn next_chunk<R: BufRead>(&mut self, r: &mut R) -> Result<Option<Vec<u8>>> {
+        let mut chunk = vec![];
+
+        // bytes before the window fills up to `min` are never fingerprinted.
+        let n = (self.min - WINDOW_SIZE) as u64;
+        err_at!(IOError, r.by_ref().take(n).read_to_end(&mut chunk))?;
+        self.reset();
+
+        loop {
+            let buf = err_at!(IOError, r.fill_buf())?;
+            if buf.is_empty() {
+                break;
+            }
+
+            let mut cut = None;
+            for (i, b) in buf.iter().enumerate() {
+                self.slide(*b);
+                let n = chunk.len() + i + 1;
+                if n >= self.min && ((self.digest & self.splitmask) == 0 || n >= self.max) {
+                    cut = Some(i + 1);
+                    break;
+                }
+            }
+
+            let used = cut.unwrap_or(buf.len());
+            chunk.extend_from_slice(&buf[..used]);
+            r.consume(used);
+            if cut.is_some() {
+                break;
+            }
+        }
+
+        match chunk.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(chunk)),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window = [0; WINDOW_SIZE];
+        self.wpos = 0;
+        self.digest = 0;
+        self.slide(1);
+    }
+
+    fn slide(&mut self, b: u8) {
+        let out = self.window[self.wpos];
+        self.window[self.wpos] = b;
+        self.digest ^= self.out_table[out as usize];
+        self.wpos = (self.wpos + 1) % WINDOW_SIZE;
+
+        let index = (self.digest >> self.pol_shift) as usize;
+        self.digest = (self.digest << 8) | (b as u64);
+        self.digest ^= self.mod_table[index];
+    }
+}
+
+// Degree of polynomial `x` over GF(2), `x` shall be non-zero.
+fn deg(x: u64) -> u32 {
+    63 - x.leading_zeros()
+}
+
+fn poly_mod(mut x: u64, d: u64) -> u64 {
+    while x != 0 && deg(x) >= deg(d) {
+        x ^= d << (deg(x) - deg(d));
+    }
+    x
+}
+
+fn append_byte(h: u64, b: u8, pol: u64) -> u64 {
+    poly_mod((h << 8) | (b as u64), pol)
+}
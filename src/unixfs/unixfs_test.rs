@@ -0,0 +1,458 @@
+use std::collections::HashMap;
+
+use super::*;
+use crate::{multicodec::Multicodec, multihash::Multihash};
+
+#[derive(Default)]
+struct Store {
+    blocks: HashMap<Cid, Vec<u8>>,
+}
+
+impl Store {
+    fn put(&mut self, codec: u128, data: Vec<u8>) -> Cid {
+        let mh = Multihash::new(multicodec::SHA2_256.into(), &data).unwrap();
+        let cid = Cid::new_v1(Multicodec::from_code(codec).unwrap(), mh).unwrap();
+        self.blocks.insert(cid.clone(), data);
+        cid
+    }
+
+    fn put_node(&mut self, links: Vec<PbLink>, data: Data) -> Cid {
+        let node = PbNode::new(links, Some(data.encode()));
+        self.put(multicodec::DAG_PB, node.encode().unwrap())
+    }
+
+    fn get(&self, cid: &Cid) -> Result<Vec<u8>> {
+        match self.blocks.get(cid) {
+            Some(data) => Ok(data.clone()),
+            None => err_at!(Invalid, msg: "missing {}", cid),
+        }
+    }
+}
+
+#[test]
+fn test_unixfs_data() {
+    // `echo "hello world" | ipfs add`
+    let mut data = Data::new(DataType::File);
+    data.data = Some(b"hello world\n".to_vec());
+    data.filesize = Some(12);
+    assert!(data.validate().is_ok());
+
+    let node = PbNode::new(vec![], Some(data.encode()));
+    let buf = node.encode().unwrap();
+    let mh = Multihash::new(multicodec::SHA2_256.into(), &buf).unwrap();
+    assert_eq!(
+        Cid::new_v0(mh).unwrap().to_string(),
+        "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o"
+    );
+    assert_eq!(
+        Data::from_node(&PbNode::decode(&buf).unwrap()).unwrap(),
+        data
+    );
+
+    let mut data = Data::new(DataType::File);
+    data.blocksizes = vec![10, 20];
+    data.filesize = Some(31);
+    data.mode = Some(0o644);
+    data.mtime = Some(UnixTime {
+        seconds: -10,
+        nanos: Some(500),
+    });
+    assert!(data.validate().is_err());
+    data.filesize = Some(30);
+    assert!(data.validate().is_ok());
+    assert_eq!(Data::decode(&data.encode()).unwrap(), data);
+
+    // packed blocksizes and unknown fields.
+    let buf = [0x08, 0x02, 0x22, 0x02, 0x0a, 0x14, 0x78, 0x01];
+    let data = Data::decode(&buf).unwrap();
+    assert_eq!(data.blocksizes, vec![10, 20]);
+
+    assert!(Data::decode(&[]).is_err());
+    assert!(Data::decode(&[0x08, 0x06]).is_err());
+    assert!(Data::from_node(&PbNode::default()).is_err());
+
+    let mut data = Data::new(DataType::HamtShard);
+    assert!(data.validate().is_err());
+    data.hash_type = Some(0x22);
+    data.fanout = Some(100);
+    assert!(data.validate().is_err());
+    data.fanout = Some(256);
+    assert!(data.validate().is_ok());
+}
+
+#[test]
+fn test_unixfs_read_file() {
+    let mut store = Store::default();
+
+    let a = store.put(multicodec::RAW, b"hello ".to_vec());
+    let b = store.put(multicodec::RAW, b"world".to_vec());
+    let mut data = Data::new(DataType::File);
+    data.blocksizes = vec![6, 5];
+    data.filesize = Some(11);
+    let inner = store.put_node(
+        vec![
+            PbLink::new(a, Some(""), Some(6)),
+            PbLink::new(b, Some(""), Some(5)),
+        ],
+        data,
+    );
+
+    // identity leaf, inlined in its CID.
+    let mh = Multihash::new(multicodec::IDENTITY.into(), b"!").unwrap();
+    let c = Cid::new_v1(multicodec::RAW.into(), mh).unwrap();
+
+    let mut data = Data::new(DataType::File);
+    data.data = Some(b">> ".to_vec());
+    data.blocksizes = vec![11, 1];
+    let root = store.put_node(
+        vec![
+            PbLink::new(inner, Some(""), None),
+            PbLink::new(c, Some(""), Some(1)),
+        ],
+        data,
+    );
+
+    let content = read_file(&root, |cid| store.get(cid)).unwrap();
+    assert_eq!(content, b">> hello world!".to_vec());
+
+    // tampered block.
+    let mut bad = Store {
+        blocks: store.blocks.clone(),
+    };
+    let cid = bad
+        .blocks
+        .keys()
+        .find(|c| bad.blocks[*c] == b"world")
+        .cloned();
+    bad.blocks.insert(cid.unwrap(), b"World".to_vec());
+    assert!(read_file(&root, |cid| bad.get(cid)).is_err());
+
+    let dir = store.put_node(vec![], Data::new(DataType::Directory));
+    assert!(read_file(&dir, |cid| store.get(cid)).is_err());
+}
+
+#[test]
+fn test_unixfs_list_directory() {
+    let mut store = Store::default();
+    let file = store.put(multicodec::RAW, b"content".to_vec());
+
+    let dir = store.put_node(
+        vec![
+            PbLink::new(file.clone(), Some("b.txt"), Some(7)),
+            PbLink::new(file.clone(), Some("a.txt"), Some(7)),
+        ],
+        Data::new(DataType::Directory),
+    );
+    let entries = list_directory(&dir, |cid| store.get(cid)).unwrap();
+    let names: Vec<_> = entries.iter().map(|e| e.name.clone().unwrap()).collect();
+    assert_eq!(names, vec!["a.txt", "b.txt"]);
+
+    let mut shard = Data::new(DataType::HamtShard);
+    shard.hash_type = Some(0x22);
+    shard.fanout = Some(256);
+    let sub = store.put_node(
+        vec![
+            PbLink::new(file.clone(), Some("0Ac.txt"), Some(7)),
+            PbLink::new(file.clone(), Some("F1d.txt"), Some(7)),
+        ],
+        shard.clone(),
+    );
+    let root = store.put_node(
+        vec![
+            PbLink::new(sub, Some("1F"), None),
+            PbLink::new(file.clone(), Some("2Be.txt"), Some(7)),
+            PbLink::new(dir.clone(), Some("A0subdir"), None),
+        ],
+        shard,
+    );
+    let entries = list_directory(&root, |cid| store.get(cid)).unwrap();
+    let names: Vec<_> = entries.iter().map(|e| e.name.clone().unwrap()).collect();
+    assert_eq!(names, vec!["c.txt", "d.txt", "e.txt", "subdir"]);
+    assert_eq!(entries[3].hash, dir);
+    assert_eq!(entries[0].tsize, Some(7));
+
+    assert!(list_directory(&file, |cid| store.get(cid)).is_err());
+}
+
+fn import(imp: &importer::Importer, content: &[u8]) -> (Cid, Store) {
+    let mut store = Store::default();
+    let root = imp
+        .import(content, |block| {
+            let (cid, data) = block.unwrap();
+            store.blocks.insert(cid, data);
+            Ok(())
+        })
+        .unwrap();
+    (root, store)
+}
+
+fn root_data(root: &Cid, store: &Store) -> (PbNode, Data) {
+    let node = PbNode::decode(&store.get(root).unwrap()).unwrap();
+    let data = Data::from_node(&node).unwrap();
+    (node, data)
+}
+
+#[test]
+fn test_unixfs_importer() {
+    use crate::cid::Version;
+    use importer::{Chunker, Importer, Layout};
+
+    // `ipfs add` and `ipfs add --cid-version=1`.
+    let testcases = vec![
+        (
+            Version::V0,
+            &b"hello world\n"[..],
+            "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o",
+        ),
+        (
+            Version::V0,
+            &b""[..],
+            "QmbFMke1KXqnYyBBWxB74N4c5SBnJMVAiMNRcGu6x1AwQH",
+        ),
+        (
+            Version::V1,
+            &b""[..],
+            "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+        ),
+    ];
+    for (version, content, text) in testcases.into_iter() {
+        let mut imp = Importer::new();
+        imp.set_cid_version(version);
+        let (root, store) = import(&imp, content);
+        assert_eq!(root.to_string(), text);
+        assert_eq!(store.blocks.len(), 1);
+        assert_eq!(read_file(&root, |cid| store.get(cid)).unwrap(), content);
+    }
+
+    let mut imp = Importer::new();
+    imp.set_cid_version(Version::V1);
+    let (root, _) = import(&imp, b"hello world\n");
+    let block = Block::encode(
+        multicodec::RAW.into(),
+        multicodec::SHA2_256.into(),
+        b"hello world\n",
+    )
+    .unwrap();
+    assert_eq!(&root, block.as_cid());
+
+    // 10 chunks, 3 links per node.
+    let content: Vec<u8> = (0..40).collect();
+    let mut imp = Importer::new();
+    imp.set_chunker(Chunker::Size(4)).set_max_links(3);
+
+    let (root, store) = import(&imp, &content);
+    assert_eq!(store.blocks.len(), 17);
+    let (node, data) = root_data(&root, &store);
+    assert_eq!(data.data_type, DataType::File);
+    assert_eq!(data.filesize, Some(40));
+    assert_eq!(data.blocksizes, vec![36, 4]);
+    assert_eq!(node.links[0].name.as_deref(), Some(""));
+    let tsize: u64 = store
+        .blocks
+        .iter()
+        .filter(|(c, _)| *c != &root)
+        .map(|(_, d)| d.len() as u64)
+        .sum();
+    assert_eq!(
+        node.links.iter().map(|l| l.tsize.unwrap()).sum::<u64>(),
+        tsize
+    );
+    assert_eq!(read_file(&root, |cid| store.get(cid)).unwrap(), content);
+
+    imp.set_layout(Layout::Trickle);
+    let (root, store) = import(&imp, &content);
+    assert_eq!(store.blocks.len(), 14);
+    let (_, data) = root_data(&root, &store);
+    assert_eq!(data.blocksizes, vec![4, 4, 4, 12, 12, 4]);
+    assert_eq!(read_file(&root, |cid| store.get(cid)).unwrap(), content);
+
+    // trickle leaves are `Raw` and never collapse into the root.
+    let (root, store) = import(&imp, b"abc");
+    assert_eq!(store.blocks.len(), 2);
+    let (node, data) = root_data(&root, &store);
+    assert_eq!(data.blocksizes, vec![3]);
+    let (_, leaf) = root_data(&node.links[0].hash, &store);
+    assert_eq!(leaf.data_type, DataType::Raw);
+
+    imp.set_layout(Layout::Balanced)
+        .set_cid_version(Version::V1);
+    let (root, store) = import(&imp, &content);
+    assert_eq!(root.to_codec().to_code(), multicodec::DAG_PB);
+    let raw = store
+        .blocks
+        .keys()
+        .filter(|c| c.to_codec().to_code() == multicodec::RAW);
+    assert_eq!(raw.count(), 10);
+    assert_eq!(read_file(&root, |cid| store.get(cid)).unwrap(), content);
+
+    assert!(Importer::new()
+        .set_max_links(1)
+        .import(&b"a"[..], |_| Ok(()))
+        .is_err());
+    assert!(Importer::new()
+        .set_chunker(Chunker::Size(0))
+        .import(&b"a"[..], |_| Ok(()))
+        .is_err());
+}
+
+#[test]
+fn test_unixfs_importer_rabin() {
+    use importer::{Chunker, Importer};
+
+    let mut seed: u64 = 0x9e3779b97f4a7c15;
+    let content: Vec<u8> = (0..200_000)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed as u8
+        })
+        .collect();
+
+    let mut imp = Importer::new();
+    imp.set_chunker(Chunker::rabin(4096)).set_raw_leaves(true);
+    assert_eq!(
+        Chunker::rabin(4096),
+        Chunker::Rabin {
+            min: 1365,
+            avg: 4096,
+            max: 6144
+        }
+    );
+
+    let (root, store) = import(&imp, &content);
+    let (_, data) = root_data(&root, &store);
+    assert_eq!(data.filesize, Some(200_000));
+    let (last, sizes) = data.blocksizes.split_last().unwrap();
+    assert!(*last <= 6144);
+    assert!(
+        sizes.iter().all(|n| (1365..=6144).contains(n)),
+        "{:?}",
+        sizes
+    );
+    assert!(sizes.iter().any(|n| *n < 6144));
+    assert_eq!(read_file(&root, |cid| store.get(cid)).unwrap(), content);
+
+    // chunk boundaries are content defined, re-synced after an insert.
+    let mut shifted = b"prefix".to_vec();
+    shifted.extend_from_slice(&content);
+    let (root2, store2) = import(&imp, &shifted);
+    assert_ne!(root, root2);
+    let common = store
+        .blocks
+        .keys()
+        .filter(|c| store2.blocks.contains_key(c));
+    assert!(common.count() > data.blocksizes.len() / 2);
+
+    imp.set_chunker(Chunker::Rabin {
+        min: 10,
+        avg: 4096,
+        max: 6144,
+    });
+    assert!(imp.import(&content[..], |_| Ok(())).is_err());
+}
+
+// Layout of a file over 256KiB for the `ipfs add` options that change the
+// DAG, default, `--trickle`, `--cid-version=1` and `--chunker=rabin`. Root
+// CIDs are not cross-checked against kubo here.
+#[test]
+fn test_unixfs_importer_kubo_layout() {
+    use crate::cid::Version;
+    use importer::{Chunker, Importer, Layout, DEFAULT_CHUNK_SIZE};
+
+    // sha2-256 of each big-endian u32 counter, concatenated, 2097280 bytes:
+    //
+    //   python3 -c 'import hashlib, sys; sys.stdout.buffer.write(b"".join(
+    //     hashlib.sha256(i.to_bytes(4, "big")).digest() for i in range(65540)))'
+    //
+    // The root CIDs below are produced by this importer and agree with an
+    // independent re-implementation of the go-unixfs layouts and the
+    // go-ipfs-chunker rabin splitter. They are yet to be cross-checked
+    // against `ipfs add --only-hash -Q [--trickle|--cid-version=1|--chunker=rabin]`.
+    let content: Vec<u8> = (0..65540_u32)
+        .flat_map(|i| {
+            let mh = Multihash::new(multicodec::SHA2_256.into(), &i.to_be_bytes());
+            mh.unwrap().to_digest().unwrap()
+        })
+        .collect();
+    let mut chunk_sizes: Vec<u64> = vec![262_144; 8];
+    chunk_sizes.push(128);
+
+    // default, CIDv0 root and dag-pb `File` leaves.
+    let (root, store) = import(&Importer::new(), &content);
+    assert_eq!(
+        root.to_string(),
+        "QmW39Cejqz93f9eW7tiVqCHb4cM9pKfqKdiCx64hPctpva"
+    );
+    assert_eq!(store.blocks.len(), 10);
+    let (node, data) = root_data(&root, &store);
+    assert_eq!(data.filesize, Some(content.len() as u64));
+    assert_eq!(data.blocksizes, chunk_sizes);
+    for link in node.links.iter() {
+        assert_eq!(link.hash.to_version(), Version::V0);
+        let (_, leaf) = root_data(&link.hash, &store);
+        assert_eq!(leaf.data_type, DataType::File);
+    }
+    let balanced = root;
+
+    // `--trickle`, leaves are `Raw`, up to max-links sit on the root.
+    let mut imp = Importer::new();
+    imp.set_layout(Layout::Trickle);
+    let (root, store) = import(&imp, &content);
+    assert_ne!(root, balanced);
+    assert_eq!(
+        root.to_string(),
+        "QmcGXMyefrySproQ4TUUhGkYoyjdn6fTHybk434PvFB7si"
+    );
+    assert_eq!(store.blocks.len(), 10);
+    let (node, data) = root_data(&root, &store);
+    assert_eq!(data.blocksizes, chunk_sizes);
+    let (_, leaf) = root_data(&node.links[0].hash, &store);
+    assert_eq!(leaf.data_type, DataType::Raw);
+    assert_eq!(read_file(&root, |cid| store.get(cid)).unwrap(), content);
+
+    // `--cid-version=1` implies `--raw-leaves`, `bafk..` leaves under a
+    // `bafy..` root.
+    let mut imp = Importer::new();
+    imp.set_cid_version(Version::V1);
+    let (root, store) = import(&imp, &content);
+    assert_eq!(
+        root.to_string(),
+        "bafybeiflhvy5aa7vv2koazu7dsi2ft4brz3qbiqxltafjmfomdlusqa32m"
+    );
+    let (node, data) = root_data(&root, &store);
+    assert_eq!(data.blocksizes, chunk_sizes);
+    for link in node.links.iter() {
+        assert!(
+            link.hash.to_string().starts_with("bafkrei"),
+            "{}",
+            link.hash
+        );
+    }
+    assert_eq!(read_file(&root, |cid| store.get(cid)).unwrap(), content);
+
+    // `--chunker=rabin` is `rabin-262144`, that is `rabin-87381-262144-393216`.
+    let rabin = Chunker::rabin(DEFAULT_CHUNK_SIZE);
+    assert_eq!(
+        rabin,
+        Chunker::Rabin {
+            min: 87_381,
+            avg: 262_144,
+            max: 393_216
+        }
+    );
+    let mut imp = Importer::new();
+    imp.set_chunker(rabin);
+    let (root, store) = import(&imp, &content);
+    assert_eq!(
+        root.to_string(),
+        "QmdUFbkWQ2qkBEvWydHG5f4H5u24gpRnqmA9jsUs8hYCLM"
+    );
+    let (_, data) = root_data(&root, &store);
+    assert_eq!(
+        data.blocksizes,
+        vec![393_216, 251_784, 127_894, 393_216, 393_216, 348_356, 189_598]
+    );
+    assert_eq!(data.filesize, Some(content.len() as u64));
+    assert_eq!(read_file(&root, |cid| store.get(cid)).unwrap(), content);
+}
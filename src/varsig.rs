@@ -0,0 +1,210 @@
+//! Module implement Varsig. _Refer [varsig] spec for detail_.
+//!
+//! A varsig is a self-describing signature. The signature bytes are
+//! prefixed with a header made of unsigned varints, the `varsig`
+//! multicodec followed by codes for the signing algorithm, the hash
+//! algorithm and the encoding of the signed payload:
+//!
+//! ```text
+//! <varsig><algorithm><hash><encoding><signature-bytes>
+//! ```
+//!
+//! Signing algorithms are identified by the multicodec of their public
+//! key, like `ed25519-pub`, and hash algorithms by their multihash code.
+//!
+//! This module implements a subset of the spec. Supported algorithms are
+//! listed by [signature_len], RSA, whose header carries the signature
+//! length, is not supported. Supported payload encodings are `raw`,
+//! `dag-pb`, `dag-cbor`, `dag-json`, [JWT] and [EIP191]. Headers using
+//! others fail with `NotImplemented`.
+//!
+//! [varsig]: https://github.com/ChainAgnostic/varsig
+
+use alloc::vec::Vec;
+
+use crate::{
+    multicodec::{self, Multicodec},
+    Error, Result,
+};
+
+/// Payload encoding for JSON Web Tokens, not in the multicodec table.
+pub const JWT: u128 = 0x6a77;
+
+/// Payload encoding for EIP-191 signed data, not in the multicodec table.
+pub const EIP191: u128 = 0xe191;
+
+// Multicodec for RSA public keys, not in the multicodec table.
+const RSA_PUB: u128 = 0x1205;
+
+/// Type implement the varsig header, describing how a signature was
+/// produced.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct Header {
+    algorithm: Multicodec,
+    hash: Multicodec,
+    encoding: Multicodec,
+}
+
+impl Header {
+    /// Create a new header, validate the combination of signing
+    /// `algorithm`, `hash` algorithm and payload `encoding`.
+    pub fn new(algorithm: Multicodec, hash: Multicodec, encoding: Multicodec) -> Result<Header> {
+        let val = Header {
+            algorithm,
+            hash,
+            encoding,
+        };
+        val.validate()?;
+        Ok(val)
+    }
+
+    /// Decode varsig header and return the remaining unparsed slice.
+    pub fn decode(buf: &[u8]) -> Result<(Header, &[u8])> {
+        let (codec, rem) = Multicodec::decode(buf)?;
+        match codec.to_code() {
+            multicodec::VARSIG => (),
+            code => err_at!(DecodeError, msg: "varsig prefix 0x{:x}", code)?,
+        }
+        let (algorithm, rem) = Multicodec::decode(rem)?;
+        let (hash, rem) = Multicodec::decode(rem)?;
+        let (encoding, rem) = Multicodec::decode(rem)?;
+
+        Ok((Header::new(algorithm, hash, encoding)?, rem))
+    }
+
+    /// Encode varsig header.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut data = Multicodec::from_code(multicodec::VARSIG)?.encode()?;
+        data.extend_from_slice(&self.algorithm.encode()?);
+        data.extend_from_slice(&self.hash.encode()?);
+        data.extend_from_slice(&self.encoding.encode()?);
+        Ok(data)
+    }
+
+    /// Validate the header, signing algorithm shall be a supported key
+    /// codec, hash shall be a multihash code, and for algorithms that
+    /// mandate a hash algorithm, the mandated one. Payload encoding shall
+    /// be one of the encodings listed in [module][self] documentation.
+    pub fn validate(&self) -> Result<()> {
+        let (algorithm, hash) = (self.algorithm.to_code(), self.hash.to_code());
+        match signature_len(algorithm) {
+            Some(_) => (),
+            None if algorithm == RSA_PUB => err_at!(NotImplemented, msg: "varsig rsa")?,
+            None => Err(Error::UnknownCodec(algorithm))?,
+        }
+        if !multicodec::multihash_codes().contains(&hash) {
+            err_at!(BadInput, msg: "varsig hash {}", self.hash)?
+        }
+        match (algorithm, hash) {
+            (multicodec::ED25519_PUB, multicodec::SHA2_512) => (),
+            (multicodec::ED448_PUB, multicodec::SHAKE_256) => (),
+            (multicodec::ED25519_PUB, _) | (multicodec::ED448_PUB, _) => {
+                err_at!(BadInput, msg: "varsig {} with hash {}", self.algorithm, self.hash)?
+            }
+            (_, _) => (),
+        }
+        match self.encoding.to_code() {
+            multicodec::RAW | multicodec::DAG_PB | multicodec::DAG_CBOR | multicodec::DAG_JSON => {
+                Ok(())
+            }
+            JWT | EIP191 => Ok(()),
+            _ => err_at!(NotImplemented, msg: "varsig encoding {}", self.encoding),
+        }
+    }
+
+    /// Return the signing algorithm, as public key codec.
+    pub fn to_algorithm(&self) -> Multicodec {
+        self.algorithm
+    }
+
+    /// Return the hash algorithm, as multihash code.
+    pub fn to_hash(&self) -> Multicodec {
+        self.hash
+    }
+
+    /// Return the payload encoding.
+    pub fn to_encoding(&self) -> Multicodec {
+        self.encoding
+    }
+
+    /// Return the length of signatures produced by this header's
+    /// algorithm.
+    pub fn to_signature_len(&self) -> usize {
+        // validated when constructing Header.
+        signature_len(self.algorithm.to_code()).unwrap()
+    }
+}
+
+/// Type implement self-describing signature, varsig header followed by
+/// the signature bytes.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Varsig {
+    header: Header,
+    signature: Vec<u8>,
+}
+
+impl Varsig {
+    /// Create a varsig from `header` and raw `signature` bytes, whose
+    /// length shall match the header's algorithm.
+    pub fn new(header: Header, signature: &[u8]) -> Result<Varsig> {
+        match header.to_signature_len() {
+            n if n == signature.len() => Ok(Varsig {
+                header,
+                signature: signature.to_vec(),
+            }),
+            n => err_at!(
+                BadInput,
+                msg: "varsig {} signature length {}, expected {}",
+                header.algorithm,
+                signature.len(),
+                n
+            ),
+        }
+    }
+
+    /// Decode varsig and return the remaining unparsed slice.
+    pub fn decode(buf: &[u8]) -> Result<(Varsig, &[u8])> {
+        let (header, rem) = Header::decode(buf)?;
+        let (signature, rem) = read_slice!(rem, header.to_signature_len(), "varsig")?;
+        Ok((Varsig::new(header, signature)?, rem))
+    }
+
+    /// Encode varsig, header followed by the signature bytes.
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut data = self.header.encode()?;
+        data.extend_from_slice(&self.signature);
+        Ok(data)
+    }
+
+    /// Return the varsig header.
+    pub fn to_header(&self) -> Header {
+        self.header
+    }
+
+    /// Return the raw signature bytes.
+    pub fn as_signature(&self) -> &[u8] {
+        &self.signature
+    }
+}
+
+/// Return the length of signatures for signing algorithm `code`, which
+/// is the codec of the public key, None if not supported. For BLS12-381
+/// signatures are on the curve group other than the public key's.
+pub fn signature_len(code: u128) -> Option<usize> {
+    let n = match code {
+        multicodec::ED25519_PUB => 64,
+        multicodec::ED448_PUB => 114,
+        multicodec::SECP256K1_PUB => 64,
+        multicodec::P256_PUB => 64,
+        multicodec::P384_PUB => 96,
+        multicodec::P521_PUB => 132,
+        multicodec::BLS12_381_G1_PUB => 96,
+        multicodec::BLS12_381_G2_PUB => 48,
+        _ => return None,
+    };
+    Some(n)
+}
+
+#[cfg(test)]
+#[path = "varsig_test.rs"]
+mod varsig_test;
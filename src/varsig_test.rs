@@ -0,0 +1,132 @@
+use super::*;
+
+#[test]
+fn test_varsig() {
+    let header = Header::new(
+        multicodec::ED25519_PUB.into(),
+        multicodec::SHA2_512.into(),
+        multicodec::DAG_CBOR.into(),
+    )
+    .unwrap();
+    assert_eq!(header.encode().unwrap(), vec![0x34, 0xed, 0x01, 0x13, 0x71]);
+    assert_eq!(header.to_signature_len(), 64);
+
+    let sig = Varsig::new(header, &[0xab; 64]).unwrap();
+    let mut data = sig.encode().unwrap();
+    assert_eq!(data.len(), 5 + 64);
+    data.extend_from_slice(b"payload");
+
+    let (val, rem) = Varsig::decode(&data).unwrap();
+    assert_eq!(val, sig);
+    assert_eq!(rem, b"payload");
+    assert_eq!(
+        val.to_header().to_algorithm().to_code(),
+        multicodec::ED25519_PUB
+    );
+    assert_eq!(val.to_header().to_hash().to_code(), multicodec::SHA2_512);
+    assert_eq!(
+        val.to_header().to_encoding().to_code(),
+        multicodec::DAG_CBOR
+    );
+    assert_eq!(val.as_signature(), &[0xab; 64][..]);
+
+    // multi-byte varint codes.
+    let header = Header::new(
+        multicodec::P256_PUB.into(),
+        multicodec::SHA2_256.into(),
+        multicodec::DAG_JSON.into(),
+    )
+    .unwrap();
+    let data = header.encode().unwrap();
+    assert_eq!(data, vec![0x34, 0x80, 0x24, 0x12, 0xa9, 0x02]);
+    assert_eq!(Header::decode(&data).unwrap(), (header, &[][..]));
+
+    // encodings outside the multicodec table.
+    let header = Header::new(
+        multicodec::SECP256K1_PUB.into(),
+        multicodec::KECCAK_256.into(),
+        EIP191.into(),
+    )
+    .unwrap();
+    let data = header.encode().unwrap();
+    assert_eq!(data, vec![0x34, 0xe7, 0x01, 0x1b, 0x91, 0xc3, 0x03]);
+    assert_eq!(Header::decode(&data).unwrap(), (header, &[][..]));
+    let header = Header::new(
+        multicodec::P256_PUB.into(),
+        multicodec::SHA2_256.into(),
+        JWT.into(),
+    )
+    .unwrap();
+    assert_eq!(Header::decode(&header.encode().unwrap()).unwrap().0, header);
+}
+
+#[test]
+fn test_varsig_errors() {
+    let testcases = [
+        // eddsa with sha2-256
+        (
+            multicodec::ED25519_PUB,
+            multicodec::SHA2_256,
+            multicodec::RAW,
+        ),
+        // not a signing algorithm
+        (
+            multicodec::X25519_PUB,
+            multicodec::SHA2_256,
+            multicodec::RAW,
+        ),
+        // not a hash algorithm
+        (
+            multicodec::SECP256K1_PUB,
+            multicodec::DAG_PB,
+            multicodec::RAW,
+        ),
+        // not a payload encoding
+        (
+            multicodec::SECP256K1_PUB,
+            multicodec::SHA2_256,
+            multicodec::SHA2_256,
+        ),
+    ];
+    for (a, h, e) in testcases.iter() {
+        assert!(Header::new((*a).into(), (*h).into(), (*e).into()).is_err());
+    }
+    match Header::new(
+        multicodec::X25519_PUB.into(),
+        multicodec::SHA2_256.into(),
+        multicodec::RAW.into(),
+    ) {
+        Err(Error::UnknownCodec(multicodec::X25519_PUB)) => (),
+        res => panic!("{:?}", res),
+    }
+    let testcases = [
+        (RSA_PUB, multicodec::SHA2_256, multicodec::RAW),
+        (multicodec::P256_PUB, multicodec::SHA2_256, multicodec::CBOR),
+    ];
+    for (a, h, e) in testcases.iter() {
+        match Header::new((*a).into(), (*h).into(), (*e).into()) {
+            Err(Error::NotImplemented(_, _)) => (),
+            res => panic!("{:?}", res),
+        }
+    }
+
+    // bad prefix
+    assert!(Header::decode(&[0x33, 0xed, 0x01, 0x13, 0x71]).is_err());
+    // truncated header
+    assert!(Header::decode(&[0x34, 0xed, 0x01, 0x13]).is_err());
+
+    let header = Header::new(
+        multicodec::SECP256K1_PUB.into(),
+        multicodec::KECCAK_256.into(),
+        multicodec::RAW.into(),
+    )
+    .unwrap();
+    assert!(Varsig::new(header, &[0; 65]).is_err());
+
+    let mut data = header.encode().unwrap();
+    data.extend_from_slice(&[0; 63]);
+    match Varsig::decode(&data) {
+        Err(Error::InsufficientBytes { needed: 64, .. }) => (),
+        res => panic!("{:?}", res),
+    }
+}